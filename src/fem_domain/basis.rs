@@ -1,13 +1,19 @@
-/// Implementations of the `HierBasisFnSpace` Trait (MaxOrthoShapeFn can be added as a Feature on the Nightly Toolchain)
+/// Implementations of the `HierCurlBasisFnSpace` Trait
 pub mod hierarchical_basis_fns;
 
-use super::domain::mesh::{
-    elem::Elem,
-    space::{M2D, V2D},
+use super::domain::{
+    dof::basis_spec::BasisDir,
+    mesh::{
+        elem::Elem,
+        space::{M2D, V2D},
+        MeshAccessError,
+    },
 };
 use crate::fem_problem::integration::glq::{gauss_quadrature_points, scale_gauss_quad_points};
 
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 use std::sync::{Arc, Mutex};
 
 /// A Trait to define the functional space used to compose a [HierCurlBasisFn]
@@ -50,6 +56,8 @@ pub trait HierCurlBasisFnSpace: Clone + Sync + Send + std::fmt::Debug {
 /// * Handle interpolation between parametric spaces to set up inter-layer integration
 /// * Cache computed [HierBasisFn]'s to avoid re-computation.
 ///
+/// [HierBasisFn]s can be sampled over the sampler's default Gauss-Legendre-Quadrature grid, or over any other grid (see [BasisFnSampler::sample_basis_fn_over_grid]). The cache is keyed by the grid as well as the [Elem]s.
+///
 pub struct BasisFnSampler<B: HierBasisFn> {
     /// Maximum u-directed expansion order. [HierBasisFn]s Generated by this sampler will be defined up to this order in `i`
     pub i_max: usize,
@@ -57,14 +65,23 @@ pub struct BasisFnSampler<B: HierBasisFn> {
     pub j_max: usize,
     /// Whether the 2nd derivatives of the [HierBasisFn]s will be computed. If true, the endpoints [-1, +1] are also defined along both axes!                 
     pub compute_d2: bool,
+    /// Number of Gauss Legendre Quadrature points in the default grid along the u- and v-directions
+    glq_dims: [usize; 2],
     /// Gauss Legendre Quadrature points evaluated along u-direction. Defined from (-1 to +1)         
     u_points: Vec<f64>,
     /// Gauss Legendre Quadrature points evaluated along v-direction. Defined from (-1 to +1)
     v_points: Vec<f64>,
 
+    glq_rules: Arc<Mutex<HashMap<usize, Arc<GlqRule>>>>,
     computed: Arc<Mutex<HashMap<BSDescription, Arc<B>>>>,
 }
 
+// Gauss Legendre Quadrature points and weights along one direction
+struct GlqRule {
+    points: Vec<f64>,
+    weights: Vec<f64>,
+}
+
 impl<B: HierBasisFn> BasisFnSampler<B> {
     /// Construct a Basis Function Sampler with the following parameters:
     ///
@@ -80,56 +97,98 @@ impl<B: HierBasisFn> BasisFnSampler<B> {
         num_v_points: Option<usize>,
         compute_2nd_derivs: bool,
     ) -> (Self, [Vec<f64>; 2]) {
-        let (u_points, u_weights) = gauss_quadrature_points(
+        let glq_dims = [
             num_u_points.unwrap_or_else(|| default_ngq(i_max)),
-            compute_2nd_derivs,
-        );
-        let (v_points, v_weights) = gauss_quadrature_points(
             num_v_points.unwrap_or_else(|| default_ngq(j_max)),
-            compute_2nd_derivs,
-        );
+        ];
+
+        let mut sampler = Self {
+            i_max,
+            j_max,
+            compute_d2: compute_2nd_derivs,
+            glq_dims,
+            u_points: Vec::new(),
+            v_points: Vec::new(),
+            glq_rules: Arc::new(Mutex::new(HashMap::new())),
+            computed: Arc::new(Mutex::new(HashMap::new())),
+        };
 
-        (
-            Self {
-                i_max,
-                j_max,
-                compute_d2: compute_2nd_derivs,
-                u_points,
-                v_points,
-                computed: Arc::new(Mutex::new(HashMap::new())),
-            },
-            [u_weights, v_weights],
-        )
+        let [u_rule, v_rule] = glq_dims.map(|n| sampler.glq_rule(n));
+        sampler.u_points = u_rule.points.clone();
+        sampler.v_points = v_rule.points.clone();
+
+        (sampler, [u_rule.weights.clone(), v_rule.weights.clone()])
+    }
+
+    /// Get the Gauss-Legendre-Quadrature points along the u and v directions. Defined from (-1 to +1)
+    pub fn glq_points(&self) -> [&[f64]; 2] {
+        [&self.u_points, &self.v_points]
+    }
+
+    /// Get the number of Gauss-Legendre-Quadrature points in the default grid along the u and v directions
+    pub fn glq_dims(&self) -> [usize; 2] {
+        self.glq_dims
+    }
+
+    /// Get the Gauss-Legendre-Quadrature weights for a grid with `glq_dims` points along the u and v directions
+    pub fn glq_weights(&self, glq_dims: [usize; 2]) -> [Vec<f64>; 2] {
+        glq_dims.map(|n| self.glq_rule(n).weights.clone())
+    }
+
+    /// Get the Gauss-Legendre-Quadrature points for a grid with `glq_dims` points along the u and v directions. Defined from (-1 to +1)
+    pub fn grid_glq_points(&self, glq_dims: [usize; 2]) -> [Vec<f64>; 2] {
+        glq_dims.map(|n| self.glq_rule(n).points.clone())
     }
 
     /// Generate or retrieve a [HierBasisFn] defined over an [Elem]. Can be defined over a subset of the `Elem`.
     pub fn sample_basis_fn(&mut self, elem: &Elem, over_desc_elem: Option<&Elem>) -> Arc<B> {
-        let desc = BSDescription::new(elem, over_desc_elem);
+        self.sample_basis_fn_over_grid(elem, over_desc_elem, self.glq_dims)
+    }
+
+    /// Generate or retrieve a [HierBasisFn] defined over an [Elem] (or a subset of the `Elem`), sampled over a Gauss-Legendre-Quadrature grid with `glq_dims` points along the u and v directions
+    pub fn sample_basis_fn_over_grid(
+        &mut self,
+        elem: &Elem,
+        over_desc_elem: Option<&Elem>,
+        glq_dims: [usize; 2],
+    ) -> Arc<B> {
+        let desc = BSDescription::new(elem, over_desc_elem, glq_dims);
+        let compute = || {
+            let [u_rule, v_rule] = glq_dims.map(|n| self.glq_rule(n));
+            B::defined_over(
+                elem,
+                over_desc_elem,
+                [&u_rule.points, &v_rule.points],
+                [self.i_max, self.j_max],
+                self.compute_d2,
+            )
+        };
 
         match self.computed.lock() {
             Ok(mut comp_guard) => {
                 if let Some(computed_bs) = comp_guard.get(&desc) {
                     computed_bs.clone()
                 } else {
-                    let bs = B::defined_over(
-                        elem,
-                        over_desc_elem,
-                        [&self.u_points, &self.v_points],
-                        [self.i_max, self.j_max],
-                        self.compute_d2,
-                    );
-                    comp_guard.insert(desc.clone(), Arc::new(bs));
-                    comp_guard.get(&desc).unwrap().clone()
+                    let bs = Arc::new(compute());
+                    comp_guard.insert(desc, bs.clone());
+                    bs
                 }
             }
             // fallback on computing directly, if MutexGuard is not available.
-            Err(_) => Arc::new(B::defined_over(
-                elem,
-                over_desc_elem,
-                [&self.u_points, &self.v_points],
-                [self.i_max, self.j_max],
-                self.compute_d2,
-            )),
+            Err(_) => Arc::new(compute()),
+        }
+    }
+
+    // Generate or retrieve the 1D Gauss Legendre Quadrature rule with n points (plus the endpoints if 2nd derivatives are computed)
+    fn glq_rule(&self, n: usize) -> Arc<GlqRule> {
+        let compute = || {
+            let (points, weights) = gauss_quadrature_points(n, self.compute_d2);
+            Arc::new(GlqRule { points, weights })
+        };
+
+        match self.glq_rules.lock() {
+            Ok(mut rules_guard) => rules_guard.entry(n).or_insert_with(compute).clone(),
+            Err(_) => compute(),
         }
     }
 }
@@ -140,8 +199,10 @@ impl<B: HierBasisFn> Clone for BasisFnSampler<B> {
             i_max: self.i_max,
             j_max: self.j_max,
             compute_d2: self.compute_d2,
+            glq_dims: self.glq_dims,
             u_points: self.u_points.clone(),
             v_points: self.v_points.clone(),
+            glq_rules: self.glq_rules.clone(),
             computed: self.computed.clone(),
         }
     }
@@ -155,21 +216,25 @@ struct BSDescription {
     sample: Option<[usize; 2]>,
     base_id: usize,
     desc_id: Option<usize>,
+    glq_dims: [usize; 2],
 }
 
 impl BSDescription {
-    pub fn new(elem: &Elem, sampled_over: Option<&Elem>) -> Self {
+    pub fn new(elem: &Elem, sampled_over: Option<&Elem>, glq_dims: [usize; 2]) -> Self {
         Self {
             space: [elem.nodes[0], elem.nodes[3]],
             sample: sampled_over.map(|so_elem| [so_elem.nodes[0], so_elem.nodes[3]]),
             base_id: elem.id,
             desc_id: sampled_over.map(|so_elem| so_elem.id),
+            glq_dims,
         }
     }
 }
 
-// 4 * the maximum order (rounded up to the nearest power of 2)
-fn default_ngq(max_order: usize) -> usize {
+/// The default number of Gauss-Legendre-Quadrature points used to integrate Basis Functions up to some expansion order
+///
+/// 4 * the maximum order (rounded up to the nearest power of 2)
+pub(crate) fn default_ngq(max_order: usize) -> usize {
     let conv = (max_order * 4) as f32;
     let conv_p2 = conv.log2().ceil() as i32;
 
@@ -218,6 +283,7 @@ pub struct HierCurlBasisFn<BSpace: HierCurlBasisFnSpace> {
     pub para_scale: V2D,
     u_shapes: BSpace,
     v_shapes: BSpace,
+    separable_jac: bool,
 }
 
 impl<BSpace: HierCurlBasisFnSpace> HierCurlBasisFn<BSpace> {
@@ -346,6 +412,24 @@ impl<BSpace: HierCurlBasisFnSpace> HierCurlBasisFn<BSpace> {
     pub fn vu_ratio(&self, [m, n]: [usize; 2]) -> f64 {
         self.jac[m][n].v[1] / self.jac[m][n].u[0]
     }
+
+    /// Whether the Jacobian is diagonal and constant over all sample points.
+    ///
+    /// If so, the Basis Functions (and their curls) are products of 1D functions along `u` and `v` with constant coefficients,
+    /// and integrals over pairs of Basis Functions can be factored into products of 1D integrals
+    pub fn has_separable_jacobian(&self) -> bool {
+        self.separable_jac
+    }
+
+    /// The u-directed 1D shape functions (`N_i(u)` and `T_i(u)`), sampled at the u-coordinates of the sample points
+    pub fn u_shapes(&self) -> &BSpace {
+        &self.u_shapes
+    }
+
+    /// The v-directed 1D shape functions (`N_j(v)` and `T_j(v)`), sampled at the v-coordinates of the sample points
+    pub fn v_shapes(&self) -> &BSpace {
+        &self.v_shapes
+    }
 }
 
 impl<BSpace: HierCurlBasisFnSpace> HierBasisFn for HierCurlBasisFn<BSpace> {
@@ -412,6 +496,13 @@ impl<BSpace: HierCurlBasisFnSpace> HierBasisFn for HierCurlBasisFn<BSpace> {
             .map(|row| row.iter().map(|v| v.det()).collect())
             .collect();
 
+        let separable_jac = t.iter().flatten().all(|jac| {
+            jac.u[1] == 0.0
+                && jac.v[0] == 0.0
+                && jac.u[0] == t[0][0].u[0]
+                && jac.v[1] == t[0][0].v[1]
+        });
+
         Self {
             jac: t,
             jac_inv: ti,
@@ -419,6 +510,357 @@ impl<BSpace: HierCurlBasisFnSpace> HierBasisFn for HierCurlBasisFn<BSpace> {
             para_scale: V2D::from([u_glq_scale, v_glq_scale]),
             u_shapes: BSpace::with(i_max, &u_points_scaled, compute_d2),
             v_shapes: BSpace::with(j_max, &v_points_scaled, compute_d2),
+            separable_jac,
+        }
+    }
+}
+
+/// The value and curl of a single [HierCurlBasisFn] at some point (with respect to real space)
+#[derive(Clone, Copy, Debug)]
+pub struct BasisFnSample {
+    /// The vectorial value of the Basis Function
+    pub value: V2D,
+    /// The (scalar) curl of the Basis Function
+    pub curl: f64,
+}
+
+/// Evaluate a single Curl-Conforming Basis Function at an arbitrary point in parametric space
+///
+/// Unlike a [HierCurlBasisFn] built by a [BasisFnSampler], the point does not need to be one of the Gauss-Legendre-Quadrature points. This is useful for point probes, custom quadrature rules, and plotting on non-uniform grids.
+///
+/// # Arguments
+/// * `elem` : the [Elem] over which the Basis Function is defined
+/// * `desc_elem` : a descendant of `elem` whose parametric space contains the point. If `None`, the point is defined in `elem`'s parametric space
+/// * `dir` : the vectorial direction of the Basis Function (only `U` and `V` directed functions are supported)
+/// * `[i, j]` : the u- and v-directed expansion orders of the Basis Function
+/// * `[u, v]` : the point, in the parametric space of `desc_elem` (or `elem`). Must fall within [-1, 1] along both axes
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::unit();
+/// mesh.global_h_refinement(HRef::T);
+///
+/// // the center of Elem 0's North-East child (Elem 4) is at (0.5, 0.5) in Elem 0's parametric space
+/// let over_child = evaluate_hcurl_basis_fn::<HierPoly>(
+///     &mesh.elems[0],
+///     Some(&mesh.elems[4]),
+///     BasisDir::U,
+///     [1, 2],
+///     [0.0, 0.0],
+/// )
+/// .unwrap();
+/// let over_parent = evaluate_hcurl_basis_fn::<HierPoly>(
+///     &mesh.elems[0],
+///     None,
+///     BasisDir::U,
+///     [1, 2],
+///     [0.5, 0.5],
+/// )
+/// .unwrap();
+///
+/// assert!((over_child.value.x() - over_parent.value.x()).abs() < 1e-14);
+/// assert!((over_child.curl - over_parent.curl).abs() < 1e-14);
+/// ```
+pub fn evaluate_hcurl_basis_fn<BSpace: HierCurlBasisFnSpace>(
+    elem: &Elem,
+    desc_elem: Option<&Elem>,
+    dir: BasisDir,
+    [i, j]: [usize; 2],
+    [u, v]: [f64; 2],
+) -> Result<BasisFnSample, BasisEvalError> {
+    const TOLERANCE: f64 = 1e-12;
+    if u.abs() > 1.0 + TOLERANCE || v.abs() > 1.0 + TOLERANCE {
+        return Err(BasisEvalError::PointOutsideElem(u, v));
+    }
+    if dir == BasisDir::W {
+        return Err(BasisEvalError::UnsupportedDirection(dir));
+    }
+
+    // map the point into elem's parametric space
+    let [u_elem, v_elem] = match desc_elem {
+        Some(desc_elem_ref) if desc_elem_ref.id != elem.id => {
+            if !desc_elem_ref
+                .loc_stack()
+                .iter()
+                .any(|(ancestor_id, _)| *ancestor_id == elem.id)
+            {
+                return Err(BasisEvalError::NotADescendant(elem.id, desc_elem_ref.id));
+            }
+
+            let [[u_min, u_max], [v_min, v_max]] = desc_elem_ref.relative_parametric_range(elem.id);
+            [
+                scale_gauss_quad_points(&[u], u_min, u_max).1[0],
+                scale_gauss_quad_points(&[v], v_min, v_max).1[0],
+            ]
+        }
+        _ => [u, v],
+    };
+
+    // Elems are rectangular, so the Jacobian is diagonal
+    let jac = elem.parametric_mapping(V2D::from([u_elem, v_elem]), elem.parametric_range());
+
+    let max_order = std::cmp::max(std::cmp::max(i, j), 1);
+    let u_shapes = BSpace::with(max_order, &[u_elem], true);
+    let v_shapes = BSpace::with(max_order, &[v_elem], true);
+
+    let sample = local_basis_sample(
+        dir,
+        [i, j],
+        [&u_shapes, &v_shapes],
+        [0, 0],
+        [jac.u[0], jac.v[1]],
+    )
+    .ok_or(BasisEvalError::UnsupportedDirection(dir))?;
+
+    Ok(BasisFnSample {
+        value: sample.field,
+        curl: sample.curl,
+    })
+}
+
+/// The value and derivatives of a single Basis Function at some point (with respect to real space)
+pub(crate) struct LocalSample {
+    pub field: V2D,
+    pub curl: f64,
+    pub div: f64,
+    pub curl_curl: V2D,
+}
+
+// Sample a u- or v-directed Basis Function with orders `[i, j]` at the `[m, n]`th point of its 1D shape functions (which must include 2nd derivatives)
+// over an Elem with the diagonal Jacobian: `diag(a, b)`. Returns `None` for w-directed Basis Functions.
+pub(crate) fn local_basis_sample<BSpace: HierCurlBasisFnSpace>(
+    dir: BasisDir,
+    [i, j]: [usize; 2],
+    [u_shapes, v_shapes]: [&BSpace; 2],
+    [m, n]: [usize; 2],
+    [a, b]: [f64; 2],
+) -> Option<LocalSample> {
+    match dir {
+        BasisDir::U => {
+            let [nu, nu_d1] = [u_shapes.norm(i, m), u_shapes.norm_d1(i, m)];
+            let [tv, tv_d1, tv_d2] = [
+                v_shapes.tang(j, n),
+                v_shapes.tang_d1(j, n),
+                v_shapes.tang_d2(j, n),
+            ];
+            Some(LocalSample {
+                field: V2D::from([nu * tv / a, 0.0]),
+                curl: -nu * tv_d1 / (a * b),
+                div: nu_d1 * tv / (a * a),
+                curl_curl: V2D::from([-nu * tv_d2 / (a * b * b), nu_d1 * tv_d1 / (a * a * b)]),
+            })
+        }
+        BasisDir::V => {
+            let [tu, tu_d1, tu_d2] = [
+                u_shapes.tang(i, m),
+                u_shapes.tang_d1(i, m),
+                u_shapes.tang_d2(i, m),
+            ];
+            let [nv, nv_d1] = [v_shapes.norm(j, n), v_shapes.norm_d1(j, n)];
+            Some(LocalSample {
+                field: V2D::from([0.0, tu * nv / b]),
+                curl: tu_d1 * nv / (a * b),
+                div: tu * nv_d1 / (b * b),
+                curl_curl: V2D::from([tu_d1 * nv_d1 / (a * b * b), -tu_d2 * nv / (a * a * b)]),
+            })
+        }
+        BasisDir::W => None,
+    }
+}
+
+/// Error Type for evaluating Basis Functions at arbitrary points
+#[derive(Debug)]
+pub enum BasisEvalError {
+    PointOutsideElem(f64, f64),
+    NotADescendant(usize, usize),
+    UnsupportedDirection(BasisDir),
+    MeshAccess(MeshAccessError),
+}
+
+impl fmt::Display for BasisEvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PointOutsideElem(u, v) => write!(
+                f,
+                "Parametric point ({}, {}) falls outside of [-1, 1]; Cannot evaluate Basis Function!",
+                u, v
+            ),
+            Self::NotADescendant(elem_id, desc_id) => write!(
+                f,
+                "Elem {} is not a descendant of Elem {}; Cannot evaluate Basis Function!",
+                desc_id, elem_id
+            ),
+            Self::UnsupportedDirection(dir) => write!(
+                f,
+                "{}-directed Basis Functions are not supported; Cannot evaluate Basis Function!",
+                dir
+            ),
+            Self::MeshAccess(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for BasisEvalError {}
+
+impl From<MeshAccessError> for BasisEvalError {
+    fn from(err: MeshAccessError) -> Self {
+        Self::MeshAccess(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hierarchical_basis_fns::{chebyshev::HierChebyshev, lobatto::HierLobatto};
+    use super::*;
+    use crate::fem_domain::domain::fields::for_each_local_basis_fn;
+    use crate::fem_domain::domain::mesh::{h_refinement::HRef, space::Point, Mesh};
+    use crate::fem_domain::domain::{ContinuityCondition, Domain};
+
+    fn refined_test_domain() -> Domain {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 4]).unwrap();
+        mesh.global_h_refinement(HRef::t());
+        mesh.h_refine_elems(vec![4], HRef::u()).unwrap();
+        Domain::from_mesh(mesh, ContinuityCondition::HCurl)
+    }
+
+    #[test]
+    fn matches_sampled_basis_fns() {
+        let domain = refined_test_domain();
+        let elem = &domain.mesh.elems[0];
+        let desc_elem = &domain.mesh.elems[domain.mesh.elems.len() - 1];
+
+        // arbitrary (non-GLQ) points in the descendant's parametric space
+        let us = [-1.0, -0.73, 0.1, 0.6];
+        let vs = [-0.35, 0.0, 0.92, 1.0];
+
+        let sampled: HierCurlBasisFn<HierLobatto> =
+            HierCurlBasisFn::defined_over(elem, Some(desc_elem), [&us, &vs], [3, 4], false);
+
+        for i in 0..3 {
+            for j in 0..=4 {
+                for (m, u) in us.iter().enumerate() {
+                    for (n, v) in vs.iter().enumerate() {
+                        let expected = sampled.f_u([i, j], [m, n]);
+                        let sample = evaluate_hcurl_basis_fn::<HierLobatto>(
+                            elem,
+                            Some(desc_elem),
+                            BasisDir::U,
+                            [i, j],
+                            [*u, *v],
+                        )
+                        .unwrap();
+
+                        assert!((sample.value.x() - expected.x()).abs() < 1e-12);
+                        assert!((sample.value.y() - expected.y()).abs() < 1e-12);
+                    }
+                }
+            }
         }
     }
+
+    #[test]
+    fn sampler_grid_cache() {
+        let domain = refined_test_domain();
+        let [elem, desc_elem] = [&domain.mesh.elems[0], &domain.mesh.elems[4]];
+
+        let (mut sampler, [u_weights, v_weights]): (
+            BasisFnSampler<HierCurlBasisFn<HierLobatto>>,
+            _,
+        ) = BasisFnSampler::with(3, 4, None, None, false);
+        assert_eq!(sampler.glq_dims(), [default_ngq(3), default_ngq(4)]);
+        assert_eq!(
+            sampler.glq_weights(sampler.glq_dims()),
+            [u_weights, v_weights]
+        );
+
+        // the default grid is shared with `sample_basis_fn`
+        let default_grid = sampler.sample_basis_fn(elem, Some(desc_elem));
+        let same_grid =
+            sampler.sample_basis_fn_over_grid(elem, Some(desc_elem), sampler.glq_dims());
+        assert!(Arc::ptr_eq(&default_grid, &same_grid));
+
+        // other grids are sampled and cached separately
+        let coarse_grid = sampler.sample_basis_fn_over_grid(elem, Some(desc_elem), [4, 6]);
+        assert!(!Arc::ptr_eq(&default_grid, &coarse_grid));
+        assert_eq!(coarse_grid.det_jac.len(), 4);
+        assert_eq!(coarse_grid.det_jac[0].len(), 6);
+        assert!(Arc::ptr_eq(
+            &coarse_grid,
+            &sampler
+                .clone()
+                .sample_basis_fn_over_grid(elem, Some(desc_elem), [4, 6])
+        ));
+
+        let [coarse_u_weights, coarse_v_weights] = sampler.glq_weights([4, 6]);
+        assert_eq!(coarse_u_weights.len(), 4);
+        assert!((coarse_v_weights.iter().sum::<f64>() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn matches_field_sampling() {
+        let domain = refined_test_domain();
+
+        // a real-space point inside one of the leaf Elems
+        let [x, y] = [0.31, 0.12];
+        let leaf_elem_id = domain.leaf_elem_containing(&Point::new(x, y)).unwrap();
+        let [p0, p1] = domain.mesh.elem_diag_points(leaf_elem_id).unwrap();
+        let uv = [
+            2.0 * (x - p0.x) / (p1.x - p0.x) - 1.0,
+            2.0 * (y - p0.y) / (p1.y - p0.y) - 1.0,
+        ];
+
+        let mut num_visited = 0;
+        for_each_local_basis_fn::<HierChebyshev, _>(
+            &domain,
+            leaf_elem_id,
+            [&[x], &[y]],
+            |bs, _, expected| {
+                let sample = domain
+                    .evaluate_basis_spec::<HierChebyshev>(bs, Some(leaf_elem_id), uv)
+                    .unwrap();
+
+                assert!((sample.value.x() - expected.field.x()).abs() < 1e-10);
+                assert!((sample.value.y() - expected.field.y()).abs() < 1e-10);
+                assert!((sample.curl - expected.curl).abs() < 1e-9 * (1.0 + expected.curl.abs()));
+                num_visited += 1;
+            },
+        );
+
+        assert!(num_visited > 0);
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        let domain = refined_test_domain();
+        let [elem_0, elem_1] = [&domain.mesh.elems[0], &domain.mesh.elems[1]];
+        let child_of_0 = &domain.mesh.elems[elem_0.child_ids().unwrap()[0]];
+
+        assert!(matches!(
+            evaluate_hcurl_basis_fn::<HierLobatto>(elem_0, None, BasisDir::U, [0, 2], [1.2, 0.0]),
+            Err(BasisEvalError::PointOutsideElem(_, _))
+        ));
+        assert!(matches!(
+            evaluate_hcurl_basis_fn::<HierLobatto>(elem_0, None, BasisDir::W, [0, 2], [0.0, 0.0]),
+            Err(BasisEvalError::UnsupportedDirection(BasisDir::W))
+        ));
+        assert!(matches!(
+            evaluate_hcurl_basis_fn::<HierLobatto>(
+                elem_1,
+                Some(child_of_0),
+                BasisDir::V,
+                [2, 0],
+                [0.0, 0.0]
+            ),
+            Err(BasisEvalError::NotADescendant(1, _))
+        ));
+
+        let bs = domain.basis_specs.iter().flatten().next().unwrap();
+        assert!(matches!(
+            domain.evaluate_basis_spec::<HierLobatto>(bs, Some(10_000), [0.0, 0.0]),
+            Err(BasisEvalError::MeshAccess(_))
+        ));
+    }
 }
@@ -198,450 +198,594 @@ pub mod poly {
     }
 }
 
-#[cfg(feature = "max_ortho_basis")]
-mod max_ortho {
-    use super::super::HierCurlBasisFnSpace;
+/// Three-term recurrences for the orthogonal polynomial families used by the [lobatto], [chebyshev] and [max_ortho] spaces
+mod recurrence {
+    /// Values and derivatives of a family of polynomials over a set of points (indexed as `[order][point]`)
+    #[derive(Clone, Debug)]
+    pub struct PolyTable {
+        pub values: Vec<Vec<f64>>,
+        pub d1: Vec<Vec<f64>>,
+        pub d2: Vec<Vec<f64>>,
+    }
 
-    //https://ieeexplore.ieee.org/stamp/stamp.jsp?tp=&arnumber=6470651
-    const EUC_NORM_COEFFS: [f64; 12] = [
-        0.968246, 2.561738, 0.838525, 4.248161, 0.816397, 5.882766, 0.808509, 1.0, 1.0, 1.0, 1.0,
-        1.0,
-    ];
+    impl PolyTable {
+        /// Evaluate `p_0 = 1`, `p_1 = x` and `p_(n+1) = a(n) * x * p_n - c(n) * p_(n-1)` up to order `n_max`
+        ///
+        /// The derivatives are found by differentiating the recurrence directly, so they remain well defined at the endpoints
+        pub fn three_term<A, C>(n_max: usize, points: &[f64], compute_d2: bool, a: A, c: C) -> Self
+        where
+            A: Fn(usize) -> f64,
+            C: Fn(usize) -> f64,
+        {
+            let num_points = points.len();
 
-    const Q_NUMERATORS: [&[i32]; 12] = [
-        &[-1, 0, 1],
-        &[0, -3, 0, 3],
-        &[-1, 0, -5, 0, 6],
-        &[0, -3, 0, -7, 0, 10],
-        &[-1, 0, -5, 0, -9, 0, 15],
-        &[0, -3, 0, -7, 0, -11, 0, 21],
-        &[-1, 0, -5, 0, -9, 0, -13, 0, 28],
-        &[0, -3, 0, -7, 0, -11, 0, -15, 0, 36],
-        &[-1, 0, -5, 0, -9, 0, -13, 0, -17, 0, 40],
-        &[0, -3, 0, -7, 0, -11, 0, -15, 0, -19, 0, 55],
-        &[-1, 0, -5, 0, -9, 0, -13, 0, -17, 0, -21, 0, 66],
-        &[0, -3, 0, -7, 0, -11, 0, -15, 0, -19, 0, -23, 0, 72],
-    ];
-    const Q_DENOMINATORS: [i32; 12] = [1, 3, 6, 10, 15, 21, 28, 36, 40, 55, 66, 72];
+            let mut values = vec![vec![1.0; num_points], points.to_vec()];
+            let mut d1 = vec![vec![0.0; num_points], vec![1.0; num_points]];
+            let mut d2 = vec![vec![0.0; num_points], vec![0.0; num_points]];
 
-    const fn get_q_weight_vector<const DIM: usize>() -> [f64; DIM] {
-        let mut coeffs = [0.0; DIM];
-        let mut i = 0;
-        let index = DIM - 3;
+            for n in 1..n_max {
+                let (a_n, c_n) = (a(n), c(n));
 
-        while i < DIM {
-            coeffs[i] = (Q_NUMERATORS[index][i] as f64) / (Q_DENOMINATORS[index] as f64);
-            i += 1;
+                values.push(
+                    points
+                        .iter()
+                        .enumerate()
+                        .map(|(p, x)| a_n * x * values[n][p] - c_n * values[n - 1][p])
+                        .collect(),
+                );
+                d1.push(
+                    points
+                        .iter()
+                        .enumerate()
+                        .map(|(p, x)| a_n * (values[n][p] + x * d1[n][p]) - c_n * d1[n - 1][p])
+                        .collect(),
+                );
+                if compute_d2 {
+                    d2.push(
+                        points
+                            .iter()
+                            .enumerate()
+                            .map(|(p, x)| {
+                                a_n * (2.0 * d1[n][p] + x * d2[n][p]) - c_n * d2[n - 1][p]
+                            })
+                            .collect(),
+                    );
+                }
+            }
+
+            values.truncate(n_max + 1);
+            d1.truncate(n_max + 1);
+            if compute_d2 {
+                d2.truncate(n_max + 1);
+            } else {
+                d2 = Vec::new();
+            }
+
+            Self { values, d1, d2 }
+        }
+
+        /// The Legendre Polynomials `P_n(x)`
+        pub fn legendre(n_max: usize, points: &[f64], compute_d2: bool) -> Self {
+            Self::three_term(
+                n_max,
+                points,
+                compute_d2,
+                |n| (2 * n + 1) as f64 / (n + 1) as f64,
+                |n| n as f64 / (n + 1) as f64,
+            )
         }
 
-        coeffs
+        /// The Chebyshev Polynomials of the first kind `T_n(x)`
+        pub fn chebyshev(n_max: usize, points: &[f64], compute_d2: bool) -> Self {
+            Self::three_term(n_max, points, compute_d2, |_| 2.0, |_| 1.0)
+        }
+
+        /// Build the hierarchical tangential functions from this family:
+        ///
+        /// * `1 - x` for `n = 0`
+        /// * `1 + x` for `n = 1`
+        /// * `sum_k w_k * p_k(x)` for `n >= 2`, where `weights(n)` gives the (sparse) list of `(k, w_k)` pairs
+        ///
+        /// The weights should be chosen such that the higher order functions vanish at both endpoints
+        pub fn tangential<W>(&self, points: &[f64], weights: W) -> Self
+        where
+            W: Fn(usize) -> Vec<(usize, f64)>,
+        {
+            let num_points = points.len();
+            let compute_d2 = !self.d2.is_empty();
+            let combine = |table: &[Vec<f64>], weights: &[(usize, f64)]| -> Vec<f64> {
+                let mut sum = vec![0.0; num_points];
+                for (k, w_k) in weights.iter() {
+                    for (s, p_k) in sum.iter_mut().zip(table[*k].iter()) {
+                        *s += w_k * p_k;
+                    }
+                }
+                sum
+            };
+
+            let mut values = vec![
+                points.iter().map(|x| 1.0 - x).collect(),
+                points.iter().map(|x| 1.0 + x).collect(),
+            ];
+            let mut d1 = vec![vec![-1.0; num_points], vec![1.0; num_points]];
+            let mut d2 = if compute_d2 {
+                vec![vec![0.0; num_points], vec![0.0; num_points]]
+            } else {
+                Vec::new()
+            };
+
+            for n in 2..self.values.len() {
+                let weights_n = weights(n);
+                values.push(combine(&self.values, &weights_n));
+                d1.push(combine(&self.d1, &weights_n));
+                if compute_d2 {
+                    d2.push(combine(&self.d2, &weights_n));
+                }
+            }
+
+            Self { values, d1, d2 }
+        }
+
+        /// Build the hierarchical tangential functions with `scale(n) * (p_n(x) - p_(n-2)(x))` for `n >= 2`
+        ///
+        /// The higher order functions vanish at both endpoints as long as `p_n(±1) = (±1)^n`
+        pub fn bubbles<S: Fn(usize) -> f64>(&self, points: &[f64], scale: S) -> Self {
+            self.tangential(points, |n| vec![(n, scale(n)), (n - 2, -scale(n))])
+        }
+
+        /// Scale the `n`th order polynomial by `scale(n)`
+        pub fn scaled<S: Fn(usize) -> f64>(mut self, scale: S) -> Self {
+            let compute_d2 = !self.d2.is_empty();
+            for n in 0..self.values.len() {
+                let s = scale(n);
+                self.values[n].iter_mut().for_each(|v| *v *= s);
+                self.d1[n].iter_mut().for_each(|v| *v *= s);
+                if compute_d2 {
+                    self.d2[n].iter_mut().for_each(|v| *v *= s);
+                }
+            }
+            self
+        }
     }
+}
 
-    const Q_WEIGHTS: [&[f64]; 11] = [
-        &get_q_weight_vector::<3>(),
-        &get_q_weight_vector::<4>(),
-        &get_q_weight_vector::<5>(),
-        &get_q_weight_vector::<6>(),
-        &get_q_weight_vector::<7>(),
-        &get_q_weight_vector::<8>(),
-        &get_q_weight_vector::<9>(),
-        &get_q_weight_vector::<10>(),
-        &get_q_weight_vector::<11>(),
-        &get_q_weight_vector::<12>(),
-        &get_q_weight_vector::<13>(),
-    ];
+pub mod lobatto {
+    use super::super::HierCurlBasisFnSpace;
+    use super::recurrence::PolyTable;
 
-    /// An advanced Curl-Conforming Hierarchical Basis Function Space
+    /// A Curl-Conforming Hierarchical Basis Function Space built from Legendre Polynomials:
     ///
-    /// The T and N functions are defined using Legendre Polynomials
+    /// N_i(x) = `sqrt((2i + 1) / 2) * P_i(x)`
+    ///
+    /// T_i(x) =
+    /// * `1 - x` for `i = 0`
+    /// * `1 + x` for `i = 1`
+    /// * `(P_i(x) - P_(i-2)(x)) / sqrt(2 (2i - 1))` for `i >= 2` (the integrated-Legendre or Lobatto shape functions)
+    ///
+    /// The normal functions are orthonormal over [-1, 1], and the derivatives of the higher order tangential functions are the normalized Legendre Polynomials of one lower order (i.e. `T_i'(x) = N_(i-1)(x)`). This keeps the mass and curl-curl matrices well conditioned at high expansion orders.
     ///
-    /// Based on: https://ieeexplore.ieee.org/stamp/stamp.jsp?tp=&arnumber=6470651
     #[derive(Clone, Debug)]
-    pub struct HierMaxOrtho {
-        pub q_fn: QFunction,
-        pub l_fn: LegendrePoly,
+    pub struct HierLobatto {
+        norm: PolyTable,
+        tang: PolyTable,
     }
 
-    impl HierCurlBasisFnSpace for HierMaxOrtho {
+    impl HierCurlBasisFnSpace for HierLobatto {
         fn with(max_order: usize, points: &[f64], compute_d2: bool) -> Self {
-            let l_fn = LegendrePoly::with(max_order as u8, points, compute_d2);
+            let legendre = PolyTable::legendre(max_order, points, compute_d2);
+
             Self {
-                q_fn: QFunction::with(max_order as u8, points, &l_fn, compute_d2),
-                l_fn,
+                tang: legendre.bubbles(points, |n| 1.0 / (2.0 * (2 * n - 1) as f64).sqrt()),
+                norm: legendre.scaled(|n| ((2 * n + 1) as f64 / 2.0).sqrt()),
             }
         }
 
         fn norm(&self, n: usize, p: usize) -> f64 {
-            self.l_fn.l[n][p]
+            self.norm.values[n][p]
         }
         fn norm_d1(&self, n: usize, p: usize) -> f64 {
-            self.l_fn.d1[n][p]
+            self.norm.d1[n][p]
         }
         fn norm_d2(&self, n: usize, p: usize) -> f64 {
-            self.l_fn.d2[n][p]
+            self.norm.d2[n][p]
         }
 
         fn tang(&self, n: usize, p: usize) -> f64 {
-            self.q_fn.q[n][p]
+            self.tang.values[n][p]
         }
         fn tang_d1(&self, n: usize, p: usize) -> f64 {
-            self.q_fn.d1[n][p]
+            self.tang.d1[n][p]
         }
         fn tang_d2(&self, n: usize, p: usize) -> f64 {
-            self.q_fn.d2[n][p]
+            self.tang.d2[n][p]
         }
     }
+}
+
+pub mod chebyshev {
+    use super::super::HierCurlBasisFnSpace;
+    use super::recurrence::PolyTable;
 
+    /// A Curl-Conforming Hierarchical Basis Function Space built from Chebyshev Polynomials of the first kind:
+    ///
+    /// N_i(x) = `T_i(x)` (the Chebyshev Polynomial)
+    ///
+    /// T_i(x) =
+    /// * `1 - x` for `i = 0`
+    /// * `1 + x` for `i = 1`
+    /// * `(T_i(x) - T_(i-2)(x)) / (2 (i - 1))` for `i >= 2`
+    ///
+    /// The Chebyshev Polynomials are not orthogonal with respect to the (unweighted) inner products used during integration, so the resulting matrices are not as well conditioned as those of [HierLobatto](super::lobatto::HierLobatto). However, they are bounded by 1 over [-1, 1] and avoid the rapid growth in condition number of the monomial space used by [HierPoly](super::poly::HierPoly).
+    ///
     #[derive(Clone, Debug)]
-    pub struct QFunction {
-        pub q: Vec<Vec<f64>>,
-        pub d1: Vec<Vec<f64>>,
-        pub d2: Vec<Vec<f64>>,
+    pub struct HierChebyshev {
+        norm: PolyTable,
+        tang: PolyTable,
     }
 
-    impl QFunction {
-        pub fn with(
-            max_n: u8,
-            points: &[f64],
-            leg_poly: &LegendrePoly,
-            compute_2nd_derivs: bool,
-        ) -> Self {
-            if compute_2nd_derivs {
-                Self::with_specs_with_2nd_derivs(max_n, points, leg_poly)
-            } else {
-                Self::with_specs_and_no_2nd_derivs(max_n, points, leg_poly)
+    impl HierCurlBasisFnSpace for HierChebyshev {
+        fn with(max_order: usize, points: &[f64], compute_d2: bool) -> Self {
+            let chebyshev = PolyTable::chebyshev(max_order, points, compute_d2);
+
+            Self {
+                tang: chebyshev.bubbles(points, |n| 1.0 / (2 * (n - 1)) as f64),
+                norm: chebyshev,
             }
         }
 
-        fn with_specs_and_no_2nd_derivs(
-            max_n: u8,
-            points: &[f64],
-            leg_poly: &LegendrePoly,
-        ) -> Self {
-            let mut values = Vec::with_capacity(max_n as usize);
-            let mut primes = Vec::with_capacity(max_n as usize);
-
-            for i in 0..=(max_n as usize) {
-                match i {
-                    0 => {
-                        values.push(points.iter().map(|p| 1.0 - p).collect());
-                        primes.push((0..points.len()).map(|_| -1.0).collect());
-                    }
-                    1 => {
-                        values.push(points.iter().map(|p| 1.0 + p).collect());
-                        primes.push((0..points.len()).map(|_| 1.0).collect());
-                    }
-                    _ => {
-                        values.push(
-                            leg_poly.weighted_value_sum(&Q_WEIGHTS[i - 2], EUC_NORM_COEFFS[i - 2]),
-                            // leg_poly.weighted_value_sum(Q_SEGMENT_WEIGHTS[i - 2], 1.0),
-                        );
-                        primes.push(
-                            leg_poly.weighted_prime_sum(&Q_WEIGHTS[i - 2], EUC_NORM_COEFFS[i - 2]),
-                            // leg_poly.weighted_prime_sum(Q_SEGMENT_WEIGHTS[i - 2], 1.0),
-                        );
-                    }
-                }
-            }
+        fn norm(&self, n: usize, p: usize) -> f64 {
+            self.norm.values[n][p]
+        }
+        fn norm_d1(&self, n: usize, p: usize) -> f64 {
+            self.norm.d1[n][p]
+        }
+        fn norm_d2(&self, n: usize, p: usize) -> f64 {
+            self.norm.d2[n][p]
+        }
 
-            Self {
-                q: values,
-                d1: primes,
-                d2: Vec::new(),
-            }
+        fn tang(&self, n: usize, p: usize) -> f64 {
+            self.tang.values[n][p]
+        }
+        fn tang_d1(&self, n: usize, p: usize) -> f64 {
+            self.tang.d1[n][p]
         }
+        fn tang_d2(&self, n: usize, p: usize) -> f64 {
+            self.tang.d2[n][p]
+        }
+    }
+}
 
-        fn with_specs_with_2nd_derivs(max_n: u8, points: &[f64], leg_poly: &LegendrePoly) -> Self {
-            let n = max_n as usize;
-            let np = points.len();
+pub mod max_ortho {
+    use super::super::HierCurlBasisFnSpace;
+    use super::recurrence::PolyTable;
 
-            let mut values = Vec::with_capacity(n);
-            let mut primes = Vec::with_capacity(n);
-            let mut double_primes = Vec::with_capacity(n);
+    //https://ieeexplore.ieee.org/stamp/stamp.jsp?tp=&arnumber=6470651
+    const EUC_NORM_COEFFS: [f64; 12] = [
+        0.968246, 2.561738, 0.838525, 4.248161, 0.816397, 5.882766, 0.808509, 1.0, 1.0, 1.0, 1.0,
+        1.0,
+    ];
 
-            for i in 0..=n {
-                match i {
-                    0 => {
-                        values.push(points.iter().map(|p| 1.0 - p).collect());
-                        primes.push(vec![-1.0; np]);
-                        double_primes.push(vec![0.0; np]);
-                    }
-                    1 => {
-                        values.push(points.iter().map(|p| 1.0 + p).collect());
-                        primes.push(vec![1.0; np]);
-                        double_primes.push(vec![0.0; np]);
-                    }
-                    _ => {
-                        values.push(
-                            leg_poly.weighted_value_sum(&Q_WEIGHTS[i - 2], EUC_NORM_COEFFS[i - 2]),
-                        );
-                        primes.push(
-                            leg_poly.weighted_prime_sum(&Q_WEIGHTS[i - 2], EUC_NORM_COEFFS[i - 2]),
-                        );
-                        double_primes.push(
-                            leg_poly.weighted_double_prime_sum(
-                                &Q_WEIGHTS[i - 2],
-                                EUC_NORM_COEFFS[i - 2],
-                            ),
-                        )
-                    }
-                }
-            }
+    /// An advanced Curl-Conforming Hierarchical Basis Function Space
+    ///
+    /// The T and N functions are defined using Legendre Polynomials:
+    ///
+    /// N_i(x) = `P_i(x)`
+    ///
+    /// T_i(x) =
+    /// * `1 - x` for `i = 0`
+    /// * `1 + x` for `i = 1`
+    /// * `c_i * (P_i(x) - sum_k (2k + 1) P_k(x) / D_i)` for `i >= 2`, where `k = i - 2, i - 4, ... >= 0` and `D_i = i (i - 1) / 2`
+    ///
+    /// The coefficients `c_i` are the Euclidean normalization coefficients given in the reference (`c_i = 1` above the highest tabulated order).
+    ///
+    /// Based on: https://ieeexplore.ieee.org/stamp/stamp.jsp?tp=&arnumber=6470651
+    #[derive(Clone, Debug)]
+    pub struct HierMaxOrtho {
+        norm: PolyTable,
+        tang: PolyTable,
+    }
 
-            Self {
-                q: values,
-                d1: primes,
-                d2: double_primes,
-            }
+    impl HierMaxOrtho {
+        /// The weights applied to each Legendre Polynomial to build the `i`th order Q-function (`i >= 2`)
+        pub fn q_weights(i: usize) -> Vec<(usize, f64)> {
+            let d_i = (i * (i - 1) / 2) as f64;
+            let c_i = EUC_NORM_COEFFS.get(i - 2).copied().unwrap_or(1.0);
+
+            std::iter::once((i, c_i))
+                .chain(
+                    (0..=i)
+                        .rev()
+                        .step_by(2)
+                        .skip(1)
+                        .map(|k| (k, -c_i * (2 * k + 1) as f64 / d_i)),
+                )
+                .collect()
         }
     }
 
-    #[derive(Clone, Debug)]
-    pub struct LegendrePoly {
-        pub l: Vec<Vec<f64>>,
-        pub d1: Vec<Vec<f64>>,
-        pub d2: Vec<Vec<f64>>,
-        has_2nd_derivs: bool,
-    }
+    impl HierCurlBasisFnSpace for HierMaxOrtho {
+        fn with(max_order: usize, points: &[f64], compute_d2: bool) -> Self {
+            let legendre = PolyTable::legendre(max_order, points, compute_d2);
 
-    impl LegendrePoly {
-        pub fn new() -> Self {
             Self {
-                l: Vec::new(),
-                d1: Vec::new(),
-                d2: Vec::new(),
-                has_2nd_derivs: false,
+                tang: legendre.tangential(points, Self::q_weights),
+                norm: legendre,
             }
         }
 
-        pub fn with(max_n: u8, points: &[f64], compute_2nd_derivs: bool) -> Self {
-            if compute_2nd_derivs {
-                Self::with_specs_with_2nd_derivs(max_n, points)
-            } else {
-                Self::with_specs_and_no_2nd_derivs(max_n, points)
-            }
+        fn norm(&self, n: usize, p: usize) -> f64 {
+            self.norm.values[n][p]
+        }
+        fn norm_d1(&self, n: usize, p: usize) -> f64 {
+            self.norm.d1[n][p]
+        }
+        fn norm_d2(&self, n: usize, p: usize) -> f64 {
+            self.norm.d2[n][p]
         }
 
-        fn with_specs_and_no_2nd_derivs(max_n: u8, points: &[f64]) -> Self {
-            let mut values = Vec::with_capacity(max_n as usize);
-            let mut primes = Vec::with_capacity(max_n as usize);
-
-            for i in 0..=(max_n as usize) {
-                values.push(Vec::with_capacity(points.len()));
-                primes.push(Vec::with_capacity(points.len()));
-
-                let i_f = i as f64;
-                for (p, &point) in points.iter().enumerate() {
-                    match i {
-                        0 => {
-                            values[i].push(1.0);
-                            primes[i].push(0.0);
-                        }
-                        1 => {
-                            values[i].push(point);
-                            primes[i].push(1.0);
-                        }
-                        _ => {
-                            let v = ((2.0 * i_f - 1.0) * point * values[i - 1][p]
-                                - (i_f - 1.0) * values[i - 2][p])
-                                / i_f;
-                            values[i].push(v);
-
-                            let p = i_f * values[i - 1][p] + point * primes[i - 1][p];
-                            primes[i].push(p)
-                        }
-                    }
-                }
-            }
-
-            Self {
-                l: values,
-                d1: primes,
-                d2: Vec::new(),
-                has_2nd_derivs: false,
-            }
+        fn tang(&self, n: usize, p: usize) -> f64 {
+            self.tang.values[n][p]
+        }
+        fn tang_d1(&self, n: usize, p: usize) -> f64 {
+            self.tang.d1[n][p]
+        }
+        fn tang_d2(&self, n: usize, p: usize) -> f64 {
+            self.tang.d2[n][p]
         }
+    }
+}
 
-        fn with_specs_with_2nd_derivs(max_n: u8, points: &[f64]) -> Self {
-            let n = max_n as usize;
-            let num_p = points.len();
+#[cfg(test)]
+mod tests {
+    use super::super::HierCurlBasisFnSpace;
+    use super::chebyshev::HierChebyshev;
+    use super::lobatto::HierLobatto;
+    use super::max_ortho::HierMaxOrtho;
+    use super::poly::HierPoly;
+    use crate::fem_domain::domain::mesh::{Mesh, MAX_POLYNOMIAL_ORDER};
+    use crate::fem_domain::domain::{ContinuityCondition, Domain};
+    use crate::fem_problem::galerkin::{assemble_matrix, galerkin_sample_gep_hcurl, MIN_GLQ_ORDER};
+    use crate::fem_problem::integration::glq::gauss_quadrature_points;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use crate::fem_problem::linalg::nalgebra_solve::nalgebra_solve_gep;
+    use nalgebra::{DMatrix, SymmetricEigen};
+
+    // condition number of the mass matrix over the unit Elem with the given expansion orders
+    fn b_condition_number<BSpace: HierCurlBasisFnSpace>(orders: [u8; 2]) -> f64 {
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders(orders).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        // enough points to integrate the products of the basis functions exactly
+        let glq_grid_dim = orders.map(|n| std::cmp::max(n as usize + 2, MIN_GLQ_ORDER));
+        let b: DMatrix<f64> = assemble_matrix::<BSpace, L2Inner>(&domain, Some(glq_grid_dim))
+            .unwrap()
+            .into();
+        let eigenvalues = SymmetricEigen::new(b).eigenvalues;
+
+        eigenvalues.max() / eigenvalues.min()
+    }
 
-            let mut values = Vec::with_capacity(n);
-            let mut primes = Vec::with_capacity(n);
-            let mut double_primes = Vec::with_capacity(n);
+    // sample the tangential and normal functions of a space (and their derivatives) over a set of points
+    fn sample<BSpace: HierCurlBasisFnSpace>(
+        space: &BSpace,
+        n_max: usize,
+        num_points: usize,
+    ) -> [Vec<Vec<f64>>; 6] {
+        [
+            (0..=n_max)
+                .map(|n| (0..num_points).map(|p| space.tang(n, p)).collect())
+                .collect(),
+            (0..=n_max)
+                .map(|n| (0..num_points).map(|p| space.tang_d1(n, p)).collect())
+                .collect(),
+            (0..=n_max)
+                .map(|n| (0..num_points).map(|p| space.tang_d2(n, p)).collect())
+                .collect(),
+            (0..=n_max)
+                .map(|n| (0..num_points).map(|p| space.norm(n, p)).collect())
+                .collect(),
+            (0..=n_max)
+                .map(|n| (0..num_points).map(|p| space.norm_d1(n, p)).collect())
+                .collect(),
+            (0..=n_max)
+                .map(|n| (0..num_points).map(|p| space.norm_d2(n, p)).collect())
+                .collect(),
+        ]
+    }
 
-            // construct (1 - x^2) without singularities at the endpoints
-            let d2_denom: Vec<f64> = match (
-                (points[0].abs() - 1.0).abs() < 1e-15,
-                (points[num_p - 1].abs() - 1.0).abs() < 1e-15,
-            ) {
-                (true, true) => {
-                    let mut den = vec![1.0];
-                    den.extend(
-                        points
-                            .iter()
-                            .skip(1)
-                            .take(num_p - 2)
-                            .map(|x| 1.0 - x.powi(2)),
-                    );
-                    den.push(1.0);
-                    den
-                }
-                (true, false) => {
-                    let mut den = vec![1.0];
-                    den.extend(
-                        points
-                            .iter()
-                            .skip(1)
-                            .take(num_p - 1)
-                            .map(|x| 1.0 - x.powi(2)),
-                    );
-                    den
-                }
-                (false, true) => {
-                    let mut den: Vec<_> = points
-                        .iter()
-                        .take(num_p - 1)
-                        .map(|x| 1.0 - x.powi(2))
-                        .collect();
-                    den.push(1.0);
-                    den
+    // check the 1st and 2nd derivatives against central differences at the interior points `x`
+    fn check_derivatives<BSpace: HierCurlBasisFnSpace>(n_max: usize) {
+        const H: f64 = 1e-5;
+        let x = [-0.9, -0.31, 0.0, 0.47, 0.88];
+        let points: Vec<f64> = x.iter().flat_map(|x| [x - H, *x, x + H]).collect();
+        let space = BSpace::with(n_max, &points, true);
+        let [t, t_d1, t_d2, n, n_d1, n_d2] = sample(&space, n_max, points.len());
+
+        for (values, d1, d2) in [(t, t_d1, t_d2), (n, n_d1, n_d2)] {
+            for order in 0..=n_max {
+                for k in 0..x.len() {
+                    let [m, c, p] = [3 * k, 3 * k + 1, 3 * k + 2];
+                    let scale = 1.0 + d1[order][c].abs();
+                    let fd_1 = (values[order][p] - values[order][m]) / (2.0 * H);
+                    assert!((fd_1 - d1[order][c]).abs() / scale < 1e-5);
+
+                    let scale = 1.0 + d2[order][c].abs();
+                    let fd_2 = (d1[order][p] - d1[order][m]) / (2.0 * H);
+                    assert!((fd_2 - d2[order][c]).abs() / scale < 1e-5);
                 }
-                (false, false) => points.iter().map(|x| (1.0 - x.powi(2))).collect(),
-            };
+            }
+        }
+    }
 
-            for i in 0..=n {
-                let i_f = i as f64;
-                match i {
-                    0 => {
-                        values.push(vec![1.0; num_p]);
-                        primes.push(vec![0.0; num_p]);
+    #[test]
+    fn recurrences_match_closed_forms() {
+        let points = [-1.0, -0.6, -0.2, 0.3, 0.75, 1.0];
 
-                        double_primes.push(vec![0.0; num_p]);
-                    }
-                    1 => {
-                        values.push(Vec::from(points));
-                        primes.push(vec![1.0; num_p]);
+        let lobatto = HierLobatto::with(4, &points, true);
+        let chebyshev = HierChebyshev::with(4, &points, true);
 
-                        double_primes.push(vec![0.0; num_p]);
-                    }
-                    2 => {
-                        values.push(
-                            points
-                                .iter()
-                                .enumerate()
-                                .map(|(p, x)| {
-                                    ((2.0 * i_f - 1.0) * x * values[i - 1][p]
-                                        - (i_f - 1.0) * values[i - 2][p])
-                                        / i_f
-                                })
-                                .collect(),
-                        );
-                        primes.push(
-                            points
-                                .iter()
-                                .enumerate()
-                                .map(|(p, x)| i_f * values[i - 1][p] + x * primes[i - 1][p])
-                                .collect(),
-                        );
+        for (p, x) in points.iter().enumerate() {
+            // P_3 and T_4
+            let p_3 = (5.0 * x * x * x - 3.0 * x) / 2.0;
+            let t_4 = 8.0 * x * x * x * x - 8.0 * x * x + 1.0;
+            assert!((lobatto.norm(3, p) - (3.5_f64).sqrt() * p_3).abs() < 1e-13);
+            assert!((chebyshev.norm(4, p) - t_4).abs() < 1e-13);
 
-                        double_primes.push(vec![3.0; num_p]);
-                    }
-                    _ => {
-                        values.push(
-                            points
-                                .iter()
-                                .enumerate()
-                                .map(|(p, x)| {
-                                    ((2.0 * i_f - 1.0) * x * values[i - 1][p]
-                                        - (i_f - 1.0) * values[i - 2][p])
-                                        / i_f
-                                })
-                                .collect(),
-                        );
-                        primes.push(
-                            points
-                                .iter()
-                                .enumerate()
-                                .map(|(p, x)| i_f * values[i - 1][p] + x * primes[i - 1][p])
-                                .collect(),
-                        );
+            // the lowest order tangential functions match the other spaces
+            assert!((lobatto.tang(0, p) - (1.0 - x)).abs() < 1e-15);
+            assert!((chebyshev.tang(1, p) - (1.0 + x)).abs() < 1e-15);
 
-                        double_primes.push(
-                            primes[i]
-                                .iter()
-                                .zip(values[i].iter())
-                                .enumerate()
-                                .map(|(p, (prime, value))| {
-                                    (2.0 * points[p] * prime - (i_f * (i_f + 1.0)) * value)
-                                        / d2_denom[p]
-                                })
-                                .collect(),
-                        )
-                    }
-                }
+            // the derivative of each Lobatto function is the normalized Legendre Polynomial of one lower order
+            for n in 2..=4 {
+                assert!((lobatto.tang_d1(n, p) - lobatto.norm(n - 1, p)).abs() < 1e-13);
             }
+        }
 
-            Self {
-                l: values,
-                d1: primes,
-                d2: double_primes,
-                has_2nd_derivs: true,
+        // the higher order tangential functions vanish at the endpoints
+        for n in 2..=4 {
+            for p in [0, points.len() - 1] {
+                assert!(lobatto.tang(n, p).abs() < 1e-14);
+                assert!(chebyshev.tang(n, p).abs() < 1e-14);
             }
         }
+    }
 
-        pub fn weighted_value_sum(&self, weights: &[f64], normalization_coeff: f64) -> Vec<f64> {
-            let mut sum = vec![0.0; self.l[0].len()];
-            for (order, &weight) in weights.iter().enumerate() {
-                for (p, &value) in self.l[order].iter().enumerate() {
-                    sum[p] += weight * value;
-                }
-            }
+    #[test]
+    fn derivatives() {
+        check_derivatives::<HierLobatto>(MAX_POLYNOMIAL_ORDER as usize);
+        check_derivatives::<HierChebyshev>(MAX_POLYNOMIAL_ORDER as usize);
+    }
 
-            for s in sum.iter_mut() {
-                *s *= normalization_coeff;
-            }
+    #[test]
+    fn lobatto_b_conditioning() {
+        // the normal functions are orthonormal, so the condition number only grows with the tangential expansion order
+        for p in 2..=MAX_POLYNOMIAL_ORDER {
+            let p_ = p as f64;
+            assert!(b_condition_number::<HierLobatto>([p, 2]) < 6.0 * p_ * p_);
+            assert!(b_condition_number::<HierLobatto>([2, p]) < 6.0 * p_ * p_);
+        }
 
-            sum
+        for p in 2..=5 {
+            let isotropic = b_condition_number::<HierLobatto>([p, p]);
+            let anisotropic = b_condition_number::<HierLobatto>([p, 2]);
+            assert!((isotropic - anisotropic).abs() / anisotropic < 1e-8);
         }
+    }
 
-        pub fn weighted_prime_sum(&self, weights: &[f64], normalization_coeff: f64) -> Vec<f64> {
-            let mut sum = vec![0.0; self.l[0].len()];
-            for (order, &weight) in weights.iter().enumerate() {
-                for (p, &value) in self.d1[order].iter().enumerate() {
-                    sum[p] += weight * value;
-                }
-            }
+    #[test]
+    fn chebyshev_b_conditioning() {
+        for p in 2..=MAX_POLYNOMIAL_ORDER {
+            let p_ = p as f64;
+            assert!(b_condition_number::<HierChebyshev>([p, 2]) < p_.powi(4));
+            assert!(b_condition_number::<HierChebyshev>([2, p]) < p_.powi(4));
+        }
 
-            for s in sum.iter_mut() {
-                *s *= normalization_coeff;
-            }
+        for p in 2..=5 {
+            let p_ = p as f64;
+            assert!(b_condition_number::<HierChebyshev>([p, p]) < p_.powi(4));
+        }
+    }
+
+    #[test]
+    fn b_conditioning_against_monomials() {
+        for p in [4, 8, 12, MAX_POLYNOMIAL_ORDER] {
+            let poly = b_condition_number::<HierPoly>([p, 2]);
+            let lobatto = b_condition_number::<HierLobatto>([p, 2]);
+            let chebyshev = b_condition_number::<HierChebyshev>([p, 2]);
 
-            sum
+            assert!(lobatto < chebyshev);
+            assert!(chebyshev < poly);
         }
 
-        pub fn weighted_double_prime_sum(
-            &self,
-            weights: &[f64],
-            normalization_coeff: f64,
-        ) -> Vec<f64> {
-            assert!(
-                self.has_2nd_derivs,
-                "2nd Derivatives not evaluated on Legendre Polynomial; cannot compute weighted sum!"
-            );
-            let mut sum = vec![0.0; self.l[0].len()];
+        // the monomial space is nearly singular at the maximum expansion order
+        assert!(b_condition_number::<HierPoly>([MAX_POLYNOMIAL_ORDER, 2]) > 1e12);
+        assert!(b_condition_number::<HierLobatto>([MAX_POLYNOMIAL_ORDER, 2]) < 1e4);
+    }
 
-            for (order, &weight) in weights.iter().enumerate() {
-                for (p, &value) in self.d2[order].iter().enumerate() {
-                    sum[p] += weight * value;
-                }
-            }
+    #[test]
+    fn same_eigenvalues_as_monomials() {
+        // every space spans the same polynomials, so the discrete spectra must agree
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([4, 4]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        let eigenvalue = |gep| nalgebra_solve_gep(gep, 10.0).unwrap().value;
+        let poly = eigenvalue(
+            galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap(),
+        );
+        let lobatto = eigenvalue(
+            galerkin_sample_gep_hcurl::<HierLobatto, CurlCurl, L2Inner>(&domain, None).unwrap(),
+        );
+        let chebyshev = eigenvalue(
+            galerkin_sample_gep_hcurl::<HierChebyshev, CurlCurl, L2Inner>(&domain, None).unwrap(),
+        );
+        let max_ortho = eigenvalue(
+            galerkin_sample_gep_hcurl::<HierMaxOrtho, CurlCurl, L2Inner>(&domain, None).unwrap(),
+        );
+
+        assert!((lobatto - poly).abs() / poly < 1e-9);
+        assert!((chebyshev - poly).abs() / poly < 1e-9);
+        assert!((max_ortho - poly).abs() / poly < 1e-9);
+    }
+
+    #[test]
+    fn max_ortho_q_functions() {
+        let (points, weights) = gauss_quadrature_points(32, true);
+        let n_max = MAX_POLYNOMIAL_ORDER as usize;
+        let max_ortho = HierMaxOrtho::with(n_max, &points, true);
+
+        // Q_4 = c_4 * (P_4 - (P_0 + 5 P_2) / 6)
+        let c_4 = 0.838525;
+        for (p, x) in points.iter().enumerate() {
+            let p_2 = (3.0 * x * x - 1.0) / 2.0;
+            let p_4 = (35.0 * x * x * x * x - 30.0 * x * x + 3.0) / 8.0;
+            let q_4 = c_4 * (p_4 - (1.0 + 5.0 * p_2) / 6.0);
+            assert!((max_ortho.tang(4, p) - q_4).abs() < 1e-13);
+        }
 
-            for s in sum.iter_mut() {
-                *s *= normalization_coeff;
+        // the higher order tangential functions vanish at the endpoints and are orthogonal
+        // (with || Q_i ||^2 = c_i^2 * (|| P_i ||^2 + sum_k ((2k + 1) / D_i)^2 || P_k ||^2) = c_i^2 * (2 / (2i + 1) + 2 / D_i))
+        for i in 2..=n_max {
+            let c_i = HierMaxOrtho::q_weights(i)[0].1;
+            let d_i = (i * (i - 1) / 2) as f64;
+
+            assert!(max_ortho.tang(i, 0).abs() < 1e-12);
+            assert!(max_ortho.tang(i, points.len() - 1).abs() < 1e-12);
+
+            for j in 2..=n_max {
+                let inner: f64 = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(p, w)| w * max_ortho.tang(i, p) * max_ortho.tang(j, p))
+                    .sum();
+                let expected = if i == j {
+                    c_i * c_i * (2.0 / (2 * i + 1) as f64 + 2.0 / d_i)
+                } else {
+                    0.0
+                };
+                assert!((inner - expected).abs() < 1e-12);
             }
+        }
+    }
+
+    #[test]
+    fn max_ortho_derivatives() {
+        check_derivatives::<HierMaxOrtho>(MAX_POLYNOMIAL_ORDER as usize);
+    }
+
+    #[test]
+    fn max_ortho_b_conditioning() {
+        // the tangential functions are orthogonal, so the condition number only grows with the normalization coefficients
+        for p in 2..=MAX_POLYNOMIAL_ORDER {
+            let p_ = p as f64;
+            assert!(b_condition_number::<HierMaxOrtho>([p, 2]) < 4.0 * p_ * p_);
+            assert!(b_condition_number::<HierMaxOrtho>([2, p]) < 4.0 * p_ * p_);
+        }
 
-            sum
+        for p in 2..=5 {
+            let p_ = p as f64;
+            assert!(b_condition_number::<HierMaxOrtho>([p, p]) < 8.0 * p_ * p_);
         }
     }
 }
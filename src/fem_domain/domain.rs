@@ -4,12 +4,17 @@ pub mod dof;
 pub mod fields;
 /// The internal geometric structure of a Domain. This is modified by hp-refinements.
 pub mod mesh;
+/// Raster image (PPM and PNG) export
+pub mod raster;
+/// VTK XML (.vtu) file export
+pub mod vtu;
 
+use super::basis::{evaluate_hcurl_basis_fn, BasisEvalError, BasisFnSample, HierCurlBasisFnSpace};
 use dof::{
     basis_spec::{BSAddress, BasisDir, BasisLoc, BasisSpec},
     DoF,
 };
-use mesh::*;
+use mesh::{h_refinement::MeshIdMap, space::Point, *};
 use smallvec::smallvec;
 use std::collections::BTreeMap;
 use std::fmt;
@@ -145,6 +150,15 @@ impl Domain {
 
                     dofs.push(DoF::new(dof_id, addresses));
                 }
+            } else if let Some(active_elem_id) = mesh.edges[edge_id].active_boundary_elem() {
+                // Create single-BasisSpec DoFs along tagged boundary Edges (untagged boundary Edges don't support DoFs)
+                for edge_bs in edge_bs_list.drain(0..).filter(|bs| {
+                    (bs.dir == BasisDir::U || bs.dir == BasisDir::V) && bs.elem_id == active_elem_id
+                }) {
+                    let dof_id = dof_id_tracker.next_id();
+                    let address = Self::push_basis_spec(&mut basis_specs, edge_bs, dof_id);
+                    dofs.push(DoF::new(dof_id, smallvec![address]));
+                }
             }
         }
 
@@ -258,6 +272,62 @@ impl Domain {
         }
     }
 
+    /// Get the ID of the leaf-`Elem` containing a point in real space (see [Mesh::leaf_elem_containing])
+    ///
+    /// Returns `None` if the point falls outside of the Mesh
+    pub fn leaf_elem_containing(&self, point: &Point) -> Option<usize> {
+        self.mesh.leaf_elem_containing(point)
+    }
+
+    /// Evaluate the value and curl of a [BasisSpec] at an arbitrary point in parametric space (see [evaluate_hcurl_basis_fn])
+    ///
+    /// If `over_elem_id` is given, the point `uv` falls in the parametric space of that descendant of the [BasisSpec]'s `Elem`. Otherwise, it falls in the parametric space of the [BasisSpec]'s `Elem` itself.
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::unit();
+    /// mesh.set_global_expansion_orders([3, 3]).unwrap();
+    /// let dom = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    ///
+    /// let bs = &dom.local_basis_specs(0).unwrap()[0];
+    /// let sample = dom.evaluate_basis_spec::<HierLobatto>(bs, None, [0.25, -0.4]).unwrap();
+    /// assert!(sample.value.dot_with(&sample.value) > 0.0);
+    ///
+    /// // points must fall within the parametric space of the Elem
+    /// assert!(dom.evaluate_basis_spec::<HierLobatto>(bs, None, [1.5, 0.0]).is_err());
+    /// ```
+    pub fn evaluate_basis_spec<BSpace: HierCurlBasisFnSpace>(
+        &self,
+        bs: &BasisSpec,
+        over_elem_id: Option<usize>,
+        uv: [f64; 2],
+    ) -> Result<BasisFnSample, BasisEvalError> {
+        let elem = self
+            .mesh
+            .elems
+            .get(bs.elem_id)
+            .ok_or(MeshAccessError::ElemDoesNotExist(bs.elem_id))?;
+        let over_elem = match over_elem_id {
+            Some(id) => Some(
+                self.mesh
+                    .elems
+                    .get(id)
+                    .ok_or(MeshAccessError::ElemDoesNotExist(id))?,
+            ),
+            None => None,
+        };
+
+        evaluate_hcurl_basis_fn::<BSpace>(
+            elem,
+            over_elem,
+            bs.dir,
+            [bs.i as usize, bs.j as usize],
+            uv,
+        )
+    }
+
     /// Retrieve a list of an `Elem`s descendant [BasisSpec]s (All the [`BasisSpec`]s on its descendant `Elem`s)
     ///
     /// # Returns
@@ -351,6 +421,76 @@ impl Domain {
         }
     }
 
+    /// Carry a DoF-indexed vector (such as a solution) over from a `previous` Domain to this Domain, where this Domain's Mesh was produced by h-coarsening the `previous` Domain's Mesh
+    ///
+    /// A DoF retains its value if all of its Basis Functions are unchanged by the coarsening (they are defined over the same `Elem`s with the same expansion orders, and were grouped into a single DoF in the `previous` Domain).
+    /// All other DoFs (such as those on coarsened `Elem`s or those whose Edge-type matching changed) are set to `T::default()`.
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+    /// mesh.set_global_expansion_orders([3, 3]).unwrap();
+    /// mesh.h_refine_elems(vec![0, 3], HRef::T).unwrap();
+    /// let fine_domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+    ///
+    /// let solution: Vec<f64> = (0..fine_domain.dofs.len()).map(|i| i as f64 + 1.0).collect();
+    ///
+    /// // merge Elem 3's children back into it
+    /// let id_map = mesh.h_coarsen_elems(vec![3]).unwrap();
+    /// let coarse_domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    ///
+    /// let remapped = coarse_domain.remap_dof_values(&fine_domain, &id_map, &solution);
+    /// assert_eq!(remapped.len(), coarse_domain.dofs.len());
+    ///
+    /// // DoFs away from Elem 3 keep their values
+    /// assert!(remapped.iter().filter(|value| **value != 0.0).count() > 0);
+    /// ```
+    pub fn remap_dof_values<T: Clone + Default>(
+        &self,
+        previous: &Domain,
+        id_map: &MeshIdMap,
+        values: &[T],
+    ) -> Vec<T> {
+        assert_eq!(
+            values.len(),
+            previous.dofs.len(),
+            "Number of values must match the number of DoFs in the previous Domain; cannot remap values!"
+        );
+
+        // look up the previous DoF associated with each Basis Function (identified by its new elem_id, expansion orders, and direction)
+        let mut previous_dof_ids: BTreeMap<(usize, u8, u8, u8), usize> = BTreeMap::new();
+        for bs in previous.basis_specs.iter().flatten() {
+            if let (Some(elem_id), Some(dof_id)) = (id_map.elem(bs.elem_id), bs.dof_id) {
+                previous_dof_ids.insert((elem_id, bs.i, bs.j, bs.dir as u8), dof_id);
+            }
+        }
+
+        self.dofs
+            .iter()
+            .map(|dof| {
+                let matching_dof_ids: Vec<Option<usize>> = dof
+                    .get_basis_specs()
+                    .iter()
+                    .map(|address| {
+                        let bs = &self.basis_specs[address.elem_id][address.elem_idx];
+                        previous_dof_ids
+                            .get(&(bs.elem_id, bs.i, bs.j, bs.dir as u8))
+                            .copied()
+                    })
+                    .collect();
+
+                match matching_dof_ids[0] {
+                    Some(dof_id) if matching_dof_ids.iter().all(|id| *id == Some(dof_id)) => {
+                        values[dof_id].clone()
+                    }
+                    _ => T::default(),
+                }
+            })
+            .collect()
+    }
+
     // Push a new `BasisSpec` onto the list, updating its ID to match its position in its elem's list
     // return its [BSAddress] composed of its element id and index
     fn push_basis_spec(
@@ -410,4 +550,70 @@ mod tests {
         dom.local_basis_specs(0).unwrap();
         dom.descendant_basis_specs(0).unwrap();
     }
+
+    #[test]
+    fn remap_after_coarsening() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        mesh.h_refine_elems(vec![0], HRef::T).unwrap();
+        let coarse_domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+
+        mesh.h_refine_elems(vec![3], HRef::T).unwrap();
+        let fine_domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+        let values: Vec<usize> = (1..=fine_domain.dofs.len()).collect();
+
+        let id_map = mesh.h_coarsen_elems(vec![3]).unwrap();
+        let remapped_domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        assert_eq!(remapped_domain.dofs.len(), coarse_domain.dofs.len());
+
+        let remapped = remapped_domain.remap_dof_values(&fine_domain, &id_map, &values);
+
+        // the elem-type DoFs on Elem 3 are reset, others keep their values
+        //      (edge-type DoFs on Elem 3 are unchanged because they are superimposed over its children in the fine Domain)
+        for (dof, value) in remapped_domain.dofs.iter().zip(remapped.iter()) {
+            let addresses = dof.get_basis_specs();
+            let elem_type_on_3 = addresses.len() == 1 && addresses[0].elem_id == 3;
+            assert_eq!(*value == 0, elem_type_on_3);
+        }
+
+        // an unchanged mesh maps every value onto itself
+        let identity = fine_domain.remap_dof_values(
+            &fine_domain,
+            &MeshIdMap::from_retained(
+                &vec![true; fine_domain.mesh.elems.len()],
+                &vec![true; fine_domain.mesh.edges.len()],
+                &vec![true; fine_domain.mesh.nodes.len()],
+            ),
+            &values,
+        );
+        assert_eq!(identity, values);
+    }
+
+    #[test]
+    fn tagged_boundary_dofs() {
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+
+        // PEC everywhere: only the elem-type DoFs remain
+        let dom = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+        assert_eq!(dom.dofs.len(), 4);
+
+        // tag the bottom edge: 2 u-directed edge-type DoFs are added
+        mesh.tag_boundary_edges(0, |point| (point.y + 1.0).abs() < 1e-12);
+        let dom = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+        assert_eq!(dom.dofs.len(), 6);
+
+        // h-refine the mesh: the bottom edge's children each support 2 DoFs on the smallest Elems
+        mesh.global_h_refinement(HRef::T);
+        let dom = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let bottom_edge_dofs: Vec<&DoF> = dom
+            .dofs
+            .iter()
+            .filter(|dof| {
+                let bs = dom.get_basis_spec(dof.get_basis_specs()[0]).unwrap();
+                matches!(bs.loc, BasisLoc::EdgeBs(_, edge_id) if dom.mesh.edges[edge_id].boundary_tag() == Some(0))
+            })
+            .collect();
+        assert_eq!(bottom_edge_dofs.len(), 4);
+    }
 }
@@ -1,5 +1,17 @@
-use super::super::basis::{HierBasisFn, HierCurlBasisFn, HierCurlBasisFnSpace};
-use super::{dof::basis_spec::BasisDir, mesh::space::V2D, Domain};
+use super::super::basis::{
+    local_basis_sample, HierBasisFn, HierCurlBasisFn, HierCurlBasisFnSpace, LocalSample,
+};
+use super::{
+    dof::basis_spec::{BasisDir, BasisSpec},
+    mesh::{
+        elem::Elem,
+        space::{Point, V2D},
+    },
+    raster::{Colormap, RgbImage},
+    vtu::{leaf_outline_piece, VtuArray, VtuEncoding, VtuPiece, VtuValues, VTK_QUAD},
+    ContinuityCondition, Domain,
+};
+use rayon::prelude::*;
 
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
@@ -9,7 +21,6 @@ use std::io::{BufWriter, Write};
 use std::time::SystemTime;
 
 // TODO: update UniformFieldSpace and print_to_vtk functions after curvilinear elements are implemented
-// TODO: implement a constant (over x and y) density FieldSpace structure which supports field image exports
 
 /// A collection of Field Solutions over a [Domain]
 ///
@@ -126,6 +137,108 @@ impl<'d> UniformFieldSpace<'d> {
         }
     }
 
+    /// Use an eigenvector and associated [HierCurlBasisFnSpace] to compute the (z-directed) curl and the divergence of the field over the [Domain]
+    ///
+    /// Derivatives are computed with respect to real space, such that the curl is proportional to H_z for an electric field solution.
+    ///
+    /// The curl and divergence quantities will be stored as {vector_name}_curl and {vector_name}_div respectively. The Names are returned in an array in that order.
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let mut mesh = RectangularCavity::homogeneous(2.0, 1.0, Materials::default()).mesh();
+    /// mesh.set_global_expansion_orders([6, 6]).unwrap();
+    /// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    ///
+    /// // the lowest order mode of a 2x1 cavity (E = sin(πx / 2) ŷ, with eigenvalue π² / 4)
+    /// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+    /// let mode = nalgebra_solve_gep(gep, PI * PI / 4.0).unwrap();
+    ///
+    /// // construct a field space with an 11x11 grid on each leaf-`Elem` (which includes the peak of the field at x = 1)
+    /// let mut ufs = UniformFieldSpace::new(&domain, [11, 11]);
+    ///
+    /// // compute the curl and divergence of the mode
+    /// let [curl_name, div_name] = ufs
+    ///     .curl_div_fields::<HierPoly>("te_10", mode.vector.clone())
+    ///     .unwrap();
+    /// let [x_name, y_name] = ufs.xy_fields::<HierPoly>("te_10", mode.vector).unwrap();
+    ///
+    /// assert_eq!(curl_name, String::from("te_10_curl"));
+    /// assert_eq!(div_name, String::from("te_10_div"));
+    ///
+    /// // derived quantities can be combined like any other
+    /// ufs.expression_2arg([&x_name, &y_name], "te_10_mag", |x, y| (x * x + y * y).sqrt())
+    ///     .unwrap();
+    ///
+    /// let max_abs = |name: &str| {
+    ///     ufs.values(name)
+    ///         .unwrap()
+    ///         .values()
+    ///         .flatten()
+    ///         .flatten()
+    ///         .fold(0.0_f64, |max, value| max.max(value.abs()))
+    /// };
+    ///
+    /// // the mode is solenoidal, and the peak of its curl (π cos(πx / 2) / 2) is π / 2 times the peak of the field
+    /// assert!(max_abs(&div_name) < 1e-3 * max_abs("te_10_mag"));
+    /// assert!((max_abs(&curl_name) / max_abs("te_10_mag") - PI / 2.0).abs() < 1e-3);
+    /// ```
+    pub fn curl_div_fields<BSpace: HierCurlBasisFnSpace>(
+        &mut self,
+        vector_name: &'static str,
+        solution: Vec<f64>,
+    ) -> Result<[String; 2], UniformFieldError> {
+        if solution.len() != self.domain.dofs.len() {
+            Err(UniformFieldError::MismatchedSolutionSize(
+                self.domain.dofs.len(),
+                solution.len(),
+            ))
+        } else {
+            let curl_q_name = format!("{}_curl", vector_name);
+            let div_q_name = format!("{}_div", vector_name);
+
+            let mut curl_quantity = FieldQuantity::new(&curl_q_name);
+            let mut div_quantity = FieldQuantity::new(&div_q_name);
+
+            for shell_elem in self.domain.mesh.elems.iter().filter(|e| !e.has_children()) {
+                // map the parametric grid onto the leaf-Elem in real space
+                let [p0, p1] = self.domain.mesh.elem_diag_points(shell_elem.id).unwrap();
+                let xs: Vec<f64> = self.parametric_points[0]
+                    .iter()
+                    .map(|u| p0.x + (u + 1.0) * (p1.x - p0.x) / 2.0)
+                    .collect();
+                let ys: Vec<f64> = self.parametric_points[1]
+                    .iter()
+                    .map(|v| p0.y + (v + 1.0) * (p1.y - p0.y) / 2.0)
+                    .collect();
+
+                let local_fields = sample_local_fields::<BSpace>(
+                    self.domain,
+                    shell_elem.id,
+                    [&xs, &ys],
+                    &solution,
+                );
+
+                curl_quantity.insert_elem_values(shell_elem.id, local_fields.curl);
+                div_quantity.insert_elem_values(shell_elem.id, local_fields.div);
+            }
+
+            self.quantities.insert(curl_q_name.clone(), curl_quantity);
+            self.quantities.insert(div_q_name.clone(), div_quantity);
+
+            Ok([curl_q_name, div_q_name])
+        }
+    }
+
+    /// The values of a Field Quantity over each leaf-`Elem` (indexed by `Elem` ID), or `None` if the quantity is not found in the Field Space
+    pub fn values(&self, name: impl AsRef<str>) -> Option<&BTreeMap<usize, Vec<Vec<f64>>>> {
+        self.quantities
+            .get(name.as_ref())
+            .map(|quantity| &quantity.values)
+    }
+
     /// create a VTK file at the designated `path` (with the file `name.vtk`) including all Field Quantities
     ///
     /// These files can be plotted using [Visit](https://wci.llnl.gov/simulation/computer-codes/visit)
@@ -233,6 +346,164 @@ impl<'d> UniformFieldSpace<'d> {
         Ok(())
     }
 
+    /// create a VTK XML Unstructured Grid file (`name.vtu`) at the designated `path` according to a set of [VtuSettings]
+    ///
+    /// Alongside the requested point data, each cell is labeled with the following data from the leaf-`Elem` it belongs to:
+    /// * `elem_id`
+    /// * `h_level_u` and `h_level_v`
+    /// * `poly_order_u` and `poly_order_v` (`ni` and `nj`)
+    /// * `eps_rel` and `mu_rel` (real parts of the relative material parameters)
+    /// * any per-`Elem` values (such as error indicators) included in the settings
+    ///
+    /// These files can be plotted using [Paraview](https://www.paraview.org/) or [Visit](https://wci.llnl.gov/simulation/computer-codes/visit)
+    ///
+    /// Can return an IO error if the file cannot be written, or a `UniformFieldError` if any of the quantity names are not found in the Field Space
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let domain = Domain::unit(ContinuityCondition::HCurl);
+    /// let unit_solution = vec![1.0; domain.dofs.len()];
+    ///
+    /// let mut ufs = UniformFieldSpace::new(&domain, [10, 10]);
+    /// let [x_name, y_name] = ufs.xy_fields::<HierPoly>("E", unit_solution.clone()).unwrap();
+    /// let [curl_name, _] = ufs.curl_div_fields::<HierPoly>("E", unit_solution).unwrap();
+    ///
+    /// let indicators: BTreeMap<usize, f64> = [(0, 0.5)].into_iter().collect();
+    /// let settings = VtuSettings::new(VtuEncoding::Base64)
+    ///     .with_vector("E", [x_name, y_name])
+    ///     .with_scalar(curl_name)
+    ///     .with_elem_data("error_indicator", indicators);
+    ///
+    /// ufs.print_to_vtu("./test_output/unit_fields.vtu", &settings)
+    ///     .unwrap();
+    /// ```
+    pub fn print_to_vtu(
+        &self,
+        path: impl AsRef<str>,
+        settings: &VtuSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        for qn in settings
+            .scalars
+            .iter()
+            .chain(settings.vectors.iter().flat_map(|(_, xy_names)| xy_names))
+        {
+            if !self.quantities.contains_key(qn) {
+                return Err(UniformFieldError::MissingQuantity(qn.clone()))?;
+            }
+        }
+
+        let [nx, ny] = self.densities;
+        let leaf_elems: Vec<&Elem> = self
+            .domain
+            .mesh
+            .elems
+            .iter()
+            .filter(|elem| !elem.has_children())
+            .collect();
+
+        let mut piece = VtuPiece::default();
+        let mut cell_elems: Vec<&Elem> = Vec::new();
+
+        // a grid of points and quadrilateral cells over each leaf-Elem
+        for leaf_elem in leaf_elems.iter() {
+            let [p0, p1] = self.domain.mesh.elem_diag_points(leaf_elem.id).unwrap();
+            let first = piece.points.len();
+
+            for x in uniform_range(p0.x, p1.x, nx) {
+                for y in uniform_range(p0.y, p1.y, ny) {
+                    piece.points.push([x, y, 0.0]);
+                }
+            }
+
+            for m in 0..(nx - 1) {
+                for n in 0..(ny - 1) {
+                    let corner = first + ny * m + n;
+                    piece.push_cell(
+                        &[corner, corner + ny, corner + ny + 1, corner + 1],
+                        VTK_QUAD,
+                    );
+                    cell_elems.push(leaf_elem);
+                }
+            }
+        }
+
+        // point data
+        let leaf_values = |q_name: &String| -> Vec<f64> {
+            let quantity = self.quantities.get(q_name).unwrap();
+            leaf_elems
+                .iter()
+                .flat_map(|leaf_elem| quantity.values[&leaf_elem.id].iter().flatten().cloned())
+                .collect()
+        };
+
+        for (vector_name, [x_name, y_name]) in settings.vectors.iter() {
+            let vectors = leaf_values(x_name)
+                .into_iter()
+                .zip(leaf_values(y_name))
+                .map(|(x, y)| [x, y, 0.0])
+                .collect();
+            piece
+                .point_data
+                .push(VtuArray::vectors(vector_name, vectors));
+        }
+
+        for q_name in settings.scalars.iter() {
+            piece.point_data.push(VtuArray::scalars(
+                q_name,
+                VtuValues::Float64(leaf_values(q_name)),
+            ));
+        }
+
+        // cell data
+        let elem_ints = |name: &str, f: &dyn Fn(&Elem) -> i64| {
+            VtuArray::scalars(
+                name,
+                VtuValues::Int64(cell_elems.iter().map(|elem| f(elem)).collect()),
+            )
+        };
+        let elem_floats = |name: &str, f: &dyn Fn(&Elem) -> f64| {
+            VtuArray::scalars(
+                name,
+                VtuValues::Float64(cell_elems.iter().map(|elem| f(elem)).collect()),
+            )
+        };
+
+        piece.cell_data.extend([
+            elem_ints("elem_id", &|elem| elem.id as i64),
+            elem_ints("h_level_u", &|elem| elem.h_levels.u as i64),
+            elem_ints("h_level_v", &|elem| elem.h_levels.v as i64),
+            elem_ints("poly_order_u", &|elem| elem.poly_orders.ni as i64),
+            elem_ints("poly_order_v", &|elem| elem.poly_orders.nj as i64),
+            elem_floats("eps_rel", &|elem| elem.element.materials.eps_rel.re),
+            elem_floats("mu_rel", &|elem| elem.element.materials.mu_rel.re),
+        ]);
+
+        for (data_name, elem_values) in settings.elem_data.iter() {
+            piece.cell_data.push(elem_floats(data_name, &|elem| {
+                *elem_values.get(&elem.id).unwrap_or(&f64::NAN)
+            }));
+        }
+
+        piece.write(path, settings.encoding)?;
+        Ok(())
+    }
+
+    /// create a VTK XML Unstructured Grid file (`name.vtu`) at the designated `path` which outlines the boundaries of each leaf-`Elem`
+    ///
+    /// Each line segment is labeled with the ID of its `Elem` (`elem_id`). This can be overlaid on top of the field data to visualize the refinement state of the Mesh.
+    ///
+    /// Can return an IO error if the file cannot be written
+    pub fn print_outline_to_vtu(
+        &self,
+        path: impl AsRef<str>,
+        encoding: VtuEncoding,
+    ) -> std::io::Result<()> {
+        leaf_outline_piece(&self.domain.mesh).write(path, encoding)
+    }
+
     /// Map an operation over a field quantity (`name`) and store the result in a new quantity (`result_name`)
     ///
     /// Returns a `UniformFieldError` if the quantity `name` is not found in the Field Space. If `result_name` already exists, it is overwritten.
@@ -260,22 +531,14 @@ impl<'d> UniformFieldSpace<'d> {
         operator: F,
     ) -> Result<(), UniformFieldError>
     where
-        F: Fn(&f64) -> f64 + Copy,
+        F: Fn(&f64) -> f64,
     {
-        let q_key = String::from(name.as_ref());
-        let q_new_key = String::from(result_name.as_ref());
-
-        if !self.quantities.contains_key(&q_key) {
-            Err(UniformFieldError::MissingQuantity(q_key))
-        } else {
-            let q_new = self
-                .quantities
-                .get(&q_key)
-                .unwrap()
-                .operation(operator, &q_new_key);
-            self.quantities.insert(q_new_key, q_new);
-            Ok(())
-        }
+        map_quantity(
+            &mut self.quantities,
+            name.as_ref(),
+            result_name.as_ref(),
+            |value| operator(&value),
+        )
     }
 
     /// Evaluate an expression of two field quantities and store the result in a new quantity (`result_name`)
@@ -307,38 +570,465 @@ impl<'d> UniformFieldSpace<'d> {
     where
         F: Fn(f64, f64) -> f64,
     {
-        let op_a = String::from(operand_names[0].as_ref());
-        let op_b = String::from(operand_names[1].as_ref());
-        let q_new_key = String::from(result_name.as_ref());
-
-        if !self.quantities.contains_key(&op_a) {
-            Err(UniformFieldError::MissingQuantity(op_a))
-        } else if !self.quantities.contains_key(&op_b) {
-            Err(UniformFieldError::MissingQuantity(op_b))
+        combine_quantities(
+            &mut self.quantities,
+            operand_names.each_ref().map(|name| name.as_ref()),
+            result_name.as_ref(),
+            expression,
+        )
+    }
+
+    // TODO: implement 3arg, Narg, and convolution.
+}
+
+/// Defines the contents and encoding of a VTK XML file exported from a [UniformFieldSpace]
+#[derive(Clone, Debug, Default)]
+pub struct VtuSettings {
+    /// Encoding of the data arrays
+    pub encoding: VtuEncoding,
+    /// Names of the scalar Field Quantities to include as point data
+    pub scalars: Vec<String>,
+    /// Vector-valued point data: a name and the names of the X and Y Field Quantities (stored as 3-component arrays)
+    pub vectors: Vec<(String, [String; 2])>,
+    /// Per-`Elem` values (such as error indicators) to include as cell data. Leaf-`Elem`s without a value are assigned `NaN`
+    pub elem_data: Vec<(String, BTreeMap<usize, f64>)>,
+}
+
+impl VtuSettings {
+    /// Create an empty set of VTU export settings with a given encoding
+    pub fn new(encoding: VtuEncoding) -> Self {
+        Self {
+            encoding,
+            ..Default::default()
+        }
+    }
+
+    /// Include a scalar Field Quantity
+    pub fn with_scalar(mut self, name: impl AsRef<str>) -> Self {
+        self.scalars.push(name.as_ref().to_string());
+        self
+    }
+
+    /// Include a vector built from a pair of X and Y Field Quantities
+    pub fn with_vector(mut self, name: impl AsRef<str>, xy_names: [String; 2]) -> Self {
+        self.vectors.push((name.as_ref().to_string(), xy_names));
+        self
+    }
+
+    /// Include a set of per-`Elem` values as cell data
+    pub fn with_elem_data(mut self, name: impl AsRef<str>, values: BTreeMap<usize, f64>) -> Self {
+        self.elem_data.push((name.as_ref().to_string(), values));
+        self
+    }
+}
+
+/// A collection of Field Solutions sampled over a uniform grid of pixels covering a [Domain]
+///
+/// Unlike the [UniformFieldSpace], the density of the grid is constant over the entire Domain (regardless of the size of the leaf-`Elem`s), so solutions can be printed directly to images.
+pub struct RasterFieldSpace<'d> {
+    quantities: HashMap<String, Vec<f64>>,
+    resolution: [usize; 2],
+    bounds: [f64; 4],
+    domain: &'d Domain,
+}
+
+// the field, curl, and divergence at a pixel
+type PixelSample = (V2D, f64, f64);
+
+impl<'d> RasterFieldSpace<'d> {
+    /// Generate a RasterFieldSpace over the bounding box of a [Domain] with a given `width` in pixels
+    ///
+    /// The height is chosen such that the pixels are (approximately) square.
+    pub fn new(domain: &'d Domain, width: usize) -> Self {
+        let bounds = domain.mesh.bounds();
+        let width = width.max(1);
+        let height = ((width as f64) * (bounds[3] - bounds[2]) / (bounds[1] - bounds[0]))
+            .round()
+            .max(1.0) as usize;
+
+        Self {
+            quantities: HashMap::new(),
+            resolution: [width, height],
+            bounds,
+            domain,
+        }
+    }
+
+    /// The number of pixels in the x and y directions
+    pub fn resolution(&self) -> [usize; 2] {
+        self.resolution
+    }
+
+    /// The real-space location of the center of a pixel (with rows counted from the top of the image)
+    pub fn pixel_center(&self, [col, row]: [usize; 2]) -> Point {
+        let [x_min, x_max, y_min, y_max] = self.bounds;
+        Point::new(
+            x_min + (col as f64 + 0.5) * (x_max - x_min) / self.resolution[0] as f64,
+            y_max - (row as f64 + 0.5) * (y_max - y_min) / self.resolution[1] as f64,
+        )
+    }
+
+    /// The values of a Field Quantity in row-major order (starting from the top-left pixel)
+    ///
+    /// Pixels that fall outside of the Mesh have a value of `NaN`
+    pub fn values(&self, name: impl AsRef<str>) -> Option<&[f64]> {
+        self.quantities
+            .get(name.as_ref())
+            .map(|values| values.as_slice())
+    }
+
+    /// Use an eigenvector and associated [HierCurlBasisFnSpace] to compute the X and Y fields over the [Domain]
+    ///
+    /// The X and Y field quantities will be stored as {vector_name}_x and {vector_name}_y respectively. The Names are returned in an array in that order.
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::unit();
+    /// mesh.global_h_refinement(HRef::t());
+    /// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    /// let solution: Vec<f64> = (0..domain.dofs.len()).map(|i| (i as f64).sin()).collect();
+    ///
+    /// let mut rfs = RasterFieldSpace::new(&domain, 40);
+    /// assert_eq!(rfs.resolution(), [40, 40]);
+    ///
+    /// let [x_name, _] = rfs.xy_fields::<HierPoly>("E", solution.clone()).unwrap();
+    ///
+    /// // pixel values match the solution sampled at the pixel centers
+    /// let center = rfs.pixel_center([3, 17]);
+    /// let sample = sample_point::<HierPoly>(&domain, &solution, &center).unwrap();
+    /// assert!((rfs.values(&x_name).unwrap()[17 * 40 + 3] - sample.field.x()).abs() < 1e-12);
+    /// ```
+    pub fn xy_fields<BSpace: HierCurlBasisFnSpace>(
+        &mut self,
+        vector_name: &'static str,
+        solution: Vec<f64>,
+    ) -> Result<[String; 2], UniformFieldError> {
+        let samples = self.sample_pixels::<BSpace>(&solution)?;
+        let x_q_name = format!("{}_x", vector_name);
+        let y_q_name = format!("{}_y", vector_name);
+
+        self.quantities.insert(
+            x_q_name.clone(),
+            samples
+                .iter()
+                .map(|s| s.map_or(f64::NAN, |(field, _, _)| field.x()))
+                .collect(),
+        );
+        self.quantities.insert(
+            y_q_name.clone(),
+            samples
+                .iter()
+                .map(|s| s.map_or(f64::NAN, |(field, _, _)| field.y()))
+                .collect(),
+        );
+
+        Ok([x_q_name, y_q_name])
+    }
+
+    /// Use an eigenvector and associated [HierCurlBasisFnSpace] to compute the (z-directed) curl and the divergence of the field over the [Domain]
+    ///
+    /// The curl and divergence quantities will be stored as {vector_name}_curl and {vector_name}_div respectively. The Names are returned in an array in that order.
+    pub fn curl_div_fields<BSpace: HierCurlBasisFnSpace>(
+        &mut self,
+        vector_name: &'static str,
+        solution: Vec<f64>,
+    ) -> Result<[String; 2], UniformFieldError> {
+        let samples = self.sample_pixels::<BSpace>(&solution)?;
+        let curl_q_name = format!("{}_curl", vector_name);
+        let div_q_name = format!("{}_div", vector_name);
+
+        self.quantities.insert(
+            curl_q_name.clone(),
+            samples
+                .iter()
+                .map(|s| s.map_or(f64::NAN, |(_, curl, _)| curl))
+                .collect(),
+        );
+        self.quantities.insert(
+            div_q_name.clone(),
+            samples
+                .iter()
+                .map(|s| s.map_or(f64::NAN, |(_, _, div)| div))
+                .collect(),
+        );
+
+        Ok([curl_q_name, div_q_name])
+    }
+
+    /// Map an operation over a field quantity (`name`) and store the result in a new quantity (`result_name`)
+    ///
+    /// Returns a `UniformFieldError` if the quantity `name` is not found in the Field Space. If `result_name` already exists, it is overwritten.
+    pub fn map_to_quantity<F>(
+        &mut self,
+        name: impl AsRef<str>,
+        result_name: impl AsRef<str>,
+        operator: F,
+    ) -> Result<(), UniformFieldError>
+    where
+        F: Fn(&f64) -> f64,
+    {
+        map_quantity(
+            &mut self.quantities,
+            name.as_ref(),
+            result_name.as_ref(),
+            |value| operator(&value),
+        )
+    }
+
+    /// Evaluate an expression of two field quantities and store the result in a new quantity (`result_name`)
+    ///
+    /// Returns a `UniformFieldError` if either of the operand names is not found in the Field Space. If `result_name` already exists, it is overwritten.
+    pub fn expression_2arg<F>(
+        &mut self,
+        operand_names: [impl AsRef<str>; 2],
+        result_name: impl AsRef<str>,
+        expression: F,
+    ) -> Result<(), UniformFieldError>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        combine_quantities(
+            &mut self.quantities,
+            operand_names.each_ref().map(|name| name.as_ref()),
+            result_name.as_ref(),
+            expression,
+        )
+    }
+
+    /// Print a Field Quantity to a binary PPM image at the designated `path`
+    ///
+    /// Can return an IO error if the file cannot be written, or a `UniformFieldError` if the quantity is not found in the Field Space
+    pub fn print_to_ppm(
+        &self,
+        name: impl AsRef<str>,
+        path: impl AsRef<str>,
+        settings: &ImageSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        self.render(name.as_ref(), settings)?.write_ppm(path)?;
+        Ok(())
+    }
+
+    /// Print a Field Quantity to a PNG image at the designated `path`
+    ///
+    /// Can return an IO error if the file cannot be written, or a `UniformFieldError` if the quantity is not found in the Field Space
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+    /// mesh.set_global_expansion_orders([3, 3]).unwrap();
+    /// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    ///
+    /// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+    /// let solution = nalgebra_solve_gep(gep, 1.3).unwrap();
+    ///
+    /// let mut rfs = RasterFieldSpace::new(&domain, 200);
+    /// let xy_names = rfs.xy_fields::<HierPoly>("E", solution.normalized_eigenvector()).unwrap();
+    /// rfs.expression_2arg(xy_names, "E_mag", |x, y| (x * x + y * y).sqrt()).unwrap();
+    ///
+    /// let settings = ImageSettings {
+    ///     colormap: Colormap::Inferno,
+    ///     ..Default::default()
+    /// };
+    /// rfs.print_to_png("E_mag", "./test_output/mesh_a_e_mag.png", &settings).unwrap();
+    /// ```
+    pub fn print_to_png(
+        &self,
+        name: impl AsRef<str>,
+        path: impl AsRef<str>,
+        settings: &ImageSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        self.render(name.as_ref(), settings)?.write_png(path)?;
+        Ok(())
+    }
+
+    fn render(&self, name: &str, settings: &ImageSettings) -> Result<RgbImage, UniformFieldError> {
+        const BACKGROUND: [u8; 3] = [255, 255, 255];
+        const TEXT_COLOR: [u8; 3] = [0, 0, 0];
+        const MARGIN: usize = 8;
+        const BAR_WIDTH: usize = 16;
+        const TEXT_SCALE: usize = 2;
+
+        let values = self
+            .quantities
+            .get(name)
+            .ok_or_else(|| UniformFieldError::MissingQuantity(String::from(name)))?;
+
+        let [v_min, v_max] = settings.range.unwrap_or_else(|| {
+            values
+                .iter()
+                .filter(|v| v.is_finite())
+                .fold([f64::MAX, f64::MIN], |[min, max], v| {
+                    [min.min(*v), max.max(*v)]
+                })
+        });
+        let to_color = |v: f64| {
+            if v_max - v_min > f64::EPSILON * v_max.abs().max(v_min.abs()) {
+                settings.colormap.color((v - v_min) / (v_max - v_min))
+            } else {
+                settings.colormap.color(0.5)
+            }
+        };
+
+        let [width, height] = self.resolution;
+        let labels = [v_max, (v_max + v_min) / 2.0, v_min].map(|v| {
+            if v.is_finite() && v.abs() < f64::MAX {
+                format!("{:.2e}", v)
+            } else {
+                String::new()
+            }
+        });
+        let [label_width, label_height] = labels
+            .iter()
+            .map(|label| RgbImage::text_size(label, TEXT_SCALE))
+            .fold([0, 0], |[w, h], [lw, lh]| [w.max(lw), h.max(lh)]);
+
+        let (image_width, image_height) = if settings.legend {
+            (
+                width + 3 * MARGIN + BAR_WIDTH + label_width,
+                height.max(3 * label_height + 4 * MARGIN),
+            )
         } else {
-            let mut q_new = FieldQuantity::new(&q_new_key);
-            let q_a = self.quantities.get(&op_a).unwrap();
-            let q_b = self.quantities.get(&op_b).unwrap();
-
-            for ((shell_elem_id, elem_values_a), elem_values_b) in
-                q_a.values.iter().zip(q_b.values.values())
-            {
-                let mut result_values = vec![vec![0.0; self.densities[0]]; self.densities[1]];
-                for m in 0..self.densities[0] {
-                    for n in 0..self.densities[1] {
-                        result_values[m][n] = expression(elem_values_a[m][n], elem_values_b[m][n]);
-                    }
+            (width, height)
+        };
+        let mut image = RgbImage::new(image_width, image_height, BACKGROUND);
+
+        for row in 0..height {
+            for col in 0..width {
+                let v = values[row * width + col];
+                if !v.is_nan() {
+                    image.set(col, row, to_color(v));
                 }
+            }
+        }
 
-                q_new.insert_elem_values(*shell_elem_id, result_values);
+        // a color bar with labels at its top, middle and bottom
+        if settings.legend {
+            let bar_x = width + MARGIN;
+            let bar_height = image_height - 2 * MARGIN;
+            for row in 0..bar_height {
+                let t = 1.0 - row as f64 / (bar_height - 1).max(1) as f64;
+                image.fill_rect(
+                    [bar_x, MARGIN + row],
+                    [BAR_WIDTH, 1],
+                    settings.colormap.color(t),
+                );
             }
 
-            self.quantities.insert(q_new_key, q_new);
-            Ok(())
+            let label_x = bar_x + BAR_WIDTH + MARGIN;
+            let label_ys = [
+                MARGIN,
+                (image_height - label_height) / 2,
+                image_height - MARGIN - label_height,
+            ];
+            for (label, label_y) in labels.iter().zip(label_ys) {
+                image.draw_text([label_x, label_y], label, TEXT_SCALE, TEXT_COLOR);
+            }
         }
+
+        Ok(image)
     }
 
-    // TODO: implement 3arg, Narg, and convolution.
+    // sample the solution at the center of each pixel (None for pixels outside of the Mesh)
+    fn sample_pixels<BSpace: HierCurlBasisFnSpace>(
+        &self,
+        solution: &[f64],
+    ) -> Result<Vec<Option<PixelSample>>, UniformFieldError> {
+        if solution.len() != self.domain.dofs.len() {
+            return Err(UniformFieldError::MismatchedSolutionSize(
+                self.domain.dofs.len(),
+                solution.len(),
+            ));
+        }
+
+        let [width, height] = self.resolution;
+        let centers: [Vec<f64>; 2] = [
+            (0..width)
+                .map(|col| self.pixel_center([col, 0]).x)
+                .collect(),
+            (0..height)
+                .map(|row| self.pixel_center([0, row]).y)
+                .collect(),
+        ];
+        let tolerance = 1e-12 * (self.bounds[1] - self.bounds[0]);
+
+        let leaf_samples: Vec<Vec<(usize, PixelSample)>> = self
+            .domain
+            .mesh
+            .elems
+            .par_iter()
+            .filter(|elem| !elem.has_children())
+            .map(|leaf_elem| {
+                let [p0, p1] = self.domain.mesh.elem_diag_points(leaf_elem.id).unwrap();
+                let [cols, rows]: [Vec<usize>; 2] =
+                    [(0, p0.x, p1.x), (1, p0.y, p1.y)].map(|(axis, min, max)| {
+                        (0..centers[axis].len())
+                            .filter(|idx| {
+                                centers[axis][*idx] >= min - tolerance
+                                    && centers[axis][*idx] <= max + tolerance
+                            })
+                            .collect()
+                    });
+                if cols.is_empty() || rows.is_empty() {
+                    return Vec::new();
+                }
+
+                let xs: Vec<f64> = cols.iter().map(|col| centers[0][*col]).collect();
+                let ys: Vec<f64> = rows.iter().map(|row| centers[1][*row]).collect();
+                let local_fields =
+                    sample_local_fields::<BSpace>(self.domain, leaf_elem.id, [&xs, &ys], solution);
+
+                let mut samples = Vec::with_capacity(cols.len() * rows.len());
+                for (m, col) in cols.iter().enumerate() {
+                    for (n, row) in rows.iter().enumerate() {
+                        samples.push((
+                            row * width + col,
+                            (
+                                local_fields.field[m][n],
+                                local_fields.curl[m][n],
+                                local_fields.div[m][n],
+                            ),
+                        ));
+                    }
+                }
+                samples
+            })
+            .collect();
+
+        // pixels on the boundary between leaf-Elems are assigned to the Elem with the lowest ID
+        let mut pixels: Vec<Option<PixelSample>> = vec![None; width * height];
+        for (pixel_idx, sample) in leaf_samples.into_iter().flatten() {
+            if pixels[pixel_idx].is_none() {
+                pixels[pixel_idx] = Some(sample);
+            }
+        }
+
+        Ok(pixels)
+    }
+}
+
+/// Defines how a Field Quantity from a [RasterFieldSpace] is drawn to an image
+#[derive(Clone, Copy, Debug)]
+pub struct ImageSettings {
+    /// The color scale
+    pub colormap: Colormap,
+    /// The values mapped to the bottom and top of the color scale. If `None`, the minimum and maximum values of the quantity are used.
+    pub range: Option<[f64; 2]>,
+    /// Include a color bar (labeled with its top, middle, and bottom values) on the right side of the image
+    pub legend: bool,
+}
+
+impl Default for ImageSettings {
+    fn default() -> Self {
+        Self {
+            colormap: Colormap::default(),
+            range: None,
+            legend: true,
+        }
+    }
 }
 
 struct FieldQuantity {
@@ -380,26 +1070,335 @@ impl FieldQuantity {
 
         Ok(())
     }
+}
 
-    pub fn operation<F>(&self, operator: F, new_name: &str) -> Self
-    where
-        F: Fn(&f64) -> f64 + Copy,
-    {
+// The values of a field quantity, as stored by a Field Space
+trait QuantityValues: Sized {
+    // Apply an operator to each value, producing a new quantity called `name`
+    fn map_values(&self, name: &str, operator: impl Fn(f64) -> f64) -> Self;
+
+    // Combine each value with the corresponding value of another quantity (sampled over the same points), producing a new quantity called `name`
+    fn zip_values(&self, other: &Self, name: &str, expression: impl Fn(f64, f64) -> f64) -> Self;
+}
+
+impl QuantityValues for FieldQuantity {
+    fn map_values(&self, name: &str, operator: impl Fn(f64) -> f64) -> Self {
+        self.zip_values(self, name, |value, _| operator(value))
+    }
+
+    fn zip_values(&self, other: &Self, name: &str, expression: impl Fn(f64, f64) -> f64) -> Self {
         Self {
             values: self
                 .values
                 .iter()
-                .map(|(elem_id, elem_values)| {
+                .zip(other.values.values())
+                .map(|((elem_id, elem_values_a), elem_values_b)| {
                     (
                         *elem_id,
-                        elem_values
+                        elem_values_a
                             .iter()
-                            .map(|col| col.iter().map(operator).collect())
+                            .zip(elem_values_b.iter())
+                            .map(|(col_a, col_b)| {
+                                col_a
+                                    .iter()
+                                    .zip(col_b.iter())
+                                    .map(|(a, b)| expression(*a, *b))
+                                    .collect()
+                            })
                             .collect(),
                     )
                 })
                 .collect(),
-            name: new_name.to_string(),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl QuantityValues for Vec<f64> {
+    fn map_values(&self, _: &str, operator: impl Fn(f64) -> f64) -> Self {
+        self.iter().map(|value| operator(*value)).collect()
+    }
+
+    fn zip_values(&self, other: &Self, _: &str, expression: impl Fn(f64, f64) -> f64) -> Self {
+        self.iter()
+            .zip(other.iter())
+            .map(|(a, b)| expression(*a, *b))
+            .collect()
+    }
+}
+
+// Map an operator over the quantity `name`, and store the result as `result_name` (overwriting any existing quantity)
+fn map_quantity<Q: QuantityValues>(
+    quantities: &mut HashMap<String, Q>,
+    name: &str,
+    result_name: &str,
+    operator: impl Fn(f64) -> f64,
+) -> Result<(), UniformFieldError> {
+    let result = quantities
+        .get(name)
+        .ok_or_else(|| UniformFieldError::MissingQuantity(String::from(name)))?
+        .map_values(result_name, operator);
+    quantities.insert(String::from(result_name), result);
+    Ok(())
+}
+
+// Evaluate an expression of two quantities, and store the result as `result_name` (overwriting any existing quantity)
+fn combine_quantities<Q: QuantityValues>(
+    quantities: &mut HashMap<String, Q>,
+    operand_names: [&str; 2],
+    result_name: &str,
+    expression: impl Fn(f64, f64) -> f64,
+) -> Result<(), UniformFieldError> {
+    let [q_a, q_b] = operand_names.map(|name| {
+        quantities
+            .get(name)
+            .ok_or_else(|| UniformFieldError::MissingQuantity(String::from(name)))
+    });
+    let result = q_a?.zip_values(q_b?, result_name, expression);
+    quantities.insert(String::from(result_name), result);
+    Ok(())
+}
+
+/// Real-Space field quantities sampled over a grid of points on a leaf-`Elem`. Values are indexed by `[m][n]`, where `m` indexes the x-coordinates and `n` indexes the y-coordinates.
+pub(crate) struct LocalFields {
+    /// The vector field
+    pub field: Vec<Vec<V2D>>,
+    /// The (z-directed) curl of the field
+    pub curl: Vec<Vec<f64>>,
+    /// The divergence of the field
+    pub div: Vec<Vec<f64>>,
+    /// The curl of the curl of the field
+    pub curl_curl: Vec<Vec<V2D>>,
+}
+
+/// The value of a solution at a single point in real space
+#[derive(Clone, Copy, Debug)]
+pub struct PointSample {
+    /// The x and y coordinates of the point
+    pub point: [f64; 2],
+    /// The ID of the leaf-`Elem` in which the point was sampled
+    pub elem_id: usize,
+    /// The vector field
+    pub field: V2D,
+    /// The (z-directed) curl of the field
+    pub curl: f64,
+    /// The divergence of the field
+    pub div: f64,
+}
+
+impl PointSample {
+    /// The magnitude of the vector field
+    pub fn magnitude(&self) -> f64 {
+        V2D::dot(self.field, self.field).sqrt()
+    }
+}
+
+/// Evaluate a solution (indexed by DoF ID) at an arbitrary point in real space
+///
+/// The point is located within the Mesh using [Domain::leaf_elem_containing]. Points along the boundary between two or more leaf-`Elem`s are evaluated on the one with the lowest ID.
+///
+/// # Returns
+/// * A [PointSample] with the field, curl, and divergence at the point
+/// * An `Err` if the solution does not match the size of the Domain, or if the point is outside of the Mesh
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+/// let eigen_pair = nalgebra_solve_gep(gep, 1.3).unwrap();
+///
+/// let sample = sample_point::<HierPoly>(&domain, &eigen_pair.vector, &Point::new(0.6, 0.2)).unwrap();
+/// assert_eq!(sample.elem_id, 0);
+/// assert!(sample.magnitude() > 0.0);
+/// assert!(sample.curl.is_finite() && sample.div.is_finite());
+///
+/// // the tangential field is continuous across the material interface at y = 0.5
+/// let below = sample_point::<HierPoly>(&domain, &eigen_pair.vector, &Point::new(0.6, 0.5 - 1e-9)).unwrap();
+/// let above = sample_point::<HierPoly>(&domain, &eigen_pair.vector, &Point::new(0.6, 0.5 + 1e-9)).unwrap();
+/// assert_ne!(below.elem_id, above.elem_id);
+/// assert!((below.field.x() - above.field.x()).abs() < 1e-6);
+///
+/// assert!(sample_point::<HierPoly>(&domain, &eigen_pair.vector, &Point::new(3.0, 0.2)).is_err());
+/// ```
+pub fn sample_point<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    solution: &[f64],
+    point: &Point,
+) -> Result<PointSample, PointSampleError> {
+    check_point_sample_inputs(domain, solution)?;
+    sample_located_point::<BSpace>(domain, solution, point)
+        .ok_or(PointSampleError::PointOutsideMesh(point.x, point.y))
+}
+
+/// Evaluate a solution (indexed by DoF ID) at a collection of points in real space
+///
+/// Points are evaluated in parallel over the Rayon Global Threadpool. Points which fall outside of the Mesh are returned as `None`.
+///
+/// This can be used to interpolate a solution onto some other grid of points.
+pub fn sample_points<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    solution: &[f64],
+    points: &[Point],
+) -> Result<Vec<Option<PointSample>>, PointSampleError> {
+    check_point_sample_inputs(domain, solution)?;
+    Ok(points
+        .par_iter()
+        .map(|point| sample_located_point::<BSpace>(domain, solution, point))
+        .collect())
+}
+
+/// Evaluate a solution (indexed by DoF ID) at `num_points` evenly spaced points along a line segment from `start` to `end` (inclusive)
+///
+/// Points which fall outside of the Mesh are returned as `None`.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+/// let eigen_pair = nalgebra_solve_gep(gep, 1.3).unwrap();
+///
+/// // probe the field along the center of the waveguide
+/// let probe = sample_line::<HierPoly>(
+///     &domain,
+///     &eigen_pair.vector,
+///     &Point::new(0.0, 0.5),
+///     &Point::new(2.0, 0.5),
+///     21,
+/// ).unwrap();
+///
+/// assert_eq!(probe.len(), 21);
+/// assert!(probe.iter().all(|sample| sample.is_some()));
+/// ```
+pub fn sample_line<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    solution: &[f64],
+    start: &Point,
+    end: &Point,
+    num_points: usize,
+) -> Result<Vec<Option<PointSample>>, PointSampleError> {
+    if num_points < 2 {
+        return Err(PointSampleError::TooFewPoints(num_points));
+    }
+    let points: Vec<Point> = uniform_range(0.0, 1.0, num_points)
+        .iter()
+        .map(|t| {
+            Point::new(
+                start.x + t * (end.x - start.x),
+                start.y + t * (end.y - start.y),
+            )
+        })
+        .collect();
+    sample_points::<BSpace>(domain, solution, &points)
+}
+
+fn check_point_sample_inputs(domain: &Domain, solution: &[f64]) -> Result<(), PointSampleError> {
+    if domain.cc != ContinuityCondition::HCurl {
+        return Err(PointSampleError::WrongContinuityCondition(domain.cc));
+    }
+    if solution.len() != domain.dofs.len() {
+        return Err(PointSampleError::MismatchedSolutionSize(
+            domain.dofs.len(),
+            solution.len(),
+        ));
+    }
+    Ok(())
+}
+
+fn sample_located_point<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    solution: &[f64],
+    point: &Point,
+) -> Option<PointSample> {
+    let elem_id = domain.leaf_elem_containing(point)?;
+    let fields = sample_local_fields::<BSpace>(domain, elem_id, [&[point.x], &[point.y]], solution);
+
+    Some(PointSample {
+        point: [point.x, point.y],
+        elem_id,
+        field: fields.field[0][0],
+        curl: fields.curl[0][0],
+        div: fields.div[0][0],
+    })
+}
+
+/// Sample a solution (indexed by DoF ID) over a grid of real-space points (`xs` by `ys`) on a leaf-`Elem`, including the contributions from all of its ancestors' Basis Functions
+///
+/// The points should fall within (or on the boundary of) the leaf-`Elem`. Derivatives are computed with respect to real space.
+pub(crate) fn sample_local_fields<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    leaf_elem_id: usize,
+    [xs, ys]: [&[f64]; 2],
+    solution: &[f64],
+) -> LocalFields {
+    let mut fields = LocalFields {
+        field: vec![vec![V2D::from([0.0, 0.0]); ys.len()]; xs.len()],
+        curl: vec![vec![0.0; ys.len()]; xs.len()],
+        div: vec![vec![0.0; ys.len()]; xs.len()],
+        curl_curl: vec![vec![V2D::from([0.0, 0.0]); ys.len()]; xs.len()],
+    };
+
+    for_each_local_basis_fn::<BSpace, _>(domain, leaf_elem_id, [xs, ys], |bs, [m, n], sample| {
+        let coeff = solution[bs.dof_id.unwrap()];
+        fields.field[m][n] = fields.field[m][n] + sample.field * coeff;
+        fields.curl[m][n] += sample.curl * coeff;
+        fields.div[m][n] += sample.div * coeff;
+        fields.curl_curl[m][n] = fields.curl_curl[m][n] + sample.curl_curl * coeff;
+    });
+
+    fields
+}
+
+/// Visit every Basis Function with support on a leaf-`Elem` (including those on its ancestors) at each point in a grid of real-space points (`xs` by `ys`)
+///
+/// The visitor is called with the [BasisSpec], the index of the point (`[m, n]`), and the Basis Function's [LocalSample] at that point.
+pub(crate) fn for_each_local_basis_fn<BSpace, F>(
+    domain: &Domain,
+    leaf_elem_id: usize,
+    [xs, ys]: [&[f64]; 2],
+    mut visit: F,
+) where
+    BSpace: HierCurlBasisFnSpace,
+    F: FnMut(&BasisSpec, [usize; 2], LocalSample),
+{
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+
+    for anc_elem_id in domain.mesh.ancestor_elems(leaf_elem_id, true).unwrap() {
+        let local_bs = domain.local_basis_specs(anc_elem_id).unwrap();
+        if local_bs.is_empty() {
+            continue;
+        }
+
+        // map the points into the ancestor's local parametric space
+        let [p0, p1] = domain.mesh.elem_diag_points(anc_elem_id).unwrap();
+        let (a, b) = ((p1.x - p0.x) / 2.0, (p1.y - p0.y) / 2.0);
+        let us: Vec<f64> = xs.iter().map(|x| (x - p0.x) / a - 1.0).collect();
+        let vs: Vec<f64> = ys.iter().map(|y| (y - p0.y) / b - 1.0).collect();
+
+        let u_shapes = BSpace::with(i_max as usize, &us, true);
+        let v_shapes = BSpace::with(j_max as usize, &vs, true);
+
+        for bs in local_bs {
+            let orders = [bs.i as usize, bs.j as usize];
+
+            for m in 0..xs.len() {
+                for n in 0..ys.len() {
+                    if let Some(sample) =
+                        local_basis_sample(bs.dir, orders, [&u_shapes, &v_shapes], [m, n], [a, b])
+                    {
+                        visit(bs, [m, n], sample);
+                    }
+                }
+            }
         }
     }
 }
@@ -409,6 +1408,44 @@ fn uniform_range(min: f64, max: f64, n: usize) -> Vec<f64> {
     (0..n).map(|i| (i as f64) * step + min).collect()
 }
 
+/// Error Type for evaluating solutions at arbitrary points
+#[derive(Debug)]
+pub enum PointSampleError {
+    WrongContinuityCondition(ContinuityCondition),
+    MismatchedSolutionSize(usize, usize),
+    PointOutsideMesh(f64, f64),
+    TooFewPoints(usize),
+}
+
+impl fmt::Display for PointSampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongContinuityCondition(cc) => write!(
+                f,
+                "Point sampling is only supported for H(Curl) Domains (found {}); Cannot sample solution!",
+                cc
+            ),
+            Self::MismatchedSolutionSize(dom_size, sol_size) => write!(
+                f,
+                "Domain size ({}) does not match solution size ({}); Cannot sample solution!",
+                dom_size, sol_size
+            ),
+            Self::PointOutsideMesh(x, y) => write!(
+                f,
+                "Point ({}, {}) falls outside of the Mesh; Cannot sample solution!",
+                x, y
+            ),
+            Self::TooFewPoints(n) => write!(
+                f,
+                "At least 2 points are required to sample along a line (found {}); Cannot sample solution!",
+                n
+            ),
+        }
+    }
+}
+
+impl Error for PointSampleError {}
+
 #[derive(Debug)]
 pub enum UniformFieldError {
     MismatchedSolutionSize(usize, usize),
@@ -4,6 +4,8 @@ pub mod edge;
 pub mod elem;
 /// A Finite Element defined in Real Space (Keeps track of physical/geometric properties)
 pub mod element;
+/// Visualization exports of a Mesh's refinement state (VTK, VTU, and SVG)
+pub mod export;
 /// Structures and Functions to facilitate RBS based anisotropic h-refinement
 pub mod h_refinement;
 /// A Point in Real Space
@@ -16,7 +18,7 @@ pub mod space;
 use edge::Edge;
 use elem::{Elem, ElemUninit};
 use element::{Element, Materials};
-use h_refinement::{HRef, HRefError};
+use h_refinement::{HRef, HRefError, MeshIdMap};
 use node::Node;
 use p_refinement::{PRef, PRefError};
 use space::{ParaDir, Point};
@@ -26,7 +28,7 @@ use super::IdTracker;
 use json::{object, JsonValue};
 use smallvec::SmallVec;
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::fs::{read_to_string, File};
 use std::io::BufWriter;
@@ -67,18 +69,25 @@ impl Mesh {
         let unit_element = Arc::new(Element::new(0, points.clone(), Materials::default()));
         let unit_elem = Elem::new(0, [0, 1, 2, 3], [0, 1, 2, 3], unit_element.clone());
 
-        let nodes: Vec<Node> = points
+        let mut nodes: Vec<Node> = points
             .iter()
             .enumerate()
             .map(|(n_id, p)| Node::new(n_id, *p, true))
             .collect();
-        let edges: Vec<Edge> = vec![
+        let mut edges: Vec<Edge> = vec![
             Edge::new(0, [&nodes[0], &nodes[1]], true),
             Edge::new(1, [&nodes[2], &nodes[3]], true),
             Edge::new(2, [&nodes[0], &nodes[2]], true),
             Edge::new(3, [&nodes[1], &nodes[3]], true),
         ];
 
+        for edge in edges.iter_mut() {
+            edge.connect_elem(&unit_elem);
+        }
+        for node in nodes.iter_mut() {
+            node.connect_elem(&unit_elem);
+        }
+
         Self {
             elements: vec![unit_element],
             elems: vec![unit_elem],
@@ -140,10 +149,14 @@ impl Mesh {
     /// }
     /// ```
     pub fn from_file(path: impl AsRef<str>) -> std::io::Result<Self> {
-        // parse mesh file as JSON
         let mesh_file_contents = read_to_string(path.as_ref())?;
-        let mesh_file_json =
-            json::parse(&mesh_file_contents).expect("Unable to parse Mesh File as JSON!");
+        Ok(Self::from_json_str(&mesh_file_contents))
+    }
+
+    /// Construct a Mesh from a string of JSON with the same format as a Mesh file (see [Mesh::from_file])
+    pub fn from_json_str(mesh_json: &str) -> Self {
+        // parse mesh description as JSON
+        let mesh_file_json = json::parse(mesh_json).expect("Unable to parse Mesh File as JSON!");
 
         // extract element material parameters and node_id sets (panicking if JSON format is not correct)
         let (mut element_materials, mut element_node_ids) =
@@ -323,7 +336,7 @@ impl Mesh {
 
         mesh.set_edge_activation();
 
-        Ok(mesh)
+        mesh
     }
 
     /// Print the mesh to a JSON file specified by path
@@ -652,6 +665,82 @@ impl Mesh {
         }
     }
 
+    /// Get the IDs of the leaf-[Elem]s on either side of a leaf-[Edge] (below/left, then above/right)
+    ///
+    /// Where the Edge lies along a hanging node (i.e. the Elem on one side is larger than the Edge), that larger Elem is found through the Edge's ancestors.
+    ///
+    /// # Returns
+    /// * `Ok(None)`: if the Edge has children or is on the boundary of the Mesh
+    /// * `MeshAccessError`: if `edge_id` does not exist
+    /// * `Ok(Some([elem_id_0, elem_id_1]))`: otherwise
+    pub fn leaf_edge_elems(&self, edge_id: usize) -> Result<Option<[usize; 2]>, MeshAccessError> {
+        if edge_id >= self.edges.len() {
+            return Err(MeshAccessError::EdgeDoesNotExist(edge_id));
+        }
+        if self.edges[edge_id].has_children() || self.edges[edge_id].boundary {
+            return Ok(None);
+        }
+
+        let side_elem = |side_idx: usize| {
+            let mut current_edge = &self.edges[edge_id];
+            loop {
+                if let Some(elem_id) = current_edge.last_entry(side_idx) {
+                    return Some(elem_id);
+                }
+                current_edge = &self.edges[current_edge.parent_id()?];
+            }
+        };
+
+        Ok(side_elem(0).zip(side_elem(1)).map(|(e0, e1)| [e0, e1]))
+    }
+
+    /// Get the ID of the leaf-[Elem] containing a point
+    ///
+    /// Points along the boundary between two or more leaf-Elems are assigned to the one with the lowest ID.
+    ///
+    /// # Returns
+    /// * `None`: if the point falls outside of the Mesh
+    /// * `Some(elem_id)`: otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::unit();
+    /// mesh.global_h_refinement(HRef::T);
+    ///
+    /// assert_eq!(mesh.leaf_elem_containing(&Point::new(0.5, 0.5)), Some(4));
+    /// assert_eq!(mesh.leaf_elem_containing(&Point::new(-0.5, 0.5)), Some(3));
+    /// assert_eq!(mesh.leaf_elem_containing(&Point::new(1.5, 0.5)), None);
+    /// ```
+    pub fn leaf_elem_containing(&self, point: &Point) -> Option<usize> {
+        let contains = |elem_id: usize| {
+            let [p0, p1] = self.elem_diag_points(elem_id).unwrap();
+            let tol = p0.dist(p1) * 1e-12;
+            point.x >= p0.x - tol
+                && point.x <= p1.x + tol
+                && point.y >= p0.y - tol
+                && point.y <= p1.y + tol
+        };
+
+        let mut elem_id = self
+            .elems
+            .iter()
+            .filter(|elem| elem.parent_id().is_none())
+            .map(|elem| elem.id)
+            .find(|elem_id| contains(*elem_id))?;
+
+        while let Some(child_ids) = self.elems[elem_id].child_ids() {
+            elem_id = child_ids
+                .iter()
+                .copied()
+                .filter(|child_id| contains(*child_id))
+                .min()?;
+        }
+
+        Some(elem_id)
+    }
+
     /// Compute the window of valid p-refinement magnitudes for an [Elem]
     ///
     /// Valid magnitudes will keep expansion orders within the range `[1, MAX_POLYNOMIAL_ORDER]`
@@ -1178,42 +1267,302 @@ impl Mesh {
     }
 
     pub(crate) fn set_edge_activation(&mut self) {
-        for edge in self.edges.iter_mut() {
+        let activation = self.edge_activation();
+
+        for (edge, state) in self.edges.iter_mut().zip(activation) {
             edge.reset_activation();
+
+            match state {
+                EdgeActivation::Inactive => (),
+                EdgeActivation::Pair(elem_ids) => edge.set_active_elems(elem_ids),
+                EdgeActivation::Boundary(elem_id) => edge.set_active_boundary_elem(elem_id),
+                EdgeActivation::NoPair => panic!("Unable to find active Edge pair over Edge {}; Something must be wrong with the mesh!", edge.id),
+                EdgeActivation::InconsistentChildren => panic!("Children of Edge {} do not have consistent support for Basis Functions; Cannot set activation states!", edge.id),
+                EdgeActivation::NoBoundaryElem => panic!("Unable to find an active Elem along boundary Edge {}; Something must be wrong with the mesh!", edge.id),
+            }
         }
+    }
 
-        let mut base_edge_ids: Vec<usize> = self
+    /// Determine which Elems support Basis Functions over each Edge (indexed by Edge ID) without modifying the Mesh
+    ///
+    /// The smallest pair of Elems along an interior Edge are active, unless both of its children have their own active pairs.
+    /// Tagged boundary Edges (leaf Edges only) support Basis Functions on their smallest Elem.
+    pub(crate) fn edge_activation(&self) -> Vec<EdgeActivation> {
+        let mut activation = vec![EdgeActivation::Inactive; self.edges.len()];
+
+        for base_edge in self
             .edges
             .iter()
             .filter(|edge| edge.parent_id().is_none() && !edge.boundary)
-            .map(|edge| edge.id)
-            .collect();
-
-        for base_edge_id in base_edge_ids.drain(0..) {
-            if !self.rec_set_edge_activation_in_tree(base_edge_id) {
-                panic!("Unable to find active Edge pair over Edge {}; Something must be wrong with the mesh!", base_edge_id);
+        {
+            if !self.rec_edge_activation_in_tree(base_edge.id, &mut activation) {
+                activation[base_edge.id] = EdgeActivation::NoPair;
             }
         }
+
+        for edge in self
+            .edges
+            .iter()
+            .filter(|edge| edge.boundary && edge.boundary_tag().is_some() && !edge.has_children())
+        {
+            activation[edge.id] = match edge.last_entry(0).or_else(|| edge.last_entry(1)) {
+                Some(elem_id) => EdgeActivation::Boundary(elem_id),
+                None => EdgeActivation::NoBoundaryElem,
+            };
+        }
+
+        activation
     }
 
-    fn rec_set_edge_activation_in_tree(&mut self, edge_id: usize) -> bool {
-        if self.edges[edge_id].set_activation() {
-            if let Some(child_edge_ids) = self.edges[edge_id].child_ids() {
-                match (
-                    self.rec_set_edge_activation_in_tree(child_edge_ids[0]),
-                    self.rec_set_edge_activation_in_tree(child_edge_ids[1]),
-                ) {
-                    (true, true) => self.edges[edge_id].reset_activation(),
-                    (false, false) => (),
-                    _ => panic!("Children of Edge {} do not have consistent support for Basis Functions; Cannot set activation states!", edge_id),
-                };
+    fn rec_edge_activation_in_tree(
+        &self,
+        edge_id: usize,
+        activation: &mut [EdgeActivation],
+    ) -> bool {
+        let edge = &self.edges[edge_id];
+        match (edge.last_entry(0), edge.last_entry(1)) {
+            (Some(bl_elem_id), Some(tr_elem_id)) => {
+                activation[edge_id] = EdgeActivation::Pair([bl_elem_id, tr_elem_id]);
+                if let Some(child_edge_ids) = edge.child_ids() {
+                    match (
+                        self.rec_edge_activation_in_tree(child_edge_ids[0], activation),
+                        self.rec_edge_activation_in_tree(child_edge_ids[1], activation),
+                    ) {
+                        (true, true) => activation[edge_id] = EdgeActivation::Inactive,
+                        (false, false) => (),
+                        _ => activation[edge_id] = EdgeActivation::InconsistentChildren,
+                    };
+                }
+                true
             }
-            true
+            _ => false,
+        }
+    }
+
+    // ----------------------------------------------------------------------------------------------------
+    // h-coarsening methods
+    // ----------------------------------------------------------------------------------------------------
+
+    /// Determine if an [Elem] can be h-coarsened (have its children merged back into it)
+    ///
+    /// # Returns
+    /// * `Ok(false)`: if the Elem has no children
+    /// * `Ok(false)`: if any of the Elem's children have been h-refined themselves
+    /// * `MeshAccessError`: if `elem_id` does not exist
+    /// * `Ok(true)`: otherwise
+    pub fn elem_is_h_coarsenable(&self, elem_id: usize) -> Result<bool, MeshAccessError> {
+        if elem_id >= self.elems.len() {
+            Err(MeshAccessError::ElemDoesNotExist(elem_id))
         } else {
-            false
+            Ok(match self.elems[elem_id].child_ids() {
+                Some(child_ids) => child_ids
+                    .iter()
+                    .all(|child_id| !self.elems[*child_id].has_children()),
+                None => false,
+            })
         }
     }
 
+    /// h-Coarsen all [Elem]s in the Mesh that are eligible for h-coarsening (removing one layer of h-refinement)
+    ///
+    /// Returns a [MeshIdMap] describing how the IDs of the Mesh's components were changed
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::unit();
+    /// mesh.global_h_refinement(HRef::T);
+    /// mesh.global_h_refinement(HRef::T);
+    /// assert_eq!(mesh.elems.len(), 21);
+    ///
+    /// mesh.global_h_coarsening();
+    /// assert_eq!(mesh.elems.len(), 5);
+    ///
+    /// mesh.global_h_coarsening();
+    /// assert_eq!(mesh.elems.len(), 1);
+    /// assert_eq!(mesh.edges.len(), 4);
+    /// assert_eq!(mesh.nodes.len(), 4);
+    /// ```
+    pub fn global_h_coarsening(&mut self) -> MeshIdMap {
+        self.execute_h_coarsening(
+            self.elems
+                .iter()
+                .filter(|elem| self.elem_is_h_coarsenable(elem.id).unwrap())
+                .map(|elem| elem.id)
+                .collect(),
+        )
+    }
+
+    /// h-Coarsen a list of [Elem]s by their IDs
+    ///
+    /// The children of each [Elem] are removed, along with any [Edge]s and [Node]s that are no longer connected to the Mesh.
+    /// The remaining components are given compacted IDs (preserving their relative order).
+    /// Coarsened [Elem]s take on the maximum expansion orders of their children.
+    ///
+    /// Returns a [MeshIdMap] describing how the IDs of the Mesh's components were changed.
+    /// DoF-indexed solution vectors can be carried over to a new [Domain](crate::fem_domain::domain::Domain) with [Domain::remap_dof_values](crate::fem_domain::domain::Domain::remap_dof_values).
+    ///
+    /// # Returns an `Err` if
+    /// * any of the `elem_ids` do not exist
+    /// * any of the `elem_ids` are not eligible for h-coarsening
+    /// * there are any duplicate `elem_ids`
+    ///
+    /// If any errors are encountered, none of the coarsenings are executed!
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::unit();
+    /// mesh.h_refine_elems(vec![0], HRef::T).unwrap();
+    /// mesh.h_refine_elems(vec![1, 4], HRef::U(None)).unwrap();
+    /// assert_eq!(mesh.elems.len(), 9);
+    ///
+    /// // Elem 1 and its children cannot be coarsened
+    /// assert!(mesh.h_coarsen_elems(vec![0]).is_err());
+    /// assert!(mesh.h_coarsen_elems(vec![5]).is_err());
+    ///
+    /// // merge Elem 4's children back into it
+    /// let id_map = mesh.h_coarsen_elems(vec![4]).unwrap();
+    /// assert_eq!(mesh.elems.len(), 7);
+    /// assert_eq!(id_map.elem(4), Some(4));
+    /// assert_eq!(id_map.elem(7), None);
+    /// assert_eq!(id_map.elem(8), None);
+    /// assert!(!mesh.elems[4].has_children());
+    /// ```
+    pub fn h_coarsen_elems(&mut self, elem_ids: Vec<usize>) -> Result<MeshIdMap, HRefError> {
+        let mut coarsenings: BTreeSet<usize> = BTreeSet::new();
+        for elem_id in elem_ids {
+            match self.elem_is_h_coarsenable(elem_id) {
+                Ok(true) => {
+                    if !coarsenings.insert(elem_id) {
+                        return Err(HRefError::DuplicateElemIds);
+                    }
+                }
+                Ok(false) => return Err(HRefError::ElemNotCoarsenable(elem_id)),
+                Err(err) => return Err(HRefError::from(err)),
+            }
+        }
+
+        Ok(self.execute_h_coarsening(coarsenings))
+    }
+
+    /// Map a coarsening closure over all [Elem]s in the Mesh that are eligible for h-coarsening
+    ///
+    /// Eligible [Elem]s for which the closure returns `true` are h-coarsened. Returns a [MeshIdMap] describing how the IDs of the Mesh's components were changed.
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+    /// mesh.global_h_refinement(HRef::T);
+    ///
+    /// // only coarsen the Elems with a relative permeability of 2
+    /// mesh.h_coarsen_with_filter(|elem| elem.get_materials().mu_rel.re > 1.5);
+    /// assert_eq!(mesh.elems.len(), 12);
+    /// ```
+    pub fn h_coarsen_with_filter<F>(&mut self, filt: F) -> MeshIdMap
+    where
+        F: Fn(&Elem) -> bool,
+    {
+        self.execute_h_coarsening(
+            self.elems
+                .iter()
+                .filter(|elem| self.elem_is_h_coarsenable(elem.id).unwrap() && filt(elem))
+                .map(|elem| elem.id)
+                .collect(),
+        )
+    }
+
+    fn execute_h_coarsening(&mut self, parent_elem_ids: BTreeSet<usize>) -> MeshIdMap {
+        let mut retained_elems = vec![true; self.elems.len()];
+        let mut retained_edges = vec![true; self.edges.len()];
+        let mut retained_nodes = vec![true; self.nodes.len()];
+
+        // remove the child Elems, and disconnect them from their Edges and Nodes
+        for parent_elem_id in parent_elem_ids {
+            let child_ids = self.elems[parent_elem_id].h_coarsen().unwrap();
+            let mut poly_orders = [1; 2];
+
+            for child_id in child_ids {
+                let child = &self.elems[child_id];
+                poly_orders = child.poly_orders.max_with(poly_orders);
+
+                for edge_id in child.edges {
+                    self.edges[edge_id].disconnect_elem(child_id);
+                }
+                for node_id in child.nodes {
+                    self.nodes[node_id].disconnect_elem(child_id);
+                }
+                retained_elems[child_id] = false;
+            }
+
+            self.elems[parent_elem_id]
+                .poly_orders
+                .set(poly_orders)
+                .unwrap();
+        }
+
+        // remove pairs of child Edges (and their center Node) that are no longer connected to any Elems
+        //      (child Edges always have larger IDs than their parents, so the Edge trees are pruned from the bottom up)
+        for edge_id in (0..self.edges.len()).rev() {
+            if let Some(child_ids) = self.edges[edge_id].child_ids() {
+                if child_ids.iter().all(|child_id| {
+                    !self.edges[*child_id].has_elems() && !self.edges[*child_id].has_children()
+                }) {
+                    let (child_ids, _) = self.edges[edge_id].h_coarsen().unwrap();
+                    for child_id in child_ids {
+                        retained_edges[child_id] = false;
+                    }
+                }
+            }
+        }
+
+        // remove internal Edges and Nodes that are no longer connected to any Elems
+        for edge in self.edges.iter() {
+            if edge.parent_id().is_none() && !edge.has_elems() {
+                retained_edges[edge.id] = false;
+            }
+        }
+        for node in self.nodes.iter() {
+            if !node.has_elems() {
+                retained_nodes[node.id] = false;
+            }
+        }
+
+        // compact the Mesh and update the IDs of the remaining components
+        let id_map = MeshIdMap::from_retained(&retained_elems, &retained_edges, &retained_nodes);
+
+        self.elems = std::mem::take(&mut self.elems)
+            .into_iter()
+            .filter(|elem| retained_elems[elem.id])
+            .collect();
+        self.edges = std::mem::take(&mut self.edges)
+            .into_iter()
+            .filter(|edge| retained_edges[edge.id])
+            .collect();
+        self.nodes = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .filter(|node| retained_nodes[node.id])
+            .collect();
+
+        for elem in self.elems.iter_mut() {
+            elem.remap_ids(&id_map);
+        }
+        for edge in self.edges.iter_mut() {
+            edge.remap_ids(&id_map);
+        }
+        for node in self.nodes.iter_mut() {
+            node.remap_ids(&id_map);
+        }
+
+        self.set_edge_activation();
+
+        id_map
+    }
+
     // ----------------------------------------------------------------------------------------------------
     // p-refinement methods
     // ----------------------------------------------------------------------------------------------------
@@ -1700,6 +2049,89 @@ impl Mesh {
 
         Ok(())
     }
+
+    // ----------------------------------------------------------------------------------------------------
+    // boundary condition methods
+    // ----------------------------------------------------------------------------------------------------
+
+    /// Assign a tag to all boundary [Edge]s whose midpoint satisfies the `on_boundary` predicate. Returns the number of Edges that were tagged.
+    ///
+    /// Tags are used to select boundary conditions (ex: absorbing boundaries, surface impedances, or ports) during Galerkin Sampling.
+    /// Edge-type Degrees of Freedom are supported along tagged boundary Edges, while untagged boundary Edges are treated as perfect electric conductors.
+    ///
+    /// Tags are inherited by the children of tagged Edges. Previously assigned tags are overwritten.
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+    ///
+    /// // tag the right-hand side of the mesh (x = 2.0)
+    /// let num_tagged = mesh.tag_boundary_edges(1, |point| (point.x - 2.0).abs() < 1e-12);
+    /// assert_eq!(num_tagged, 2);
+    ///
+    /// // child Edges inherit the tag
+    /// mesh.global_h_refinement(HRef::T);
+    /// assert_eq!(mesh.edges.iter().filter(|edge| edge.boundary_tag() == Some(1)).count(), 6);
+    /// ```
+    pub fn tag_boundary_edges<F>(&mut self, tag: usize, on_boundary: F) -> usize
+    where
+        F: Fn(Point) -> bool,
+    {
+        let edge_ids: Vec<usize> = self
+            .edges
+            .iter()
+            .filter(|edge| edge.boundary)
+            .filter(|edge| {
+                let [p0, p1] = self.edge_points(edge.id).unwrap();
+                on_boundary(Point::between(p0, p1))
+            })
+            .map(|edge| edge.id)
+            .collect();
+
+        for edge_id in edge_ids.iter() {
+            self.edges[*edge_id].set_boundary_tag(tag);
+        }
+
+        edge_ids.len()
+    }
+
+    /// Replace the material parameters of each `Element` with the result of a closure
+    ///
+    /// The Mesh's geometry and refinement state are unaffected, so a Domain constructed afterwards has the same Degrees of Freedom
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+    ///
+    /// // double the permittivity of the first Element
+    /// mesh.set_element_materials(|element| {
+    ///     let mut materials = element.materials.clone();
+    ///     if element.id == 0 {
+    ///         materials.eps_rel *= 2.0;
+    ///     }
+    ///     materials
+    /// });
+    ///
+    /// assert!((mesh.elems[0].get_materials().eps_rel.re - 2.0).abs() < 1e-14);
+    /// ```
+    pub fn set_element_materials<F>(&mut self, materials: F)
+    where
+        F: Fn(&Element) -> Materials,
+    {
+        self.elements = self
+            .elements
+            .iter()
+            .map(|element| Arc::new(Element::new(element.id, element.points, materials(element))))
+            .collect();
+
+        for elem in self.elems.iter_mut() {
+            elem.element = self.elements[elem.element.id].clone();
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------------------------------
@@ -1833,6 +2265,23 @@ where
     false
 }
 
+/// The support for Basis Functions along an Edge (see [Mesh::edge_activation])
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EdgeActivation {
+    /// The Edge doesn't support any Basis Functions itself (they are supported by its ancestors or descendants, or it is an untagged boundary Edge)
+    Inactive,
+    /// The pair of Elems supporting Basis Functions over an interior Edge
+    Pair([usize; 2]),
+    /// The Elem supporting Basis Functions along a tagged boundary Edge
+    Boundary(usize),
+    /// No pair of Elems could be found along a base interior Edge
+    NoPair,
+    /// Only one of the interior Edge's children has an active pair
+    InconsistentChildren,
+    /// No Elem could be found along a tagged boundary Edge
+    NoBoundaryElem,
+}
+
 #[derive(Debug)]
 pub enum MeshAccessError {
     ElemDoesNotExist(usize),
@@ -1936,6 +2385,67 @@ mod tests {
         mesh_c.h_refine_elems(vec![3, 4], HRef::V(None)).unwrap();
     }
 
+    #[test]
+    fn h_coarsening_reverts_refinement() {
+        let mut reference = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        reference.global_h_refinement(HRef::T);
+        reference.h_refine_elems(vec![5], HRef::U(None)).unwrap();
+
+        let mut mesh = reference.clone();
+        mesh.h_refine_elems(vec![4, 6], HRef::T).unwrap();
+        mesh.h_refine_elems(vec![7], HRef::V(None)).unwrap();
+        let id_map = mesh.h_coarsen_elems(vec![4, 6, 7]).unwrap();
+
+        assert_eq!(mesh.elems.len(), reference.elems.len());
+        assert_eq!(mesh.edges.len(), reference.edges.len());
+        assert_eq!(mesh.nodes.len(), reference.nodes.len());
+        assert!(!id_map.is_identity());
+
+        // the retained components are unchanged
+        for (elem, ref_elem) in mesh.elems.iter().zip(reference.elems.iter()) {
+            assert_eq!(elem.id, ref_elem.id);
+            assert_eq!(elem.nodes, ref_elem.nodes);
+            assert_eq!(elem.edges, ref_elem.edges);
+            assert_eq!(elem.child_ids(), ref_elem.child_ids());
+            assert_eq!(elem.parent_id(), ref_elem.parent_id());
+        }
+        for (edge, ref_edge) in mesh.edges.iter().zip(reference.edges.iter()) {
+            assert_eq!(edge.nodes, ref_edge.nodes);
+            assert_eq!(edge.child_ids(), ref_edge.child_ids());
+            assert_eq!(edge.child_node_id(), ref_edge.child_node_id());
+            assert_eq!(edge.active_elem_pair(), ref_edge.active_elem_pair());
+        }
+
+        // the coarsened mesh can be refined again
+        mesh.h_refine_elems(vec![4], HRef::T).unwrap();
+        mesh.global_h_coarsening();
+        mesh.global_h_coarsening();
+        assert_eq!(mesh.elems.len(), 4);
+        assert_eq!(mesh.edges.len(), 12);
+        assert_eq!(mesh.nodes.len(), 9);
+    }
+
+    #[test]
+    fn h_coarsening_expansion_orders() {
+        let mut mesh = Mesh::unit();
+        mesh.global_h_refinement(HRef::T);
+        mesh.set_expansion_orders(vec![(1, [2, 3]), (4, [4, 1])])
+            .unwrap();
+
+        mesh.global_h_coarsening();
+        assert_eq!(mesh.elems[0].poly_orders.ni, 4);
+        assert_eq!(mesh.elems[0].poly_orders.nj, 3);
+
+        assert!(matches!(
+            mesh.h_coarsen_elems(vec![0]),
+            Err(HRefError::ElemNotCoarsenable(0))
+        ));
+        assert!(matches!(
+            mesh.h_coarsen_elems(vec![1]),
+            Err(HRefError::ElemDoesNotExist(1))
+        ));
+    }
+
     #[test]
     fn basic_p_refinements() {
         let mut mesh_b = Mesh::from_file("./test_input/test_mesh_b.json").unwrap();
@@ -1,4 +1,10 @@
-use super::{elem::Elem, h_refinement::HRefError, node::Node, space::ParaDir, MIN_EDGE_LENGTH};
+use super::{
+    elem::Elem,
+    h_refinement::{HRefError, MeshIdMap},
+    node::Node,
+    space::ParaDir,
+    MIN_EDGE_LENGTH,
+};
 use json::{array, object, JsonValue};
 use smallvec::{smallvec, SmallVec};
 use std::collections::BTreeMap;
@@ -73,6 +79,8 @@ pub struct Edge {
     elems: [BTreeMap<[u8; 2], usize>; 2],
     active_elems: Option<[usize; 2]>,
     child_node: Option<usize>,
+    boundary_tag: Option<usize>,
+    active_boundary_elem: Option<usize>,
 }
 
 impl Edge {
@@ -91,6 +99,8 @@ impl Edge {
             elems: [BTreeMap::new(), BTreeMap::new()],
             active_elems: None,
             child_node: None,
+            boundary_tag: None,
+            active_boundary_elem: None,
         }
     }
 
@@ -150,6 +160,8 @@ impl Edge {
                             elems: [BTreeMap::new(), BTreeMap::new()],
                             active_elems: None,
                             child_node: None,
+                            boundary_tag: self.boundary_tag,
+                            active_boundary_elem: None,
                         },
                         Self {
                             id: new_ids[1],
@@ -162,6 +174,8 @@ impl Edge {
                             elems: [BTreeMap::new(), BTreeMap::new()],
                             active_elems: None,
                             child_node: None,
+                            boundary_tag: self.boundary_tag,
+                            active_boundary_elem: None,
                         },
                     ])
                 }
@@ -169,6 +183,43 @@ impl Edge {
         }
     }
 
+    /// Remove an Elem's connection to this Edge
+    pub(crate) fn disconnect_elem(&mut self, elem_id: usize) {
+        for side in self.elems.iter_mut() {
+            side.retain(|_, connected_id| *connected_id != elem_id);
+        }
+    }
+
+    /// Is this Edge connected to any Elems
+    pub(crate) fn has_elems(&self) -> bool {
+        !(self.elems[0].is_empty() && self.elems[1].is_empty())
+    }
+
+    /// Remove this Edge's connections to its children and its center Node (returning their ids)
+    pub(crate) fn h_coarsen(&mut self) -> Option<([usize; 2], usize)> {
+        match (self.children.take(), self.child_node.take()) {
+            (Some(child_ids), Some(child_node_id)) => Some((child_ids, child_node_id)),
+            _ => None,
+        }
+    }
+
+    /// Update the IDs of this Edge and its connections after the Mesh has been compacted
+    pub(crate) fn remap_ids(&mut self, id_map: &MeshIdMap) {
+        self.id = id_map.edge(self.id).unwrap();
+        self.nodes = self.nodes.map(|node_id| id_map.node(node_id).unwrap());
+        self.children = self
+            .children
+            .map(|child_ids| child_ids.map(|child_id| id_map.edge(child_id).unwrap()));
+        self.parent = self.parent.map(|parent_id| id_map.edge(parent_id).unwrap());
+        self.child_node = self.child_node.map(|node_id| id_map.node(node_id).unwrap());
+        for side in self.elems.iter_mut() {
+            for elem_id in side.values_mut() {
+                *elem_id = id_map.elem(*elem_id).unwrap();
+            }
+        }
+        self.reset_activation();
+    }
+
     /// Id of the Parent Edge if this Edge has a parent
     pub fn parent_id(&self) -> Option<usize> {
         self.parent
@@ -199,21 +250,12 @@ impl Edge {
         self.elems[0].is_empty() || self.elems[1].is_empty()
     }
 
-    /// Attempts to establish an active pair of Elems. Returns false if none can be established
-    pub(crate) fn set_activation(&mut self) -> bool {
-        match (self.last_entry(0), self.last_entry(1)) {
-            (Some(bl_elem_id), Some(tr_elem_id)) => {
-                self.active_elems = Some([bl_elem_id, tr_elem_id]);
-                true
-            }
-            (_, _) => {
-                self.active_elems = None;
-                false
-            }
-        }
+    pub(crate) fn set_active_elems(&mut self, elem_ids: [usize; 2]) {
+        self.active_elems = Some(elem_ids);
     }
 
-    fn last_entry(&self, side_idx: usize) -> Option<usize> {
+    /// The smallest Elem connected to this Edge on one of its sides (0: below or left, 1: above or right)
+    pub(crate) fn last_entry(&self, side_idx: usize) -> Option<usize> {
         if let Some((_, elem_id)) = self.elems[side_idx].iter().rev().take(1).next() {
             Some(*elem_id)
         } else {
@@ -223,6 +265,25 @@ impl Edge {
 
     pub(crate) fn reset_activation(&mut self) {
         self.active_elems = None;
+        self.active_boundary_elem = None;
+    }
+
+    /// The tag used to select boundary conditions along this Edge (`None` for untagged and interior Edges)
+    pub fn boundary_tag(&self) -> Option<usize> {
+        self.boundary_tag
+    }
+
+    pub(crate) fn set_boundary_tag(&mut self, tag: usize) {
+        self.boundary_tag = Some(tag);
+    }
+
+    /// Which Elem should support edge-type Shape Functions along this tagged boundary Edge (if any)
+    pub fn active_boundary_elem(&self) -> Option<usize> {
+        self.active_boundary_elem
+    }
+
+    pub(crate) fn set_active_boundary_elem(&mut self, elem_id: usize) {
+        self.active_boundary_elem = Some(elem_id);
     }
 
     /// Get the ID of the other active `Elem` connected to this Edge
@@ -267,7 +328,8 @@ impl Edge {
             "elems": array![array![], array![]],
             "active_elems": self.active_elems.map_or(array![], |[ee_bl, ee_tr]| {
                 array![ee_bl, ee_tr]
-            })
+            }),
+            "boundary_tag": self.boundary_tag,
         };
 
         for side_idx in 0..2 {
@@ -1,8 +1,8 @@
 use super::{
     element::{Element, Materials},
-    h_refinement::{HLevels, HRef, HRefError, HRefLoc},
+    h_refinement::{HLevels, HRef, HRefError, HRefLoc, MeshIdMap},
     p_refinement::PolyOrders,
-    space::{M2D, V2D},
+    space::{Point, M2D, V2D},
     EXPECTED_NUM_H_REFINEMENTS,
 };
 use json::{array, object, JsonValue};
@@ -154,6 +154,26 @@ impl Elem {
         }
     }
 
+    /// Remove this Elem's connections to its children (returning their ids)
+    pub(crate) fn h_coarsen(&mut self) -> Option<SmallVec<[usize; 4]>> {
+        self.children.take()
+    }
+
+    /// Update the IDs of this Elem and its connections after the Mesh has been compacted
+    pub(crate) fn remap_ids(&mut self, id_map: &MeshIdMap) {
+        self.id = id_map.elem(self.id).unwrap();
+        self.nodes = self.nodes.map(|node_id| id_map.node(node_id).unwrap());
+        self.edges = self.edges.map(|edge_id| id_map.edge(edge_id).unwrap());
+        if let Some(children) = self.children.as_mut() {
+            for child_id in children.iter_mut() {
+                *child_id = id_map.elem(*child_id).unwrap();
+            }
+        }
+        for (ancestor_id, _) in self.ancestors.iter_mut() {
+            *ancestor_id = id_map.elem(*ancestor_id).unwrap();
+        }
+    }
+
     /// Id of the Parent Elem if this Elem has a parent
     pub fn parent_id(&self) -> Option<usize> {
         self.ancestors.last().map(|(id, _)| *id)
@@ -196,6 +216,15 @@ impl Elem {
             })
     }
 
+    /// Map a point in this `Elem`'s local parametric space (defined over [-1, 1] along both axes) into Real Space
+    pub fn real_point(&self, local_point: V2D) -> Point {
+        let [[u_min, u_max], [v_min, v_max]] = self.parametric_range();
+        self.element.real_point(V2D::from([
+            (local_point.x() + 1.0) * (u_max - u_min) / 2.0 + u_min,
+            (local_point.y() + 1.0) * (v_max - v_min) / 2.0 + v_min,
+        ]))
+    }
+
     /// Get a reference to the Material properties in this region of the Mesh (Defined by this Elem's associated Element)
     pub fn get_materials(&self) -> &Materials {
         &self.element.materials
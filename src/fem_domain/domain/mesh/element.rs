@@ -49,6 +49,27 @@ impl Element {
         M2D::from([dx_du, 0.0], [0.0, dy_dv])
     }
 
+    // TODO: update this method to support curvilinear Elements
+    /// Map a point in the Element's parametric space (defined over [-1, 1] along both axes) into Real Space
+    pub fn real_point(&self, parametric_point: V2D) -> Point {
+        Point::new(
+            map_range(
+                parametric_point.x(),
+                -1.0,
+                1.0,
+                self.points[0].x,
+                self.points[3].x,
+            ),
+            map_range(
+                parametric_point.y(),
+                -1.0,
+                1.0,
+                self.points[0].y,
+                self.points[3].y,
+            ),
+        )
+    }
+
     // TODO: update this method to support curvilinear Elements
     /// Get the ordering of two points within the Element
     ///
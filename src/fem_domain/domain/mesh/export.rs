@@ -0,0 +1,508 @@
+use super::{elem::Elem, EdgeActivation, Mesh};
+use crate::fem_domain::domain::vtu::{
+    VtuArray, VtuEncoding, VtuPiece, VtuValues, VTK_LINE, VTK_QUAD,
+};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// fill colors for SVG exports (cycled through when a quantity takes on more values than there are colors)
+const SVG_PALETTE: [&str; 10] = [
+    "#f7fbff", "#c6dbef", "#9ecae1", "#6baed6", "#fdd0a2", "#fdae6b", "#fd8d3c", "#e6550d",
+    "#a1d99b", "#31a354",
+];
+
+/// The quantity used to color the leaf-`Elem`s in an SVG export of a [Mesh]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MeshColoring {
+    /// The larger of the two expansion orders (`ni` and `nj`). Each `Elem` is labeled with both.
+    PolyOrders,
+    /// The total h-refinement depth (u + v). Each `Elem` is labeled with both levels.
+    HLevels,
+    /// The material parameters. Each `Elem` is labeled with the real parts of ε_r and μ_r.
+    Material,
+}
+
+/// Defines the contents of a visualization export of a [Mesh]
+#[derive(Clone, Copy, Debug)]
+pub struct MeshPlotSettings {
+    /// Include the leaf-`Edge`s, labeled with their ID's and the pair of `Elem`s that support their edge-type Basis Functions
+    pub show_edges: bool,
+    /// The quantity used to color `Elem`s in SVG exports (VTK exports include all quantities as cell data)
+    pub coloring: MeshColoring,
+    /// Width of SVG images in pixels
+    pub svg_width: f64,
+}
+
+impl Default for MeshPlotSettings {
+    fn default() -> Self {
+        Self {
+            show_edges: false,
+            coloring: MeshColoring::PolyOrders,
+            svg_width: 800.0,
+        }
+    }
+}
+
+impl Mesh {
+    /// Print the refinement state of the Mesh to a legacy VTK file specified by path
+    ///
+    /// Each leaf-`Elem` is written as a quadrilateral cell with the following cell data: `elem_id`, `h_level_u`, `h_level_v`, `poly_order_u`, `poly_order_v`, `eps_rel`, and `mu_rel`.
+    ///
+    /// If `settings.show_edges` is set, the leaf-`Edge`s are also written as line cells with the cell data: `edge_id`, `active_edge_id`, `active_elem_0`, and `active_elem_1`, where the "active" Edge is the Edge (or ancestor Edge) whose active `Elem` pair supports Basis Functions over this Edge.
+    /// Values that do not apply to a cell (or do not exist) are set to `-1`.
+    /// The active `Elem` pairs are computed from the current refinement state without modifying the Mesh; Edges that cannot be resolved to an active pair are given `-1` values.
+    ///
+    /// These files can be plotted using [Paraview](https://www.paraview.org/) or [Visit](https://wci.llnl.gov/simulation/computer-codes/visit)
+    pub fn export_to_vtk(
+        &self,
+        path: impl AsRef<str>,
+        settings: &MeshPlotSettings,
+    ) -> std::io::Result<()> {
+        self.refinement_piece(settings.show_edges)
+            .write_legacy(path)
+    }
+
+    /// Print the refinement state of the Mesh to a VTK XML (.vtu) file specified by path
+    ///
+    /// The cell data are the same as those described in [Mesh::export_to_vtk]
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::unit();
+    /// mesh.global_h_refinement(HRef::t());
+    /// mesh.h_refine_elems(vec![1], HRef::u()).unwrap();
+    /// mesh.p_refine_elems(vec![4], PRef::from(2, 1)).unwrap();
+    ///
+    /// let settings = MeshPlotSettings {
+    ///     show_edges: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// mesh.export_to_vtu("./test_output/mesh_refinement_state.vtu", &settings, VtuEncoding::Base64)
+    ///     .unwrap();
+    /// mesh.export_to_svg("./test_output/mesh_refinement_state.svg", &settings)
+    ///     .unwrap();
+    /// ```
+    pub fn export_to_vtu(
+        &self,
+        path: impl AsRef<str>,
+        settings: &MeshPlotSettings,
+        encoding: VtuEncoding,
+    ) -> std::io::Result<()> {
+        self.refinement_piece(settings.show_edges)
+            .write(path, encoding)
+    }
+
+    /// Draw the refinement state of the Mesh to an SVG image specified by path
+    ///
+    /// Leaf-`Elem`s are colored and labeled according to `settings.coloring` (hovering over an `Elem` shows its ID and all of its properties).
+    ///
+    /// If `settings.show_edges` is set, each leaf-`Edge` is labeled with its ID and the active pair of `Elem`s supporting its Basis Functions (`-` if there are none).
+    /// Edges without an active pair (including those that cannot be resolved) are dashed. The Mesh is not modified.
+    pub fn export_to_svg(
+        &self,
+        path: impl AsRef<str>,
+        settings: &MeshPlotSettings,
+    ) -> std::io::Result<()> {
+        let [x_min, x_max, y_min, y_max] = self.bounds();
+        let scale = settings.svg_width / (x_max - x_min);
+        let height = (y_max - y_min) * scale;
+        let to_svg = |x: f64, y: f64| [(x - x_min) * scale, (y_max - y) * scale];
+
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+
+        writeln!(
+            writer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.1}\" height=\"{:.1}\" viewBox=\"0 0 {:.3} {:.3}\">",
+            settings.svg_width, height, settings.svg_width, height
+        )?;
+        writeln!(writer, "<!-- File generated by fem_2d -->")?;
+        writeln!(
+            writer,
+            "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>"
+        )?;
+
+        // the distinct materials in the mesh
+        let mut materials: Vec<[f64; 4]> = Vec::new();
+        for elem in self.elems.iter() {
+            let mat = material_key(elem);
+            if !materials.contains(&mat) {
+                materials.push(mat);
+            }
+        }
+
+        // leaf-elems
+        for elem in self.elems.iter().filter(|elem| !elem.has_children()) {
+            let [p0, p1] = self.elem_diag_points(elem.id).unwrap();
+            let [x0, y0] = to_svg(p0.x, p1.y);
+            let [x1, y1] = to_svg(p1.x, p0.y);
+            let (w, h) = (x1 - x0, y1 - y0);
+
+            let (color_idx, label) = match settings.coloring {
+                MeshColoring::PolyOrders => (
+                    elem.poly_orders.ni.max(elem.poly_orders.nj) as usize,
+                    format!("{},{}", elem.poly_orders.ni, elem.poly_orders.nj),
+                ),
+                MeshColoring::HLevels => (
+                    (elem.h_levels.u + elem.h_levels.v) as usize,
+                    format!("{},{}", elem.h_levels.u, elem.h_levels.v),
+                ),
+                MeshColoring::Material => {
+                    let mat = material_key(elem);
+                    (
+                        materials.iter().position(|m| *m == mat).unwrap(),
+                        format!("{},{}", mat[0], mat[2]),
+                    )
+                }
+            };
+
+            writeln!(
+                writer,
+                "<rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" fill=\"{}\" stroke=\"black\" stroke-width=\"1\"><title>Elem {}: orders [{}, {}]; h-levels [{}, {}]; eps_rel {}; mu_rel {}</title></rect>",
+                x0,
+                y0,
+                w,
+                h,
+                SVG_PALETTE[color_idx % SVG_PALETTE.len()],
+                elem.id,
+                elem.poly_orders.ni,
+                elem.poly_orders.nj,
+                elem.h_levels.u,
+                elem.h_levels.v,
+                elem.element.materials.eps_rel,
+                elem.element.materials.mu_rel,
+            )?;
+            writeln!(
+                writer,
+                "<text x=\"{:.3}\" y=\"{:.3}\" font-size=\"{:.3}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>",
+                x0 + w / 2.0,
+                y0 + h / 2.0,
+                w.min(h) / 5.0,
+                label
+            )?;
+        }
+
+        // leaf-edges
+        if settings.show_edges {
+            let active_pairs = self.governing_active_pairs();
+            for edge in self.edges.iter().filter(|edge| !edge.has_children()) {
+                let [p0, p1] = self.edge_points(edge.id).unwrap();
+                let [x0, y0] = to_svg(p0.x, p0.y);
+                let [x1, y1] = to_svg(p1.x, p1.y);
+
+                let (dash, pair_label) = match active_pairs[edge.id] {
+                    Some((_, [elem_a, elem_b])) => ("", format!("{}|{}", elem_a, elem_b)),
+                    None => (" stroke-dasharray=\"4 2\"", String::from("-")),
+                };
+                let font_size = ((x1 - x0).abs() + (y1 - y0).abs()) / 10.0;
+
+                writeln!(
+                    writer,
+                    "<line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\" stroke=\"red\" stroke-width=\"1.5\"{}/>",
+                    x0, y0, x1, y1, dash
+                )?;
+                writeln!(
+                    writer,
+                    "<text x=\"{:.3}\" y=\"{:.3}\" font-size=\"{:.3}\" fill=\"red\" text-anchor=\"middle\">{}: {}</text>",
+                    (x0 + x1) / 2.0,
+                    (y0 + y1) / 2.0 - font_size / 3.0,
+                    font_size,
+                    edge.id,
+                    pair_label
+                )?;
+            }
+        }
+
+        writeln!(writer, "</svg>")?;
+        writer.flush()
+    }
+
+    // A grid of quadrilaterals over each leaf-Elem (and lines over each leaf-Edge), labeled with their refinement state
+    fn refinement_piece(&self, show_edges: bool) -> VtuPiece {
+        let mut piece = VtuPiece::default();
+        let leaf_elems: Vec<&Elem> = self
+            .elems
+            .iter()
+            .filter(|elem| !elem.has_children())
+            .collect();
+
+        for elem in leaf_elems.iter() {
+            let [p0, p1] = self.elem_diag_points(elem.id).unwrap();
+            let first = piece.points.len();
+            piece.points.extend([
+                [p0.x, p0.y, 0.0],
+                [p1.x, p0.y, 0.0],
+                [p1.x, p1.y, 0.0],
+                [p0.x, p1.y, 0.0],
+            ]);
+            piece.push_cell(&[first, first + 1, first + 2, first + 3], VTK_QUAD);
+        }
+
+        let elem_value = |f: &dyn Fn(&Elem) -> i64| -> Vec<i64> {
+            leaf_elems.iter().map(|elem| f(elem)).collect()
+        };
+        let mut int_data: Vec<(&str, Vec<i64>)> = vec![
+            ("elem_id", elem_value(&|elem| elem.id as i64)),
+            ("h_level_u", elem_value(&|elem| elem.h_levels.u as i64)),
+            ("h_level_v", elem_value(&|elem| elem.h_levels.v as i64)),
+            (
+                "poly_order_u",
+                elem_value(&|elem| elem.poly_orders.ni as i64),
+            ),
+            (
+                "poly_order_v",
+                elem_value(&|elem| elem.poly_orders.nj as i64),
+            ),
+        ];
+        let mut float_data: Vec<(&str, Vec<f64>)> = vec![
+            (
+                "eps_rel",
+                leaf_elems
+                    .iter()
+                    .map(|elem| material_key(elem)[0])
+                    .collect(),
+            ),
+            (
+                "mu_rel",
+                leaf_elems
+                    .iter()
+                    .map(|elem| material_key(elem)[2])
+                    .collect(),
+            ),
+        ];
+
+        if show_edges {
+            let mut edge_data: [Vec<i64>; 4] = [
+                vec![-1; leaf_elems.len()],
+                vec![-1; leaf_elems.len()],
+                vec![-1; leaf_elems.len()],
+                vec![-1; leaf_elems.len()],
+            ];
+
+            let active_pairs = self.governing_active_pairs();
+            for edge in self.edges.iter().filter(|edge| !edge.has_children()) {
+                let [p0, p1] = self.edge_points(edge.id).unwrap();
+                let first = piece.points.len();
+                piece.points.extend([[p0.x, p0.y, 0.0], [p1.x, p1.y, 0.0]]);
+                piece.push_cell(&[first, first + 1], VTK_LINE);
+
+                let (active_edge_id, [elem_a, elem_b]) = match active_pairs[edge.id] {
+                    Some((active_edge_id, pair)) => {
+                        (active_edge_id as i64, [pair[0] as i64, pair[1] as i64])
+                    }
+                    None => (-1, [-1, -1]),
+                };
+
+                for (values, value) in
+                    edge_data
+                        .iter_mut()
+                        .zip([edge.id as i64, active_edge_id, elem_a, elem_b])
+                {
+                    values.push(value);
+                }
+            }
+
+            let num_edge_cells = piece.num_cells() - leaf_elems.len();
+            for (_, values) in int_data.iter_mut() {
+                values.extend(vec![-1; num_edge_cells]);
+            }
+            for (_, values) in float_data.iter_mut() {
+                values.extend(vec![-1.0; num_edge_cells]);
+            }
+
+            let [edge_ids, active_edge_ids, active_elems_0, active_elems_1] = edge_data;
+            int_data.extend([
+                ("edge_id", edge_ids),
+                ("active_edge_id", active_edge_ids),
+                ("active_elem_0", active_elems_0),
+                ("active_elem_1", active_elems_1),
+            ]);
+        }
+
+        for (name, values) in int_data {
+            piece
+                .cell_data
+                .push(VtuArray::scalars(name, VtuValues::Int64(values)));
+        }
+        for (name, values) in float_data {
+            piece
+                .cell_data
+                .push(VtuArray::scalars(name, VtuValues::Float64(values)));
+        }
+
+        piece
+    }
+
+    // The active Elem pair supporting Basis Functions over each Edge (found on the Edge itself or one of its ancestors), along with the ID of the Edge it belongs to
+    //
+    // Boundary Edges give their active Elem twice, and Edges without an active pair (including those that cannot be resolved) give `None`
+    fn governing_active_pairs(&self) -> Vec<Option<(usize, [usize; 2])>> {
+        let activation = self.edge_activation();
+
+        self.edges
+            .iter()
+            .map(|edge| {
+                let mut current = Some(edge.id);
+                while let Some(current_id) = current {
+                    match activation[current_id] {
+                        EdgeActivation::Pair(pair) => return Some((current_id, pair)),
+                        EdgeActivation::Boundary(elem_id) => {
+                            return Some((current_id, [elem_id, elem_id]))
+                        }
+                        _ => current = self.edges[current_id].parent_id(),
+                    }
+                }
+                None
+            })
+            .collect()
+    }
+
+    // [x_min, x_max, y_min, y_max] over all Nodes
+    pub(crate) fn bounds(&self) -> [f64; 4] {
+        self.nodes.iter().fold(
+            [f64::MAX, f64::MIN, f64::MAX, f64::MIN],
+            |[x_min, x_max, y_min, y_max], node| {
+                [
+                    x_min.min(node.coords.x),
+                    x_max.max(node.coords.x),
+                    y_min.min(node.coords.y),
+                    y_max.max(node.coords.y),
+                ]
+            },
+        )
+    }
+}
+
+fn material_key(elem: &Elem) -> [f64; 4] {
+    let mat = &elem.element.materials;
+    [mat.eps_rel.re, mat.eps_rel.im, mat.mu_rel.re, mat.mu_rel.im]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::domain::mesh::{h_refinement::HRef, p_refinement::PRef};
+
+    fn refined_mesh() -> Mesh {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.global_h_refinement(HRef::t());
+        mesh.h_refine_elems(vec![4, 5], HRef::u()).unwrap();
+        mesh.p_refine_elems(vec![6, 7], PRef::from(2, 1)).unwrap();
+        mesh
+    }
+
+    #[test]
+    fn mesh_svg() {
+        let mesh = refined_mesh();
+        let num_leaf_elems = mesh.elems.iter().filter(|e| !e.has_children()).count();
+        let num_leaf_edges = mesh.edges.iter().filter(|e| !e.has_children()).count();
+
+        for (coloring, name) in [
+            (MeshColoring::PolyOrders, "orders"),
+            (MeshColoring::HLevels, "h_levels"),
+            (MeshColoring::Material, "material"),
+        ] {
+            let settings = MeshPlotSettings {
+                show_edges: true,
+                coloring,
+                ..Default::default()
+            };
+            let path = format!("./test_output/mesh_a_{}.svg", name);
+            mesh.export_to_svg(&path, &settings).unwrap();
+
+            let svg = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(svg.matches("<rect x=").count(), num_leaf_elems);
+            assert_eq!(svg.matches("<line ").count(), num_leaf_edges);
+            assert!(svg.trim_end().ends_with("</svg>"));
+        }
+    }
+
+    #[test]
+    fn mesh_vtk() {
+        let mesh = refined_mesh();
+        let num_leaf_elems = mesh.elems.iter().filter(|e| !e.has_children()).count();
+        let num_leaf_edges = mesh.edges.iter().filter(|e| !e.has_children()).count();
+
+        let elems_only = MeshPlotSettings::default();
+        mesh.export_to_vtk("./test_output/mesh_a_state.vtk", &elems_only)
+            .unwrap();
+        let vtk = std::fs::read_to_string("./test_output/mesh_a_state.vtk").unwrap();
+        assert!(vtk.contains(&format!("CELL_DATA {}", num_leaf_elems)));
+        assert!(vtk.contains("SCALARS poly_order_u long 1"));
+        assert!(!vtk.contains("edge_id"));
+
+        let with_edges = MeshPlotSettings {
+            show_edges: true,
+            ..Default::default()
+        };
+        mesh.export_to_vtu(
+            "./test_output/mesh_a_state.vtu",
+            &with_edges,
+            VtuEncoding::Ascii,
+        )
+        .unwrap();
+        let vtu = std::fs::read_to_string("./test_output/mesh_a_state.vtu").unwrap();
+        assert!(vtu.contains(&format!(
+            "NumberOfCells=\"{}\"",
+            num_leaf_elems + num_leaf_edges
+        )));
+        assert!(vtu.contains("Name=\"active_elem_1\""));
+
+        // every interior leaf-edge is governed by an active pair of elems
+        let active_pairs = mesh.governing_active_pairs();
+        for edge in mesh
+            .edges
+            .iter()
+            .filter(|e| !e.has_children() && !e.boundary)
+        {
+            assert!(active_pairs[edge.id].is_some());
+        }
+    }
+
+    #[test]
+    fn export_activation_matches_mesh() {
+        let mut mesh = refined_mesh();
+        mesh.tag_boundary_edges(1, |_| true);
+        let active_pairs = mesh.governing_active_pairs();
+
+        mesh.set_edge_activation();
+        for edge in mesh.edges.iter() {
+            let expected = edge.active_elem_pair().or_else(|| {
+                edge.active_boundary_elem()
+                    .map(|elem_id| [elem_id, elem_id])
+            });
+            if let Some(pair) = expected {
+                assert_eq!(active_pairs[edge.id], Some((edge.id, pair)));
+            }
+        }
+    }
+
+    #[test]
+    fn unresolved_edges() {
+        let mut mesh = refined_mesh();
+        let settings = MeshPlotSettings {
+            show_edges: true,
+            ..Default::default()
+        };
+
+        // remove the elems below an interior edge, so it no longer has an active pair
+        let edge_id = mesh
+            .edges
+            .iter()
+            .find(|e| !e.has_children() && !e.boundary && e.parent_id().is_none())
+            .unwrap()
+            .id;
+        while let Some(elem_id) = mesh.edges[edge_id].last_entry(0) {
+            mesh.edges[edge_id].disconnect_elem(elem_id);
+        }
+
+        assert!(mesh.governing_active_pairs()[edge_id].is_none());
+        mesh.export_to_svg("./test_output/mesh_a_unresolved.svg", &settings)
+            .unwrap();
+        let svg = std::fs::read_to_string("./test_output/mesh_a_unresolved.svg").unwrap();
+        assert!(svg.contains(&format!(">{}: -</text>", edge_id)));
+    }
+}
@@ -292,6 +292,7 @@ pub enum HRefError {
     // Public Errors
     ElemDoesNotExist(usize),
     ElemNotRefineable(usize),
+    ElemNotCoarsenable(usize),
     DuplicateElemIds,
 }
 
@@ -326,6 +327,11 @@ impl fmt::Display for HRefError {
                 "Elem {} cannot be h-refined; it is either too small or has already been refined!",
                 elem_id,
             ),
+            Self::ElemNotCoarsenable(elem_id) => write!(
+                f,
+                "Elem {} cannot be h-coarsened; it either has no children or its children have been refined!",
+                elem_id,
+            ),
             Self::DuplicateElemIds => {
                 write!(f, "Duplicate element ids in h-Refinement; Cannot h-Refine!")
             }
@@ -339,6 +345,68 @@ impl fmt::Display for HRefError {
     }
 }
 
+/// A mapping from the IDs of a Mesh's `Elem`s, `Edge`s, and `Node`s before an h-coarsening to their IDs afterwards
+///
+/// Removed components map to `None`. The relative order of the retained components is preserved.
+#[derive(Debug, Clone)]
+pub struct MeshIdMap {
+    elems: Vec<Option<usize>>,
+    edges: Vec<Option<usize>>,
+    nodes: Vec<Option<usize>>,
+}
+
+impl MeshIdMap {
+    /// Construct a mapping from flags indicating which `Elem`s, `Edge`s, and `Node`s were retained
+    pub(crate) fn from_retained(elems: &[bool], edges: &[bool], nodes: &[bool]) -> Self {
+        let compact = |retained: &[bool]| {
+            let mut next_id = 0;
+            retained
+                .iter()
+                .map(|keep| {
+                    if *keep {
+                        next_id += 1;
+                        Some(next_id - 1)
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        };
+
+        Self {
+            elems: compact(elems),
+            edges: compact(edges),
+            nodes: compact(nodes),
+        }
+    }
+
+    /// The new ID of an `Elem` (`None` if it was removed or did not exist)
+    pub fn elem(&self, old_id: usize) -> Option<usize> {
+        self.elems.get(old_id).copied().flatten()
+    }
+
+    /// The new ID of an `Edge` (`None` if it was removed or did not exist)
+    pub fn edge(&self, old_id: usize) -> Option<usize> {
+        self.edges.get(old_id).copied().flatten()
+    }
+
+    /// The new ID of a `Node` (`None` if it was removed or did not exist)
+    pub fn node(&self, old_id: usize) -> Option<usize> {
+        self.nodes.get(old_id).copied().flatten()
+    }
+
+    /// Number of `Elem`s, `Edge`s, and `Node`s that were removed
+    pub fn num_removed(&self) -> [usize; 3] {
+        [&self.elems, &self.edges, &self.nodes]
+            .map(|ids| ids.iter().filter(|id| id.is_none()).count())
+    }
+
+    /// Does this map leave all IDs unchanged (no components were removed)
+    pub fn is_identity(&self) -> bool {
+        self.num_removed() == [0; 3]
+    }
+}
+
 impl From<MeshAccessError> for HRefError {
     fn from(err: MeshAccessError) -> Self {
         match err {
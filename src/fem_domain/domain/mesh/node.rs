@@ -1,5 +1,6 @@
 use super::super::space::Point;
 use super::elem::Elem;
+use super::h_refinement::MeshIdMap;
 use json::{object, JsonValue};
 use std::collections::BTreeMap;
 
@@ -69,6 +70,28 @@ impl Node {
         }
     }
 
+    /// Remove an Elem's connection to this Node
+    pub(crate) fn disconnect_elem(&mut self, elem_id: usize) {
+        for quadrant in self.elems.iter_mut() {
+            quadrant.retain(|_, connected_id| *connected_id != elem_id);
+        }
+    }
+
+    /// Is this Node connected to any Elems
+    pub(crate) fn has_elems(&self) -> bool {
+        self.elems.iter().any(|quadrant| !quadrant.is_empty())
+    }
+
+    /// Update the IDs of this Node and its connections after the Mesh has been compacted
+    pub(crate) fn remap_ids(&mut self, id_map: &MeshIdMap) {
+        self.id = id_map.node(self.id).unwrap();
+        for quadrant in self.elems.iter_mut() {
+            for elem_id in quadrant.values_mut() {
+                *elem_id = id_map.elem(*elem_id).unwrap();
+            }
+        }
+    }
+
     // pub(crate) fn active_elems(&self) -> Option<[usize; 4]> {
     //     self.active_elems
     // }
@@ -0,0 +1,290 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// the maximum length of a stored (uncompressed) deflate block
+const MAX_STORED_BLOCK: usize = 65535;
+
+// 3x5 pixel glyphs (one row per entry, with the 3 least significant bits as pixels from left to right)
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+const GLYPHS: [(char, [u8; GLYPH_HEIGHT]); 15] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('-', [0b000, 0b000, 0b111, 0b000, 0b000]),
+    ('+', [0b000, 0b010, 0b111, 0b010, 0b000]),
+    ('.', [0b000, 0b000, 0b000, 0b000, 0b010]),
+    ('e', [0b000, 0b111, 0b111, 0b100, 0b111]),
+    (' ', [0b000, 0b000, 0b000, 0b000, 0b000]),
+];
+
+/// Color scales used to map scalar values to pixel colors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Colormap {
+    /// Perceptually uniform dark-blue to yellow scale
+    #[default]
+    Viridis,
+    /// Perceptually uniform black to light-yellow scale
+    Inferno,
+    /// Black to white
+    Grayscale,
+    /// Diverging blue to white to red scale (useful for signed quantities)
+    CoolWarm,
+}
+
+impl Colormap {
+    fn control_points(&self) -> &'static [[u8; 3]] {
+        match self {
+            Self::Viridis => &[
+                [68, 1, 84],
+                [59, 82, 139],
+                [33, 145, 140],
+                [94, 201, 98],
+                [253, 231, 37],
+            ],
+            Self::Inferno => &[
+                [0, 0, 4],
+                [87, 16, 110],
+                [188, 55, 84],
+                [249, 142, 9],
+                [252, 255, 164],
+            ],
+            Self::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+            Self::CoolWarm => &[[59, 76, 192], [221, 221, 221], [180, 4, 38]],
+        }
+    }
+
+    /// Get the color at a location `t` along the scale (`t` is clamped to `[0, 1]`)
+    pub fn color(&self, t: f64) -> [u8; 3] {
+        let points = self.control_points();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        let scaled = t * (points.len() - 1) as f64;
+        let idx = (scaled.floor() as usize).min(points.len() - 2);
+        let frac = scaled - idx as f64;
+
+        let mut color = [0; 3];
+        for (c, (lower, upper)) in color
+            .iter_mut()
+            .zip(points[idx].iter().zip(points[idx + 1].iter()))
+        {
+            *c = (*lower as f64 + frac * (*upper as f64 - *lower as f64)).round() as u8;
+        }
+        color
+    }
+}
+
+/// An 8-bit RGB image
+pub(crate) struct RgbImage {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl RgbImage {
+    pub fn new(width: usize, height: usize, background: [u8; 3]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Set the color of the pixel at column `x` and row `y` (pixels outside of the image are ignored)
+    pub fn set(&mut self, x: usize, y: usize, color: [u8; 3]) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    pub fn fill_rect(&mut self, [x, y]: [usize; 2], [w, h]: [usize; 2], color: [u8; 3]) {
+        for row in y..(y + h) {
+            for col in x..(x + w) {
+                self.set(col, row, color);
+            }
+        }
+    }
+
+    /// Draw text with its top left corner at `[x, y]`, where each glyph pixel is drawn as a `scale` by `scale` square
+    ///
+    /// Only digits and the characters `-+.e ` are supported (others are drawn as blank spaces)
+    pub fn draw_text(&mut self, [x, y]: [usize; 2], text: &str, scale: usize, color: [u8; 3]) {
+        for (char_idx, c) in text.chars().enumerate() {
+            let glyph = GLYPHS
+                .iter()
+                .find(|(gc, _)| *gc == c)
+                .map_or([0; GLYPH_HEIGHT], |(_, rows)| *rows);
+            let x0 = x + char_idx * (GLYPH_WIDTH + 1) * scale;
+
+            for (row, bits) in glyph.iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                        self.fill_rect([x0 + col * scale, y + row * scale], [scale, scale], color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The size of a string of text drawn with [RgbImage::draw_text]
+    pub fn text_size(text: &str, scale: usize) -> [usize; 2] {
+        [
+            text.chars().count() * (GLYPH_WIDTH + 1) * scale,
+            GLYPH_HEIGHT * scale,
+        ]
+    }
+
+    /// Write the image to a binary PPM (P6) file
+    pub fn write_ppm(&self, path: impl AsRef<str>) -> std::io::Result<()> {
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for pixel in self.pixels.iter() {
+            writer.write_all(pixel)?;
+        }
+        writer.flush()
+    }
+
+    /// Write the image to a PNG file (using uncompressed deflate blocks)
+    pub fn write_png(&self, path: impl AsRef<str>) -> std::io::Result<()> {
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+
+        writer.write_all(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A])?;
+
+        // header: dimensions, 8-bit depth, truecolor, default compression/filter/interlacing
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        header.extend([8, 2, 0, 0, 0]);
+        write_png_chunk(&mut writer, b"IHDR", &header)?;
+
+        // scanlines, each preceded by a filter-type byte (none)
+        let mut raw = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.pixels.chunks(self.width) {
+            raw.push(0);
+            row.iter().for_each(|pixel| raw.extend(pixel));
+        }
+        write_png_chunk(&mut writer, b"IDAT", &zlib_stored(&raw))?;
+        write_png_chunk(&mut writer, b"IEND", &[])?;
+
+        writer.flush()
+    }
+}
+
+fn write_png_chunk(
+    writer: &mut impl Write,
+    chunk_type: &[u8; 4],
+    data: &[u8],
+) -> std::io::Result<()> {
+    writer.write_all(&(data.len() as u32).to_be_bytes())?;
+    writer.write_all(chunk_type)?;
+    writer.write_all(data)?;
+
+    let mut crc_data = chunk_type.to_vec();
+    crc_data.extend(data);
+    writer.write_all(&crc32(&crc_data).to_be_bytes())
+}
+
+// wrap data in a zlib stream made up of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let num_blocks = data.len().div_ceil(MAX_STORED_BLOCK).max(1);
+
+    for block_idx in 0..num_blocks {
+        let start = block_idx * MAX_STORED_BLOCK;
+        let end = (start + MAX_STORED_BLOCK).min(data.len());
+        let len = (end - start) as u16;
+
+        stream.push((block_idx + 1 == num_blocks) as u8);
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(&data[start..end]);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn stored_blocks() {
+        let data = vec![7_u8; MAX_STORED_BLOCK + 10];
+        let stream = zlib_stored(&data);
+
+        // zlib header + two block headers + data + adler32
+        assert_eq!(stream.len(), 2 + 2 * 5 + data.len() + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + MAX_STORED_BLOCK], 1);
+    }
+
+    #[test]
+    fn colormaps() {
+        assert_eq!(Colormap::Grayscale.color(0.0), [0, 0, 0]);
+        assert_eq!(Colormap::Grayscale.color(1.0), [255, 255, 255]);
+        assert_eq!(Colormap::Grayscale.color(2.0), [255, 255, 255]);
+        assert_eq!(Colormap::Viridis.color(1.0), [253, 231, 37]);
+        assert_eq!(Colormap::CoolWarm.color(0.5), [221, 221, 221]);
+    }
+
+    #[test]
+    fn image_files() {
+        let mut image = RgbImage::new(20, 10, [255, 255, 255]);
+        image.draw_text([1, 1], "-1.5e+3", 1, [0, 0, 0]);
+        image.set(19, 9, [255, 0, 0]);
+
+        image.write_ppm("./test_output/raster_test.ppm").unwrap();
+        let ppm = std::fs::read("./test_output/raster_test.ppm").unwrap();
+        assert!(ppm.starts_with(b"P6\n20 10\n255\n"));
+        assert_eq!(ppm.len(), 13 + 20 * 10 * 3);
+        assert_eq!(ppm[ppm.len() - 3..], [255, 0, 0]);
+
+        image.write_png("./test_output/raster_test.png").unwrap();
+        let png = std::fs::read("./test_output/raster_test.png").unwrap();
+        assert_eq!(png[1..4], *b"PNG");
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[png.len() - 8..png.len() - 4], *b"IEND");
+    }
+}
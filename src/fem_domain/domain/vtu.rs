@@ -0,0 +1,414 @@
+use super::mesh::Mesh;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// VTK cell type for a line segment
+pub(crate) const VTK_LINE: u8 = 3;
+/// VTK cell type for a four-node quadrilateral
+pub(crate) const VTK_QUAD: u8 = 9;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The encoding of the data arrays in a VTK XML (.vtu) file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VtuEncoding {
+    /// Human readable text (largest files)
+    Ascii,
+    /// Base64-encoded binary data written inline with each array
+    #[default]
+    Base64,
+    /// Raw binary data appended to the end of the file (smallest files)
+    RawBinary,
+}
+
+/// The values of a VTU data array
+pub(crate) enum VtuValues {
+    Float64(Vec<f64>),
+    Int64(Vec<i64>),
+    UInt8(Vec<u8>),
+}
+
+impl VtuValues {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Float64(_) => "Float64",
+            Self::Int64(_) => "Int64",
+            Self::UInt8(_) => "UInt8",
+        }
+    }
+
+    // little-endian bytes prefixed by their length (as a UInt64)
+    fn to_block(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::new();
+        match self {
+            Self::Float64(values) => values.iter().for_each(|v| bytes.extend(v.to_le_bytes())),
+            Self::Int64(values) => values.iter().for_each(|v| bytes.extend(v.to_le_bytes())),
+            Self::UInt8(values) => bytes.extend(values),
+        }
+
+        let mut block = (bytes.len() as u64).to_le_bytes().to_vec();
+        block.extend(bytes);
+        block
+    }
+
+    fn write_ascii(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            Self::Float64(values) => values
+                .iter()
+                .try_for_each(|v| write!(writer, "{:.15} ", v))?,
+            Self::Int64(values) => values.iter().try_for_each(|v| write!(writer, "{} ", v))?,
+            Self::UInt8(values) => values.iter().try_for_each(|v| write!(writer, "{} ", v))?,
+        }
+        Ok(())
+    }
+}
+
+/// A named data array with one or more components per point or cell
+pub(crate) struct VtuArray {
+    pub name: String,
+    pub num_components: usize,
+    pub values: VtuValues,
+}
+
+impl VtuArray {
+    pub fn scalars(name: impl AsRef<str>, values: VtuValues) -> Self {
+        Self {
+            name: name.as_ref().to_string(),
+            num_components: 1,
+            values,
+        }
+    }
+
+    pub fn vectors(name: impl AsRef<str>, values: Vec<[f64; 3]>) -> Self {
+        Self {
+            name: name.as_ref().to_string(),
+            num_components: 3,
+            values: VtuValues::Float64(values.into_iter().flatten().collect()),
+        }
+    }
+}
+
+/// An unstructured grid of points and cells, along with any point and cell data
+#[derive(Default)]
+pub(crate) struct VtuPiece {
+    pub points: Vec<[f64; 3]>,
+    pub connectivity: Vec<i64>,
+    pub offsets: Vec<i64>,
+    pub types: Vec<u8>,
+    pub point_data: Vec<VtuArray>,
+    pub cell_data: Vec<VtuArray>,
+}
+
+impl VtuPiece {
+    /// Add a cell (defined by the indices of its points) to the grid
+    pub fn push_cell(&mut self, point_indices: &[usize], cell_type: u8) {
+        self.connectivity
+            .extend(point_indices.iter().map(|idx| *idx as i64));
+        self.offsets.push(self.connectivity.len() as i64);
+        self.types.push(cell_type);
+    }
+
+    pub fn num_cells(&self) -> usize {
+        self.types.len()
+    }
+
+    /// Write the grid to a VTK XML Unstructured Grid file at `path`
+    pub fn write(&self, path: impl AsRef<str>, encoding: VtuEncoding) -> std::io::Result<()> {
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+        let mut appended: Vec<u8> = Vec::new();
+
+        writeln!(writer, "<?xml version=\"1.0\"?>")?;
+        writeln!(writer, "<!-- File generated by fem_2d -->")?;
+        writeln!(
+            writer,
+            "<VTKFile type=\"UnstructuredGrid\" version=\"1.0\" byte_order=\"LittleEndian\" header_type=\"UInt64\">"
+        )?;
+        writeln!(writer, "<UnstructuredGrid>")?;
+        writeln!(
+            writer,
+            "<Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">",
+            self.points.len(),
+            self.num_cells()
+        )?;
+
+        writeln!(writer, "<Points>")?;
+        let points = VtuArray {
+            name: String::from("Points"),
+            num_components: 3,
+            values: VtuValues::Float64(self.points.iter().flatten().cloned().collect()),
+        };
+        write_array(&mut writer, &points, encoding, &mut appended)?;
+        writeln!(writer, "</Points>")?;
+
+        writeln!(writer, "<Cells>")?;
+        for (name, values) in [
+            ("connectivity", VtuValues::Int64(self.connectivity.clone())),
+            ("offsets", VtuValues::Int64(self.offsets.clone())),
+            ("types", VtuValues::UInt8(self.types.clone())),
+        ] {
+            write_array(
+                &mut writer,
+                &VtuArray::scalars(name, values),
+                encoding,
+                &mut appended,
+            )?;
+        }
+        writeln!(writer, "</Cells>")?;
+
+        writeln!(writer, "<PointData>")?;
+        for array in self.point_data.iter() {
+            write_array(&mut writer, array, encoding, &mut appended)?;
+        }
+        writeln!(writer, "</PointData>")?;
+
+        writeln!(writer, "<CellData>")?;
+        for array in self.cell_data.iter() {
+            write_array(&mut writer, array, encoding, &mut appended)?;
+        }
+        writeln!(writer, "</CellData>")?;
+
+        writeln!(writer, "</Piece>")?;
+        writeln!(writer, "</UnstructuredGrid>")?;
+
+        if encoding == VtuEncoding::RawBinary {
+            write!(writer, "<AppendedData encoding=\"raw\">\n_")?;
+            writer.write_all(&appended)?;
+            writeln!(writer, "\n</AppendedData>")?;
+        }
+
+        writeln!(writer, "</VTKFile>")?;
+        writer.flush()
+    }
+
+    /// Write the grid to a legacy ASCII VTK file at `path`
+    pub fn write_legacy(&self, path: impl AsRef<str>) -> std::io::Result<()> {
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+
+        writeln!(writer, "# vtk DataFile Version 3.0")?;
+        writeln!(writer, "# File generated by fem_2d")?;
+        writeln!(writer, "ASCII")?;
+        writeln!(writer, "DATASET UNSTRUCTURED_GRID")?;
+
+        writeln!(writer, "\nPOINTS {} double", self.points.len())?;
+        for [x, y, z] in self.points.iter() {
+            writeln!(writer, "{:.10} {:.10} {:.10}", x, y, z)?;
+        }
+
+        writeln!(
+            writer,
+            "\nCELLS {} {}",
+            self.num_cells(),
+            self.num_cells() + self.connectivity.len()
+        )?;
+        let mut start = 0;
+        for end in self.offsets.iter().map(|offset| *offset as usize) {
+            write!(writer, "{}", end - start)?;
+            for point_idx in self.connectivity[start..end].iter() {
+                write!(writer, "\t{}", point_idx)?;
+            }
+            writeln!(writer)?;
+            start = end;
+        }
+
+        writeln!(writer, "\nCELL_TYPES {}", self.num_cells())?;
+        for cell_type in self.types.iter() {
+            writeln!(writer, "{}", cell_type)?;
+        }
+
+        if !self.cell_data.is_empty() {
+            writeln!(writer, "\nCELL_DATA {}", self.num_cells())?;
+            for array in self.cell_data.iter() {
+                write_legacy_array(&mut writer, array)?;
+            }
+        }
+
+        if !self.point_data.is_empty() {
+            writeln!(writer, "\nPOINT_DATA {}", self.points.len())?;
+            for array in self.point_data.iter() {
+                write_legacy_array(&mut writer, array)?;
+            }
+        }
+
+        writer.flush()
+    }
+}
+
+fn write_legacy_array(writer: &mut impl Write, array: &VtuArray) -> std::io::Result<()> {
+    let type_name = match array.values {
+        VtuValues::Float64(_) => "double",
+        VtuValues::Int64(_) => "long",
+        VtuValues::UInt8(_) => "unsigned_char",
+    };
+
+    if array.num_components == 3 {
+        writeln!(writer, "VECTORS {} {}", array.name, type_name)?;
+    } else {
+        writeln!(
+            writer,
+            "SCALARS {} {} {}\nLOOKUP_TABLE default",
+            array.name, type_name, array.num_components
+        )?;
+    }
+    array.values.write_ascii(writer)?;
+    writeln!(writer)
+}
+
+fn write_array(
+    writer: &mut impl Write,
+    array: &VtuArray,
+    encoding: VtuEncoding,
+    appended: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    let attributes = format!(
+        "type=\"{}\" Name=\"{}\" NumberOfComponents=\"{}\"",
+        array.values.type_name(),
+        array.name,
+        array.num_components
+    );
+
+    match encoding {
+        VtuEncoding::Ascii => {
+            writeln!(writer, "<DataArray {} format=\"ascii\">", attributes)?;
+            array.values.write_ascii(writer)?;
+            writeln!(writer, "\n</DataArray>")
+        }
+        VtuEncoding::Base64 => {
+            writeln!(writer, "<DataArray {} format=\"binary\">", attributes)?;
+            writeln!(writer, "{}", base64_encode(&array.values.to_block()))?;
+            writeln!(writer, "</DataArray>")
+        }
+        VtuEncoding::RawBinary => {
+            writeln!(
+                writer,
+                "<DataArray {} format=\"appended\" offset=\"{}\"/>",
+                attributes,
+                appended.len()
+            )?;
+            appended.extend(array.values.to_block());
+            Ok(())
+        }
+    }
+}
+
+/// Encode a slice of bytes as (padded) Base64 text
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(4 * bytes.len().div_ceil(3));
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let triple = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | (b[2] as u32);
+
+        for k in 0..4 {
+            if k <= chunk.len() {
+                let sextet = (triple >> (18 - 6 * k)) & 0b11_1111;
+                encoded.push(BASE64_ALPHABET[sextet as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Build a grid of line segments tracing the boundaries of each leaf-`Elem` in a [Mesh] (with the `Elem` ID's as cell data)
+pub(crate) fn leaf_outline_piece(mesh: &Mesh) -> VtuPiece {
+    let mut piece = VtuPiece::default();
+    let mut elem_ids = Vec::new();
+
+    for elem in mesh.elems.iter().filter(|elem| !elem.has_children()) {
+        let [p0, p1] = mesh.elem_diag_points(elem.id).unwrap();
+        let first = piece.points.len();
+        piece.points.extend([
+            [p0.x, p0.y, 0.0],
+            [p1.x, p0.y, 0.0],
+            [p1.x, p1.y, 0.0],
+            [p0.x, p1.y, 0.0],
+        ]);
+
+        for k in 0..4 {
+            piece.push_cell(&[first + k, first + (k + 1) % 4], VTK_LINE);
+            elem_ids.push(elem.id as i64);
+        }
+    }
+
+    piece
+        .cell_data
+        .push(VtuArray::scalars("elem_id", VtuValues::Int64(elem_ids)));
+    piece
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xff, 0xee, 0x00, 0x01]), "/+4AAQ==");
+    }
+
+    #[test]
+    fn encodings() {
+        let mut piece = VtuPiece {
+            points: vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+            ..Default::default()
+        };
+        piece.push_cell(&[0, 1, 2, 3], VTK_QUAD);
+        piece.point_data.push(VtuArray::scalars(
+            "q",
+            VtuValues::Float64(vec![0.0, 1.0, 2.0, 3.0]),
+        ));
+        piece
+            .cell_data
+            .push(VtuArray::scalars("id", VtuValues::Int64(vec![7])));
+
+        for (encoding, name) in [
+            (VtuEncoding::Ascii, "ascii"),
+            (VtuEncoding::Base64, "base64"),
+            (VtuEncoding::RawBinary, "raw"),
+        ] {
+            let path = format!("./test_output/vtu_{}.vtu", name);
+            piece.write(&path, encoding).unwrap();
+            let contents = std::fs::read(&path).unwrap();
+            let text = String::from_utf8_lossy(&contents);
+
+            assert!(text.contains("NumberOfPoints=\"4\" NumberOfCells=\"1\""));
+            assert!(text.contains("Name=\"q\""));
+            assert!(text.trim_end().ends_with("</VTKFile>"));
+
+            match encoding {
+                VtuEncoding::Ascii => assert!(text.contains("0 1 2 3")),
+                // 8-byte header (32) followed by the 4 connectivity entries
+                VtuEncoding::Base64 => {
+                    let mut block = 32_u64.to_le_bytes().to_vec();
+                    [0_i64, 1, 2, 3]
+                        .iter()
+                        .for_each(|v| block.extend(v.to_le_bytes()));
+                    assert!(text.contains(&base64_encode(&block)));
+                }
+                VtuEncoding::RawBinary => {
+                    assert!(text.contains("<AppendedData encoding=\"raw\">"));
+                    // points: 8 + 96 bytes, connectivity: 8 + 32 bytes
+                    assert!(text.contains("Name=\"connectivity\" NumberOfComponents=\"1\" format=\"appended\" offset=\"104\""));
+                }
+            }
+        }
+    }
+}
@@ -6,3 +6,33 @@ pub mod linalg;
 
 /// Strucutures to Execute Galerkin Sampling over a `Domain` using `Integral`s
 pub mod galerkin;
+
+/// Perfectly Matched Layers for truncating open-region problems
+pub mod pml;
+
+/// Waveguide Ports for driven problems and S-Parameter extraction
+pub mod port;
+
+/// A-posteriori error estimation for guiding hp-refinement
+pub mod error_estimation;
+
+/// Global integrated quantities: stored energies, norms, and quality factors
+pub mod energy;
+
+/// Parametric sweeps with mode tracking
+pub mod sweep;
+
+/// Analytic reference solutions and convergence studies for verification
+pub mod verification;
+
+/// Method of Manufactured Solutions for verifying the assembled operators
+pub mod manufactured;
+
+/// Transfer of solutions between Domains following refinement
+pub mod projection;
+
+/// Selection of h- and p-refinements from the decay of hierarchical solution coefficients
+pub mod refinement_selection;
+
+/// An hp-adaptive refinement driver for eigenproblems
+pub mod adaptive;
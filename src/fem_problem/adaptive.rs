@@ -0,0 +1,344 @@
+use super::error_estimation::{residual_error_indicators, ErrorEstimationError};
+use super::galerkin::{galerkin_sample_gep_hcurl, GalerkinSamplingError};
+use super::integration::HierCurlIntegral;
+use super::linalg::{EigenPair, GEP};
+use super::refinement_selection::{RefinementChoice, RefinementSelector};
+use crate::fem_domain::basis::HierCurlBasisFnSpace;
+use crate::fem_domain::domain::{
+    mesh::{
+        h_refinement::{HRef, HRefError},
+        p_refinement::{PRef, PRefError},
+        Mesh,
+    },
+    ContinuityCondition, Domain,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Parameters controlling the [hp_adaptive_solve] driver
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveSettings {
+    /// The eigenvalue targeted by the first solve (subsequent solves target the most recent eigenvalue)
+    pub target_eigenvalue: f64,
+    /// Convergence is declared once the relative change in the eigenvalue between successive iterations falls below this value
+    pub tolerance: f64,
+    /// The maximum number of solves
+    pub max_iterations: usize,
+    /// Dörfler marking parameter in `(0, 1]`: the smallest set of `Elem`s whose squared error indicators make up this fraction of the total is refined
+    pub marking_fraction: f64,
+    /// Chooses between h- and p-refinement for each marked `Elem`
+    pub selector: RefinementSelector,
+    /// Number of Gauss Legendre Quadrature Points to use for Galerkin Sampling. If `None`, the default values are used.
+    pub glq_grid_dim: Option<[usize; 2]>,
+}
+
+impl AdaptiveSettings {
+    /// Create a set of adaptive settings with a target eigenvalue and tolerance (other parameters take default values)
+    pub fn new(target_eigenvalue: f64, tolerance: f64) -> Self {
+        Self {
+            target_eigenvalue,
+            tolerance,
+            max_iterations: 10,
+            marking_fraction: 0.5,
+            selector: RefinementSelector::default(),
+            glq_grid_dim: None,
+        }
+    }
+}
+
+/// A record of one iteration of the adaptive loop
+#[derive(Clone, Copy, Debug)]
+pub struct AdaptiveStep {
+    /// Number of Degrees of Freedom in the Domain
+    pub num_dofs: usize,
+    /// Eigenvalue computed over the Domain
+    pub eigenvalue: f64,
+    /// Global error estimate: `sqrt(Σ η_K²)`
+    pub estimated_error: f64,
+    /// Number of `Elem`s h-refined following this iteration
+    pub num_h_refinements: usize,
+    /// Number of `Elem`s p-refined following this iteration
+    pub num_p_refinements: usize,
+}
+
+/// The result of the [hp_adaptive_solve] driver
+pub struct AdaptiveSolution {
+    /// The final Domain
+    pub domain: Domain,
+    /// The solution computed over the final Domain
+    pub eigen_pair: EigenPair,
+    /// The DoF count, eigenvalue, and error estimate of each iteration
+    pub history: Vec<AdaptiveStep>,
+    /// Whether the eigenvalue converged within the given tolerance
+    pub converged: bool,
+}
+
+/// Solve an eigenproblem with automatic hp-refinement
+///
+/// Each iteration:
+/// 1. constructs a [Domain] from the current Mesh and solves the Generalized Eigenproblem
+/// 2. computes residual-based error indicators (see [residual_error_indicators])
+/// 3. marks `Elem`s for refinement with Dörfler's criterion
+/// 4. chooses an [HRef] or [PRef] for each marked `Elem` based on the decay of its hierarchical solution coefficients (see [RefinementSelector])
+///
+/// The loop ends once the relative change in the eigenvalue falls below the tolerance, the maximum number of iterations is reached, or no more refinements are possible.
+/// An `Err` is returned if the marking fraction is outside of `(0, 1]`, or if the maximum number of iterations is zero.
+///
+/// # Arguments
+/// * `mesh`: The initial Mesh
+/// * `settings`: The parameters of the adaptive loop
+/// * `solver`: A Generalized Eigenproblem solver taking a [GEP] and target eigenvalue (such as `nalgebra_solve_gep` or `slepc_solve_gep`)
+/// * Two [HierCurlIntegral]s: `AI` and `BI` must be specified as Generic Arguments. These are used to populate the A and B matrices respectively
+/// * A [HierCurlBasisFnSpace] `BSpace` must also be specified as a Generic Argument
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([2, 2]).unwrap();
+///
+/// let settings = AdaptiveSettings::new(1.3, 1e-4);
+/// let solution =
+///     hp_adaptive_solve::<HierPoly, CurlCurl, L2Inner, _, _>(mesh, &settings, nalgebra_solve_gep)
+///         .unwrap();
+///
+/// assert!(solution.converged);
+/// assert!((solution.eigen_pair.value - 1.3072861).abs() < 1e-3);
+///
+/// // each iteration adds Degrees of Freedom until the eigenvalue converges
+/// assert!(solution.history.len() <= settings.max_iterations);
+/// assert!(solution
+///     .history
+///     .windows(2)
+///     .all(|steps| steps[1].num_dofs > steps[0].num_dofs));
+/// assert_eq!(solution.history.last().unwrap().num_dofs, solution.domain.dofs.len());
+/// ```
+pub fn hp_adaptive_solve<BSpace, AI, BI, S, E>(
+    mesh: Mesh,
+    settings: &AdaptiveSettings,
+    solver: S,
+) -> Result<AdaptiveSolution, AdaptiveError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    AI: HierCurlIntegral,
+    BI: HierCurlIntegral,
+    S: Fn(GEP, f64) -> Result<EigenPair, E>,
+    E: Into<Box<dyn std::error::Error>>,
+{
+    if settings.marking_fraction <= 0.0 || settings.marking_fraction > 1.0 {
+        return Err(AdaptiveError::InvalidMarkingFraction(
+            settings.marking_fraction,
+        ));
+    }
+
+    if settings.max_iterations == 0 {
+        return Err(AdaptiveError::NoIterations);
+    }
+
+    let mut mesh = mesh;
+    let mut target = settings.target_eigenvalue;
+    let mut history: Vec<AdaptiveStep> = Vec::new();
+
+    loop {
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let gep = galerkin_sample_gep_hcurl::<BSpace, AI, BI>(&domain, settings.glq_grid_dim)?;
+        let eigen_pair = solver(gep, target).map_err(|err| AdaptiveError::Solver(err.into()))?;
+        let indicators = residual_error_indicators::<BSpace>(&domain, &eigen_pair, None)?;
+
+        let estimated_error = indicators.values().map(|eta| eta * eta).sum::<f64>().sqrt();
+        let converged = history.last().is_some_and(|prev| {
+            (eigen_pair.value - prev.eigenvalue).abs()
+                <= settings.tolerance * eigen_pair.value.abs()
+        });
+
+        let final_iteration = history.len() + 1 == settings.max_iterations;
+
+        let (h_refinements, p_refinements) = if converged || final_iteration {
+            (Vec::new(), Vec::new())
+        } else {
+            select_refinements(&domain, &eigen_pair, &indicators, settings)
+        };
+
+        history.push(AdaptiveStep {
+            num_dofs: domain.dofs.len(),
+            eigenvalue: eigen_pair.value,
+            estimated_error,
+            num_h_refinements: h_refinements.len(),
+            num_p_refinements: p_refinements.len(),
+        });
+
+        if h_refinements.is_empty() && p_refinements.is_empty() {
+            return Ok(AdaptiveSolution {
+                domain,
+                eigen_pair,
+                history,
+                converged,
+            });
+        }
+
+        // h-refined Elems are not p-refined, so the two sets of refinements are independent
+        mesh = domain.mesh;
+        mesh.execute_p_refinements(p_refinements)?;
+        mesh.execute_h_refinements(h_refinements)?;
+        target = eigen_pair.value;
+    }
+}
+
+type RefinementSelection = (Vec<(usize, HRef)>, Vec<(usize, PRef)>);
+
+// Mark Elems with Dörfler's criterion and choose a refinement for each
+fn select_refinements(
+    domain: &Domain,
+    eigen_pair: &EigenPair,
+    indicators: &BTreeMap<usize, f64>,
+    settings: &AdaptiveSettings,
+) -> RefinementSelection {
+    let mut ranked: Vec<(usize, f64)> = indicators
+        .iter()
+        .map(|(elem_id, eta)| (*elem_id, eta * eta))
+        .collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+
+    let total: f64 = ranked.iter().map(|(_, eta_sq)| eta_sq).sum();
+    let mut marked_sum = 0.0;
+    let mut h_refinements = Vec::new();
+    let mut p_refinements = Vec::new();
+
+    for (elem_id, eta_sq) in ranked {
+        if marked_sum >= settings.marking_fraction * total {
+            break;
+        }
+        marked_sum += eta_sq;
+
+        match settings
+            .selector
+            .select(domain, elem_id, &eigen_pair.vector)
+            .unwrap()
+        {
+            RefinementChoice::H(refinement) => h_refinements.push((elem_id, refinement)),
+            RefinementChoice::P(refinement) => p_refinements.push((elem_id, refinement)),
+            RefinementChoice::None => (),
+        }
+    }
+
+    (h_refinements, p_refinements)
+}
+
+/// Error Type for the hp-adaptive driver
+#[derive(Debug)]
+pub enum AdaptiveError {
+    InvalidMarkingFraction(f64),
+    NoIterations,
+    Sampling(GalerkinSamplingError),
+    Solver(Box<dyn std::error::Error>),
+    Estimation(ErrorEstimationError),
+    HRefinement(HRefError),
+    PRefinement(PRefError),
+}
+
+impl std::error::Error for AdaptiveError {}
+
+impl fmt::Display for AdaptiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidMarkingFraction(fraction) => write!(
+                f,
+                "Marking fraction must be in (0, 1] (found {}); Cannot run adaptive solver!",
+                fraction
+            ),
+            Self::NoIterations => write!(
+                f,
+                "Maximum number of iterations must be at least 1; Cannot run adaptive solver!"
+            ),
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::Solver(err) => write!(f, "{}", err),
+            Self::Estimation(err) => write!(f, "{}", err),
+            Self::HRefinement(err) => write!(f, "{}", err),
+            Self::PRefinement(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<GalerkinSamplingError> for AdaptiveError {
+    fn from(err: GalerkinSamplingError) -> Self {
+        Self::Sampling(err)
+    }
+}
+
+impl From<ErrorEstimationError> for AdaptiveError {
+    fn from(err: ErrorEstimationError) -> Self {
+        Self::Estimation(err)
+    }
+}
+
+impl From<HRefError> for AdaptiveError {
+    fn from(err: HRefError) -> Self {
+        Self::HRefinement(err)
+    }
+}
+
+impl From<PRefError> for AdaptiveError {
+    fn from(err: PRefError) -> Self {
+        Self::PRefinement(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use crate::fem_problem::linalg::nalgebra_solve::nalgebra_solve_gep;
+
+    #[test]
+    fn adaptive_history() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+
+        let mut settings = AdaptiveSettings::new(1.3, 1e-5);
+        settings.max_iterations = 6;
+        let solution = hp_adaptive_solve::<HierPoly, CurlCurl, L2Inner, _, _>(
+            mesh,
+            &settings,
+            nalgebra_solve_gep,
+        )
+        .unwrap();
+
+        assert!(solution.history.len() > 1);
+        assert!(solution
+            .history
+            .windows(2)
+            .all(|steps| steps[1].num_dofs > steps[0].num_dofs));
+        assert_eq!(
+            solution.history.last().unwrap().num_dofs,
+            solution.domain.dofs.len()
+        );
+        assert!((solution.eigen_pair.value - 1.3072861).abs() < 1e-4);
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let mut settings = AdaptiveSettings::new(1.3, 1e-5);
+        settings.marking_fraction = 0.0;
+        assert!(matches!(
+            hp_adaptive_solve::<HierPoly, CurlCurl, L2Inner, _, _>(
+                Mesh::unit(),
+                &settings,
+                nalgebra_solve_gep
+            ),
+            Err(AdaptiveError::InvalidMarkingFraction(_))
+        ));
+
+        let mut settings = AdaptiveSettings::new(1.3, 1e-5);
+        settings.max_iterations = 0;
+        assert!(matches!(
+            hp_adaptive_solve::<HierPoly, CurlCurl, L2Inner, _, _>(
+                Mesh::unit(),
+                &settings,
+                nalgebra_solve_gep
+            ),
+            Err(AdaptiveError::NoIterations)
+        ));
+    }
+}
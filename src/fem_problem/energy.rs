@@ -0,0 +1,399 @@
+use super::galerkin::{
+    check_sampling_inputs, domain_glq_grids, grid_integrators, integrate_elem_pairs,
+    GalerkinSamplingError,
+};
+use super::integration::{
+    integrals::{curl_curl::CurlCurl, inner::L2Inner},
+    HierCurlIntegral,
+};
+use super::linalg::EigenPair;
+use crate::fem_domain::basis::{BasisFnSampler, HierCurlBasisFn, HierCurlBasisFnSpace};
+use crate::fem_domain::domain::{mesh::element::Materials, Domain};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Energies and losses within a single material region (an `Element` of the Mesh and all of its `Elem`s)
+#[derive(Clone, Debug)]
+pub struct RegionQuantities {
+    /// ID of the `Element`
+    pub element_id: usize,
+    /// Material parameters of the `Element`
+    pub materials: Materials,
+    /// Time-averaged stored electric energy: `¼ ∫ ε' |E|²`
+    pub electric_energy: f64,
+    /// Time-averaged stored magnetic energy: `¼ ∫ μ' |H|²`
+    pub magnetic_energy: f64,
+    /// Fraction of the total stored energy within this region
+    pub energy_fraction: f64,
+    /// Power dissipated by dielectric and magnetic losses, divided by the angular frequency: `½ ∫ (|ε''| |E|² + |μ''| |H|²)`
+    pub normalized_loss: f64,
+}
+
+/// Global quantities integrated over a [Domain] for a solution of the problem: `∇ × (μ⁻¹ ∇ × E) = λ ε E`
+///
+/// All quantities are expressed in normalized units (`ε₀ = μ₀ = c = 1`), such that the angular frequency is `ω = √λ` and `H = ∇ × E / (-j ω μ)`.
+/// Energies scale with the square of the eigenvector's magnitude, and are computed for the eigenvector as given.
+#[derive(Clone, Debug)]
+pub struct IntegratedQuantities {
+    /// Time-averaged stored electric energy
+    pub electric_energy: f64,
+    /// Time-averaged stored magnetic energy
+    pub magnetic_energy: f64,
+    /// L2 norm of the field: `√(∫ |E|²)`
+    pub l2_norm: f64,
+    /// H(curl) norm of the field: `√(∫ |E|² + |∇ × E|²)`
+    pub hcurl_norm: f64,
+    /// Quality factor: `ω W / P`, where `W` is the total stored energy and `P` is the dissipated power (infinite for lossless materials)
+    ///
+    /// Losses are treated as a perturbation of the (real) solution, using the imaginary parts of the material parameters
+    pub quality_factor: f64,
+    /// Energies and losses broken down by material region (sorted by `Element` ID)
+    pub regions: Vec<RegionQuantities>,
+}
+
+impl IntegratedQuantities {
+    /// Total time-averaged stored energy
+    pub fn total_energy(&self) -> f64 {
+        self.electric_energy + self.magnetic_energy
+    }
+}
+
+/// Compute the stored energies, norms, and quality factor of an [EigenPair] by integrating the `L2Inner` and `CurlCurl` forms over each `Elem`
+///
+/// # Arguments
+/// * `domain`: The [Domain] over which the [EigenPair] was computed
+/// * `eigen_pair`: The solution. Its eigenvalue must be positive (`λ = ω²`)
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points to use for integration along each direction. If `None`, the number of points is chosen for each pair of Elems from their expansion orders.
+/// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument. It should match the Basis Space used to compute the [EigenPair].
+///
+/// # Returns
+/// * An `Err` if the Domain cannot be sampled (see [galerkin_sample_gep_hcurl](crate::fem_problem::galerkin::galerkin_sample_gep_hcurl)), if the eigenvector doesn't match the number of DoFs, or if the eigenvalue is not positive
+/// * The [IntegratedQuantities], otherwise
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+/// let eigen_pair = nalgebra_solve_gep(gep, 3.62).unwrap();
+///
+/// let quantities = integrated_quantities::<HierPoly>(&domain, &eigen_pair, None).unwrap();
+///
+/// // electric and magnetic energies are equal at resonance
+/// assert!((quantities.electric_energy - quantities.magnetic_energy).abs() < 1e-8 * quantities.total_energy());
+///
+/// // the materials are lossless
+/// assert!(quantities.quality_factor.is_infinite());
+///
+/// // each of the four material regions holds part of the stored energy
+/// assert_eq!(quantities.regions.len(), 4);
+/// assert!(quantities.regions.iter().all(|region| region.energy_fraction > 0.0));
+/// let fraction_sum: f64 = quantities.regions.iter().map(|region| region.energy_fraction).sum();
+/// assert!((fraction_sum - 1.0).abs() < 1e-10);
+/// ```
+pub fn integrated_quantities<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    eigen_pair: &EigenPair,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<IntegratedQuantities, IntegratedQuantityError> {
+    if eigen_pair.value <= 0.0 {
+        return Err(IntegratedQuantityError::NonPositiveEigenvalue(
+            eigen_pair.value,
+        ));
+    }
+    let omega_sq = eigen_pair.value;
+    let region_integrals = integrate_by_region::<BSpace>(domain, &eigen_pair.vector, glq_grid_dim)?;
+    let [l2_norm, hcurl_norm] = norms(&region_integrals);
+
+    let mut regions: Vec<RegionQuantities> = region_integrals
+        .into_iter()
+        .map(|(element_id, [e_sq, curl_sq])| {
+            let materials = domain.mesh.elements[element_id].materials.clone();
+            let [eps, mu] = [materials.eps_rel, materials.mu_rel];
+
+            // |H|² = |∇ × E|² / (ω² |μ|²)
+            let h_sq = curl_sq / (omega_sq * mu.norm_sqr());
+
+            RegionQuantities {
+                element_id,
+                electric_energy: 0.25 * eps.re * e_sq,
+                magnetic_energy: 0.25 * mu.re * h_sq,
+                energy_fraction: 0.0,
+                normalized_loss: 0.5 * (eps.im.abs() * e_sq + mu.im.abs() * h_sq),
+                materials,
+            }
+        })
+        .collect();
+
+    let electric_energy: f64 = regions.iter().map(|r| r.electric_energy).sum();
+    let magnetic_energy: f64 = regions.iter().map(|r| r.magnetic_energy).sum();
+    let total_energy = electric_energy + magnetic_energy;
+    let normalized_loss: f64 = regions.iter().map(|r| r.normalized_loss).sum();
+
+    for region in regions.iter_mut() {
+        region.energy_fraction = (region.electric_energy + region.magnetic_energy) / total_energy;
+    }
+
+    Ok(IntegratedQuantities {
+        electric_energy,
+        magnetic_energy,
+        l2_norm,
+        hcurl_norm,
+        quality_factor: if normalized_loss > 0.0 {
+            total_energy / normalized_loss
+        } else {
+            f64::INFINITY
+        },
+        regions,
+    })
+}
+
+/// Compute the L2 norm (`√(∫ |E|²)`) and the H(curl) norm (`√(∫ |E|² + |∇ × E|²)`) of a solution vector (indexed by DoF ID)
+///
+/// The norms are independent of the material parameters.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::unit();
+/// mesh.set_global_expansion_orders([2, 2]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let solution = vec![1.0; domain.dofs.len()];
+/// let [l2_norm, hcurl_norm] = solution_norms::<HierPoly>(&domain, &solution, None).unwrap();
+/// assert!(hcurl_norm >= l2_norm);
+/// ```
+pub fn solution_norms<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    solution: &[f64],
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<[f64; 2], IntegratedQuantityError> {
+    Ok(norms(&integrate_by_region::<BSpace>(
+        domain,
+        solution,
+        glq_grid_dim,
+    )?))
+}
+
+// the L2 and H(curl) norms from the integrals over each region
+fn norms(region_integrals: &BTreeMap<usize, [f64; 2]>) -> [f64; 2] {
+    let [e_sq, curl_sq] = region_integrals
+        .values()
+        .fold([0.0, 0.0], |[e_acc, c_acc], [e_sq, curl_sq]| {
+            [e_acc + e_sq, c_acc + curl_sq]
+        });
+    [e_sq.sqrt(), (e_sq + curl_sq).sqrt()]
+}
+
+// Integrate ∫ |E|² and ∫ |∇ × E|² (with unit material parameters) over each Element of the Domain
+fn integrate_by_region<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    solution: &[f64],
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<BTreeMap<usize, [f64; 2]>, IntegratedQuantityError> {
+    let glq_grid_dim = check_sampling_inputs(domain, glq_grid_dim)?;
+    if solution.len() != domain.dofs.len() {
+        return Err(IntegratedQuantityError::MismatchedSolutionSize(
+            domain.dofs.len(),
+            solution.len(),
+        ));
+    }
+
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+    let (bs_sampler, _): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) = BasisFnSampler::with(
+        i_max as usize,
+        j_max as usize,
+        glq_grid_dim[0],
+        glq_grid_dim[1],
+        false,
+    );
+    let glq_grids = domain_glq_grids(domain, glq_grid_dim);
+    let inner = grid_integrators(&bs_sampler, &glq_grids, L2Inner::with_weights);
+    let curl_curl = grid_integrators(&bs_sampler, &glq_grids, CurlCurl::with_weights);
+    let unit_materials = Materials::default();
+
+    let elem_integrals: Vec<(usize, [f64; 2])> = domain
+        .mesh
+        .elems
+        .par_iter()
+        .map(|elem| {
+            let entries = integrate_elem_pairs(
+                domain,
+                elem,
+                &mut bs_sampler.clone(),
+                glq_grid_dim,
+                |_, glq_dims, p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, _| {
+                    [
+                        inner[&glq_dims]
+                            .integrate(
+                                p_dir,
+                                q_dir,
+                                p_orders,
+                                q_orders,
+                                p_basis,
+                                q_basis,
+                                &unit_materials,
+                            )
+                            .full_solution(),
+                        curl_curl[&glq_dims]
+                            .integrate(
+                                p_dir,
+                                q_dir,
+                                p_orders,
+                                q_orders,
+                                p_basis,
+                                q_basis,
+                                &unit_materials,
+                            )
+                            .full_solution(),
+                    ]
+                },
+            );
+
+            // each pair is integrated once, so off-diagonal entries are counted twice (matching the symmetric system matrices)
+            let sums = entries
+                .iter()
+                .fold([0.0, 0.0], |[e_acc, c_acc], ([p, q], [e_pq, c_pq])| {
+                    let weight = if p == q { 1.0 } else { 2.0 } * solution[*p] * solution[*q];
+                    [e_acc + weight * e_pq, c_acc + weight * c_pq]
+                });
+
+            (elem.element.id, sums)
+        })
+        .collect();
+
+    let mut region_integrals = BTreeMap::new();
+    for (element_id, [e_sq, curl_sq]) in elem_integrals {
+        let sums = region_integrals.entry(element_id).or_insert([0.0, 0.0]);
+        sums[0] += e_sq;
+        sums[1] += curl_sq;
+    }
+
+    Ok(region_integrals)
+}
+
+/// Error Type for the computation of integrated quantities
+#[derive(Debug)]
+pub enum IntegratedQuantityError {
+    Sampling(GalerkinSamplingError),
+    MismatchedSolutionSize(usize, usize),
+    NonPositiveEigenvalue(f64),
+}
+
+impl std::error::Error for IntegratedQuantityError {}
+
+impl fmt::Display for IntegratedQuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::MismatchedSolutionSize(dom_size, sol_size) => write!(
+                f,
+                "Domain size ({}) does not match solution size ({}); Cannot compute integrated quantities!",
+                dom_size, sol_size
+            ),
+            Self::NonPositiveEigenvalue(value) => write!(
+                f,
+                "Eigenvalue must be positive (found {}); Cannot compute integrated quantities!",
+                value
+            ),
+        }
+    }
+}
+
+impl From<GalerkinSamplingError> for IntegratedQuantityError {
+    fn from(err: GalerkinSamplingError) -> Self {
+        Self::Sampling(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_domain::domain::{mesh::Mesh, ContinuityCondition};
+    use crate::fem_problem::galerkin::galerkin_sample_gep_hcurl;
+    use crate::fem_problem::linalg::nalgebra_solve::nalgebra_solve_gep;
+
+    fn solve(mesh_path: &str, target: f64) -> (Domain, EigenPair) {
+        let mut mesh = Mesh::from_file(mesh_path).unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+        let eigen_pair = nalgebra_solve_gep(gep, target).unwrap();
+        (domain, eigen_pair)
+    }
+
+    #[test]
+    fn energy_balance() {
+        let (domain, eigen_pair) = solve("./test_input/test_mesh_a.json", 3.62);
+        let quantities = integrated_quantities::<HierPoly>(&domain, &eigen_pair, None).unwrap();
+
+        assert_eq!(quantities.regions.len(), 4);
+        assert!(
+            (quantities.electric_energy - quantities.magnetic_energy).abs()
+                < 1e-8 * quantities.total_energy()
+        );
+        assert!(
+            (quantities
+                .regions
+                .iter()
+                .map(|r| r.energy_fraction)
+                .sum::<f64>()
+                - 1.0)
+                .abs()
+                < 1e-12
+        );
+        assert!(quantities.quality_factor.is_infinite());
+
+        // the norms are consistent with the standalone computation
+        let [l2_norm, hcurl_norm] =
+            solution_norms::<HierPoly>(&domain, &eigen_pair.vector, None).unwrap();
+        assert!((quantities.l2_norm - l2_norm).abs() < 1e-12 * l2_norm);
+        assert!((quantities.hcurl_norm - hcurl_norm).abs() < 1e-12 * hcurl_norm);
+
+        // energies scale with the square of the eigenvector
+        let scaled = EigenPair {
+            value: eigen_pair.value,
+            vector: eigen_pair.vector.iter().map(|x| x * 2.0).collect(),
+        };
+        let scaled_quantities = integrated_quantities::<HierPoly>(&domain, &scaled, None).unwrap();
+        assert!(
+            (scaled_quantities.total_energy() - 4.0 * quantities.total_energy()).abs()
+                < 1e-10 * quantities.total_energy()
+        );
+    }
+
+    #[test]
+    fn lossy_quality_factor() {
+        // uniform dielectric with a loss tangent of 0.01
+        let (domain, eigen_pair) = solve("./test_input/test_mesh_lossy.json", 3.0);
+        let quantities = integrated_quantities::<HierPoly>(&domain, &eigen_pair, None).unwrap();
+
+        assert!((quantities.quality_factor - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn integrated_quantity_errors() {
+        let (domain, eigen_pair) = solve("./test_input/test_mesh_a.json", 3.62);
+
+        let negative = EigenPair {
+            value: -1.0,
+            vector: eigen_pair.vector.clone(),
+        };
+        assert!(matches!(
+            integrated_quantities::<HierPoly>(&domain, &negative, None),
+            Err(IntegratedQuantityError::NonPositiveEigenvalue(_))
+        ));
+        assert!(matches!(
+            solution_norms::<HierPoly>(&domain, &eigen_pair.vector[1..], None),
+            Err(IntegratedQuantityError::MismatchedSolutionSize(_, _))
+        ));
+    }
+}
@@ -0,0 +1,357 @@
+use super::integration::glq::{gauss_quadrature_points, scale_gauss_quad_points};
+use super::linalg::EigenPair;
+use crate::fem_domain::basis::HierCurlBasisFnSpace;
+use crate::fem_domain::domain::{
+    fields::{sample_local_fields, LocalFields},
+    mesh::space::{ParaDir, V2D},
+    ContinuityCondition, Domain,
+};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Minimum number of Gauss Legendre Quadrature Points used along each direction when computing error indicators
+pub const MIN_ESTIMATOR_GLQ_ORDER: usize = 4;
+
+/// Compute a residual-based a-posteriori error indicator on each leaf-`Elem` for an [EigenPair] of the problem: `∇ × (μ⁻¹ ∇ × E) = λ ε E`
+///
+/// The indicator on a leaf-`Elem` `K` (with diameter `h_K` and maximum expansion order `p_K`) is defined as:
+///
+/// `η_K² = (h_K / p_K)² (‖μ⁻¹ ∇ × ∇ × E - λ ε E‖²_K + ‖∇ · (ε E)‖²_K) + Σ_e (h_e / 2p_K) (‖[μ⁻¹ ∇ × E]‖²_e + ‖[ε E · n]‖²_e)`
+///
+/// where the sum is taken over the interior leaf-`Edge`s along `K` (including those along hanging nodes), and `[·]` denotes the jump across an `Edge`.
+/// The first jump term measures the discontinuity of the tangential magnetic field, while the second measures the discontinuity of the normal electric flux.
+/// Each jump is shared evenly between the two `Elem`s on either side of the `Edge`. No jumps are computed along the boundary of the Domain.
+///
+/// # Arguments
+/// * `domain`: The [Domain] over which the [EigenPair] was computed
+/// * `eigen_pair`: The solution whose error should be estimated
+/// * `glq_order`: The number of Gauss Legendre Quadrature Points to use along each direction. If `None`, a default value is chosen from the maximum expansion order.
+/// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument. It should match the Basis Space used to compute the [EigenPair].
+///
+/// # Returns
+/// * A map from leaf-`Elem` ID to error indicator
+/// * An `Err` if the Domain was not constructed with an `H(Curl)` [ContinuityCondition], if the eigenvector doesn't match the number of DoFs, or if the `glq_order` is too small
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+/// let eigen_pair = nalgebra_solve_gep(gep, 3.62).unwrap();
+///
+/// let indicators = residual_error_indicators::<HierPoly>(&domain, &eigen_pair, None).unwrap();
+/// assert_eq!(indicators.len(), 4);
+///
+/// // refine the Elems with above-average error
+/// let mean = indicators.values().sum::<f64>() / indicators.len() as f64;
+/// let mut mesh = domain.mesh.clone();
+/// mesh.h_refine_with_filter(|elem| {
+///     indicators.get(&elem.id).filter(|eta| **eta > mean).map(|_| HRef::T)
+/// });
+/// ```
+pub fn residual_error_indicators<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    eigen_pair: &EigenPair,
+    glq_order: Option<usize>,
+) -> Result<BTreeMap<usize, f64>, ErrorEstimationError> {
+    if domain.cc != ContinuityCondition::HCurl {
+        return Err(ErrorEstimationError::WrongContinuityCondition(domain.cc));
+    }
+    if eigen_pair.vector.len() != domain.dofs.len() {
+        return Err(ErrorEstimationError::MismatchedSolutionSize(
+            domain.dofs.len(),
+            eigen_pair.vector.len(),
+        ));
+    }
+
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+    let num_glq = match glq_order {
+        Some(n) if n < MIN_ESTIMATOR_GLQ_ORDER => {
+            return Err(ErrorEstimationError::InvalidGLQOrder(n))
+        }
+        Some(n) => n,
+        None => (i_max.max(j_max) as usize + 3).max(MIN_ESTIMATOR_GLQ_ORDER),
+    };
+    let (glq_points, glq_weights) = gauss_quadrature_points(num_glq, false);
+
+    let solution = &eigen_pair.vector;
+    let lambda = eigen_pair.value;
+
+    // element residuals
+    let mut indicators_sq: BTreeMap<usize, f64> = domain
+        .mesh
+        .elems
+        .par_iter()
+        .filter(|elem| !elem.has_children())
+        .map(|elem| {
+            let [p0, p1] = domain.mesh.elem_diag_points(elem.id).unwrap();
+            let (_, xs) = scale_gauss_quad_points(&glq_points, p0.x, p1.x);
+            let (_, ys) = scale_gauss_quad_points(&glq_points, p0.y, p1.y);
+            let area_scale = (p1.x - p0.x) * (p1.y - p0.y) / 4.0;
+
+            let materials = elem.get_materials();
+            let [eps, mu] = [materials.eps_rel.re, materials.mu_rel.re];
+
+            let LocalFields {
+                field,
+                div,
+                curl_curl,
+                ..
+            } = sample_local_fields::<BSpace>(domain, elem.id, [&xs, &ys], solution);
+
+            let mut residual_sq = 0.0;
+            for (m, w_m) in glq_weights.iter().enumerate() {
+                for (n, w_n) in glq_weights.iter().enumerate() {
+                    let r = curl_curl[m][n] * (1.0 / mu) + field[m][n] * (-lambda * eps);
+                    residual_sq +=
+                        w_m * w_n * (V2D::dot(r, r) + (eps * div[m][n]).powi(2)) * area_scale;
+                }
+            }
+
+            let h = p0.dist(p1);
+            let p = elem.poly_orders.ni.max(elem.poly_orders.nj) as f64;
+
+            (elem.id, (h / p).powi(2) * residual_sq)
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .collect();
+
+    // jumps across interior leaf-Edges
+    let edge_jumps: Vec<([usize; 2], [f64; 2], f64)> = domain
+        .mesh
+        .edges
+        .par_iter()
+        .filter_map(|edge| {
+            domain
+                .mesh
+                .leaf_edge_elems(edge.id)
+                .unwrap()
+                .map(|pair| (edge, pair))
+        })
+        .map(|(edge, elem_ids)| {
+            let [n0, n1] = domain.mesh.edge_points(edge.id).unwrap();
+            let line_scale = edge.length / 2.0;
+
+            // sample both Elems along the Edge (the normal points from side 0 to side 1)
+            let [tangential, normal] = match edge.dir {
+                ParaDir::U => {
+                    let (_, xs) = scale_gauss_quad_points(&glq_points, n0.x, n1.x);
+                    let [f0, f1] = elem_ids.map(|elem_id| {
+                        sample_local_fields::<BSpace>(domain, elem_id, [&xs, &[n0.y]], solution)
+                    });
+                    [0, 1].map(|component| {
+                        (0..num_glq)
+                            .map(|q| {
+                                jump_components(domain, elem_ids, [&f0, &f1], [q, 0], 1, component)
+                            })
+                            .collect::<Vec<f64>>()
+                    })
+                }
+                ParaDir::V => {
+                    let (_, ys) = scale_gauss_quad_points(&glq_points, n0.y, n1.y);
+                    let [f0, f1] = elem_ids.map(|elem_id| {
+                        sample_local_fields::<BSpace>(domain, elem_id, [&[n0.x], &ys], solution)
+                    });
+                    [0, 1].map(|component| {
+                        (0..num_glq)
+                            .map(|q| {
+                                jump_components(domain, elem_ids, [&f0, &f1], [0, q], 0, component)
+                            })
+                            .collect::<Vec<f64>>()
+                    })
+                }
+            };
+
+            let jump_sq: f64 = glq_weights
+                .iter()
+                .zip(tangential.iter().zip(normal.iter()))
+                .map(|(w, (t, n))| w * (t * t + n * n) * line_scale)
+                .sum();
+
+            let p_orders = elem_ids.map(|elem_id| {
+                let orders = &domain.mesh.elems[elem_id].poly_orders;
+                orders.ni.max(orders.nj) as f64
+            });
+
+            (elem_ids, p_orders, edge.length * jump_sq)
+        })
+        .collect();
+
+    for (elem_ids, p_orders, weighted_jump_sq) in edge_jumps {
+        for (elem_id, p) in elem_ids.iter().zip(p_orders.iter()) {
+            *indicators_sq.get_mut(elem_id).unwrap() += weighted_jump_sq / (4.0 * p);
+        }
+    }
+
+    Ok(indicators_sq
+        .into_iter()
+        .map(|(elem_id, eta_sq)| (elem_id, eta_sq.sqrt()))
+        .collect())
+}
+
+// jump in the tangential magnetic field (component 0) or normal electric flux (component 1) at some point along an Edge
+fn jump_components(
+    domain: &Domain,
+    elem_ids: [usize; 2],
+    fields: [&LocalFields; 2],
+    [m, n]: [usize; 2],
+    normal_dir: usize,
+    component: usize,
+) -> f64 {
+    let [side_0, side_1] = [0, 1].map(|side| {
+        let materials = domain.mesh.elems[elem_ids[side]].get_materials();
+        match component {
+            0 => fields[side].curl[m][n] / materials.mu_rel.re,
+            _ => fields[side].field[m][n][normal_dir] * materials.eps_rel.re,
+        }
+    });
+    side_1 - side_0
+}
+
+/// Error Type for a-posteriori Error Estimation
+#[derive(Debug)]
+pub enum ErrorEstimationError {
+    WrongContinuityCondition(ContinuityCondition),
+    MismatchedSolutionSize(usize, usize),
+    InvalidGLQOrder(usize),
+}
+
+impl std::error::Error for ErrorEstimationError {}
+
+impl fmt::Display for ErrorEstimationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongContinuityCondition(cc) => write!(
+                f,
+                "Error estimation is only supported for H(Curl) Domains (found {}); Cannot compute indicators!",
+                cc
+            ),
+            Self::MismatchedSolutionSize(dom_size, sol_size) => write!(
+                f,
+                "Domain size ({}) does not match solution size ({}); Cannot compute indicators!",
+                dom_size, sol_size
+            ),
+            Self::InvalidGLQOrder(n) => write!(
+                f,
+                "At least {} GLQ points are required (found {}); Cannot compute indicators!",
+                MIN_ESTIMATOR_GLQ_ORDER, n
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_domain::domain::mesh::{h_refinement::HRef, Mesh};
+    use crate::fem_problem::galerkin::galerkin_sample_gep_hcurl;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use crate::fem_problem::linalg::nalgebra_solve::nalgebra_solve_gep;
+
+    fn solve_mesh_a_mode(mesh: Mesh) -> (Domain, EigenPair) {
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+        let eigen_pair = nalgebra_solve_gep(gep, 3.62).unwrap();
+        (domain, eigen_pair)
+    }
+
+    #[test]
+    fn indicators_detect_residual() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        let leaf_ids: Vec<usize> = mesh
+            .elems
+            .iter()
+            .filter(|elem| !elem.has_children())
+            .map(|elem| elem.id)
+            .collect();
+        let (domain, eigen_pair) = solve_mesh_a_mode(mesh);
+        assert!((eigen_pair.value - 3.6180459_f64).abs() < 1e-6);
+
+        let total_error = |eigen_pair: &EigenPair| {
+            let indicators =
+                residual_error_indicators::<HierPoly>(&domain, eigen_pair, None).unwrap();
+            assert_eq!(indicators.keys().copied().collect::<Vec<_>>(), leaf_ids);
+            assert!(indicators
+                .values()
+                .all(|eta| eta.is_finite() && *eta >= 0.0));
+            indicators.values().map(|eta| eta * eta).sum::<f64>().sqrt()
+        };
+
+        // pairing the eigenvector with the wrong eigenvalue should leave a large residual
+        let solved = total_error(&eigen_pair);
+        let detuned = total_error(&EigenPair {
+            value: eigen_pair.value * 2.0,
+            vector: eigen_pair.vector.clone(),
+        });
+        assert!(solved < detuned);
+    }
+
+    #[test]
+    fn indicators_converge_under_h_refinement() {
+        // the (1, 1) mode of the unit cavity has eigenvalue 2(π/2)² and a smooth eigenfunction,
+        // so the global estimate should decay as h^p under uniform refinement
+        let exact = 2.0 * (std::f64::consts::PI / 2.0).powi(2);
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+
+        let mut estimates = Vec::new();
+        for _ in 0..4 {
+            let domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+            let gep =
+                galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+            let eigen_pair = nalgebra_solve_gep(gep, exact).unwrap();
+            let indicators =
+                residual_error_indicators::<HierPoly>(&domain, &eigen_pair, None).unwrap();
+
+            estimates.push((
+                (eigen_pair.value - exact).abs(),
+                indicators.values().map(|eta| eta * eta).sum::<f64>().sqrt(),
+            ));
+            mesh.global_h_refinement(HRef::T);
+        }
+
+        for pair in estimates.windows(2) {
+            let [(err_coarse, eta_coarse), (err_fine, eta_fine)] = [pair[0], pair[1]];
+            assert!(err_fine < err_coarse);
+            assert!(eta_fine < eta_coarse);
+        }
+
+        // once the mesh resolves the mode, halving h should reduce the estimate by a factor of 2^p
+        for pair in estimates[1..].windows(2) {
+            let rate = (pair[0].1 / pair[1].1).log2();
+            assert!(
+                (rate - 2.0).abs() < 0.2,
+                "unexpected convergence rate: {}",
+                rate
+            );
+        }
+    }
+
+    #[test]
+    fn estimation_errors() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        let (domain, eigen_pair) = solve_mesh_a_mode(mesh);
+
+        let short = EigenPair {
+            value: eigen_pair.value,
+            vector: vec![1.0; domain.dofs.len() - 1],
+        };
+        assert!(matches!(
+            residual_error_indicators::<HierPoly>(&domain, &short, None),
+            Err(ErrorEstimationError::MismatchedSolutionSize(_, _))
+        ));
+        assert!(matches!(
+            residual_error_indicators::<HierPoly>(&domain, &eigen_pair, Some(2)),
+            Err(ErrorEstimationError::InvalidGLQOrder(2))
+        ));
+    }
+}
@@ -1,12 +1,27 @@
 use super::{
-    integration::HierCurlIntegral,
-    linalg::{sparse_matrix::SparseMatrix, GEP},
+    integration::{glq::real_gauss_quad, HierCurlIntegral, IntegralSum},
+    linalg::{
+        sparse_matrix::{ComplexSparseMatrix, SparseMatrix},
+        GEP,
+    },
 };
 use crate::fem_domain::{
-    basis::{BasisFnSampler, HierCurlBasisFn, HierCurlBasisFnSpace},
-    domain::{ContinuityCondition, Domain},
+    basis::{default_ngq, BasisFnSampler, HierCurlBasisFn, HierCurlBasisFnSpace},
+    domain::{
+        dof::basis_spec::{BasisDir, BasisLoc},
+        mesh::{
+            edge::Edge,
+            elem::Elem,
+            element::Materials,
+            space::{Point, V2D},
+        },
+        ContinuityCondition, Domain,
+    },
 };
+use num_complex::Complex64;
 use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 
 /// Minimum number of Gauss Legendre Quadrature Points Allowed for Galerkin Sampling
@@ -20,7 +35,7 @@ pub const MIN_GLQ_ORDER: usize = 4;
 ///
 /// # Arguments
 /// * `domain`: The [Domain] over which the Galerkin Sampling is to be performed
-/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the default values are used.
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the number of points is chosen for each pair of Elems from their expansion orders.
 /// * Two [HierCurlIntegral]s: `AI` and `BI` must be specified as Generic Arguments. These are used to populate the A and B matrices respectively
 /// * A [HierCurlBasisFnSpace] `BSpace` must also be specified as a Generic Argument. This is used to instantiate the Domains `BasisSpec`s as [HierCurlBasisFn]s
 ///
@@ -39,151 +54,791 @@ pub fn galerkin_sample_gep_hcurl<
     glq_grid_dim: Option<[usize; 2]>,
 ) -> Result<GEP, GalerkinSamplingError> {
     // check for errors
-    if domain.cc != ContinuityCondition::HCurl {
-        return Err(GalerkinSamplingError::WrongContinuityCondition(
-            ContinuityCondition::HCurl,
-            domain.cc,
-        ));
-    }
-    if domain.dofs.is_empty() {
-        return Err(GalerkinSamplingError::EmptyDOFSet);
-    }
-    let [num_glq_u, num_glq_v] = match glq_grid_dim {
-        Some([u_dim, v_dim]) => {
-            if u_dim < MIN_GLQ_ORDER || v_dim < MIN_GLQ_ORDER {
-                return Err(GalerkinSamplingError::InvalidGLQSettings);
-            }
-            [Some(u_dim), Some(v_dim)]
-        }
-        None => [None; 2],
-    };
+    let glq_grid_dim = check_sampling_inputs(domain, glq_grid_dim)?;
 
     // construct an eigenproblem with a and b matrices
     let mut gep = GEP::new(domain.dofs.len());
 
     // construct basis sampler
     let [i_max, j_max] = domain.mesh.max_expansion_orders();
-    let (bs_sampler, [u_weights, v_weights]): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) =
-        BasisFnSampler::with(i_max as usize, j_max as usize, num_glq_u, num_glq_v, false);
+    let (bs_sampler, _): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) = BasisFnSampler::with(
+        i_max as usize,
+        j_max as usize,
+        glq_grid_dim[0],
+        glq_grid_dim[1],
+        false,
+    );
 
     // setup integration
-    let a_integrator = AI::with_weights(&u_weights, &v_weights);
-    let b_integrator = BI::with_weights(&u_weights, &v_weights);
+    let glq_grids = domain_glq_grids(domain, glq_grid_dim);
+    let a_integrators = grid_integrators(&bs_sampler, &glq_grids, AI::with_weights);
+    let b_integrators = grid_integrators(&bs_sampler, &glq_grids, BI::with_weights);
 
     gep.par_extend(domain.mesh.elems.par_iter().map(|elem| {
         let mut local_a = SparseMatrix::new(domain.dofs.len());
         let mut local_b = SparseMatrix::new(domain.dofs.len());
 
-        let mut bf_sampler_elem = bs_sampler.clone();
-        let elem_materials = elem.get_materials();
+        let (a_entries, b_entries) = integrate_elem_pairs(
+            domain,
+            elem,
+            &mut bs_sampler.clone(),
+            glq_grid_dim,
+            |_, glq_dims, p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials| {
+                let a = a_integrators[&glq_dims]
+                    .integrate(
+                        p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials,
+                    )
+                    .full_solution();
+                let b = b_integrators[&glq_dims]
+                    .integrate(
+                        p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials,
+                    )
+                    .full_solution();
+                [a, b]
+            },
+        )
+        .drain(0..)
+        .map(|(ids, [a, b])| ((ids, a), (ids, b)))
+        .unzip();
+
+        local_a.insert_group(a_entries);
+        local_b.insert_group(b_entries);
 
-        // get relevant data for this Elem
-        let bs_local = bf_sampler_elem.sample_basis_fn(elem, None);
-        let local_basis_specs = domain.local_basis_specs(elem.id).unwrap();
-        let desc_basis_specs = domain.descendant_basis_specs(elem.id).unwrap();
+        [local_a, local_b]
+    }));
 
-        let mut local_a_entries: Vec<([usize; 2], f64)> =
-            Vec::with_capacity(local_basis_specs.len() * local_basis_specs.len() / 2);
-        let mut local_b_entries: Vec<([usize; 2], f64)> =
-            Vec::with_capacity(local_basis_specs.len() * local_basis_specs.len() / 2);
+    Ok(gep)
+}
+
+/// Fill a single system matrix by integrating one [HierCurlIntegral] over a [Domain]'s Basis Space
+///
+/// All pairs of overlapping Shape Functions will be integrated and stored in the matrix by their associated DoF IDs
+///
+/// Computations are parallelized over the Rayon Global Threadpool
+///
+/// # Arguments
+/// * `domain`: The [Domain] over which the Galerkin Sampling is to be performed
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the number of points is chosen for each pair of Elems from their expansion orders.
+/// * A [HierCurlIntegral] `I` must be specified as a Generic Argument. This is used to populate the matrix
+/// * A [HierCurlBasisFnSpace] `BSpace` must also be specified as a Generic Argument. This is used to instantiate the Domains `BasisSpec`s as [HierCurlBasisFn]s
+///
+/// # Returns
+/// * An `Err` if the `Domain` was not constructed with an `H(Curl)` [ContinuityCondition]
+/// * An `Err` if the `Domain` doesn't have any Degrees of Freedom
+/// * An `Err` if the specified number of Gauss Legendre Points is too small
+/// * A [SparseMatrix], otherwise
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let mass_matrix = assemble_matrix::<HierPoly, L2Inner>(&domain, None).unwrap();
+/// assert_eq!(mass_matrix.dimension, domain.dofs.len());
+/// ```
+pub fn assemble_matrix<BSpace: HierCurlBasisFnSpace, I: HierCurlIntegral>(
+    domain: &Domain,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<SparseMatrix, GalerkinSamplingError> {
+    // check for errors
+    let glq_grid_dim = check_sampling_inputs(domain, glq_grid_dim)?;
+
+    let mut matrix = SparseMatrix::new(domain.dofs.len());
+
+    // construct basis sampler
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+    let (bs_sampler, _): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) = BasisFnSampler::with(
+        i_max as usize,
+        j_max as usize,
+        glq_grid_dim[0],
+        glq_grid_dim[1],
+        false,
+    );
+
+    // setup integration
+    let integrators = grid_integrators(
+        &bs_sampler,
+        &domain_glq_grids(domain, glq_grid_dim),
+        I::with_weights,
+    );
+
+    matrix.par_extend(domain.mesh.elems.par_iter().map(|elem| {
+        let mut local_matrix = SparseMatrix::new(domain.dofs.len());
+
+        local_matrix.insert_group(integrate_elem_pairs(
+            domain,
+            elem,
+            &mut bs_sampler.clone(),
+            glq_grid_dim,
+            |_, glq_dims, p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials| {
+                integrators[&glq_dims]
+                    .integrate(
+                        p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials,
+                    )
+                    .full_solution()
+            },
+        ));
+
+        local_matrix
+    }));
+
+    Ok(matrix)
+}
+
+/// Fill a single (complex) system matrix by integrating a weighted sum of [HierCurlIntegral]s over a [Domain]'s Basis Space
+///
+/// Each matrix entry is: `w_0 * I_0(p, q) + w_1 * I_1(p, q) + ...` for the terms of the [IntegralSum]
+///
+/// If the [IntegralSum] has a [Pml](crate::fem_problem::pml::Pml), each term is computed with [HierCurlIntegral::integrate_stretched]. The stretch is evaluated at each of the quadrature points of the Elem over which the integral is computed.
+///
+/// Computations are parallelized over the Rayon Global Threadpool
+///
+/// # Arguments
+/// * `domain`: The [Domain] over which the Galerkin Sampling is to be performed
+/// * `integral_sum`: The weighted sum of Integrals used to populate the matrix
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the number of points is chosen for each pair of Elems from their expansion orders.
+///
+/// # Returns
+/// * An `Err` if the `Domain` was not constructed with an `H(Curl)` [ContinuityCondition]
+/// * An `Err` if the `Domain` doesn't have any Degrees of Freedom
+/// * An `Err` if the specified number of Gauss Legendre Points is too small
+/// * An `Err` if the `IntegralSum` has no terms
+/// * A [ComplexSparseMatrix], otherwise
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// // curl-curl - k² * mass
+/// let k = 1.5;
+/// let sum = IntegralSum::<HierPoly>::new()
+///     .with::<CurlCurl>(1.0)
+///     .with::<L2Inner>(-k * k);
+///
+/// let system_matrix = assemble_matrix_sum(&domain, &sum, None).unwrap();
+/// assert_eq!(system_matrix.dimension(), domain.dofs.len());
+/// ```
+pub fn assemble_matrix_sum<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    integral_sum: &IntegralSum<BSpace>,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<ComplexSparseMatrix, GalerkinSamplingError> {
+    // check for errors
+    let [num_glq_u, num_glq_v] = check_sampling_inputs(domain, glq_grid_dim)?;
+    if integral_sum.is_empty() {
+        return Err(GalerkinSamplingError::EmptyIntegralSum);
+    }
 
-        // local - local
-        for (i, (p_orders, p_dir, p_dof_id)) in local_basis_specs
+    let mut matrix = ComplexSparseMatrix::new(domain.dofs.len());
+
+    // construct basis sampler
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+    let (bs_sampler, _): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) =
+        BasisFnSampler::with(i_max as usize, j_max as usize, num_glq_u, num_glq_v, false);
+
+    // setup integration
+    let integrators = grid_integrators(
+        &bs_sampler,
+        &domain_glq_grids(domain, [num_glq_u, num_glq_v]),
+        |u_weights, v_weights| integral_sum.integrators(u_weights, v_weights),
+    );
+
+    if integral_sum.has_domain_terms() {
+        matrix.par_extend(domain.mesh.elems.par_iter().map(|elem| {
+            let mut local_matrix = ComplexSparseMatrix::new(domain.dofs.len());
+
+            // PML stretch at the quadrature points of each (region Elem, GLQ grid) pair
+            let stretches = RefCell::new(HashMap::new());
+
+            local_matrix.insert_group(integrate_elem_pairs(
+                domain,
+                elem,
+                &mut bs_sampler.clone(),
+                [num_glq_u, num_glq_v],
+                |region_elem,
+                 glq_dims,
+                 p_dir,
+                 q_dir,
+                 p_orders,
+                 q_orders,
+                 p_basis,
+                 q_basis,
+                 materials| {
+                    let Some(pml) = integral_sum.pml() else {
+                        return integrators[&glq_dims]
+                            .iter()
+                            .map(|(weight, integrator)| {
+                                weight
+                                    * integrator.integrate_full(
+                                        p_dir, q_dir, p_orders, q_orders, p_basis, q_basis,
+                                        materials,
+                                    )
+                            })
+                            .sum::<Complex64>();
+                    };
+
+                    let mut stretches = stretches.borrow_mut();
+                    let stretch =
+                        stretches
+                            .entry((region_elem.id, glq_dims))
+                            .or_insert_with(|| {
+                                let [u_points, v_points] = bs_sampler.grid_glq_points(glq_dims);
+                                u_points
+                                    .iter()
+                                    .map(|u| {
+                                        v_points
+                                            .iter()
+                                            .map(|v| {
+                                                pml.stretch(
+                                                    region_elem.real_point(V2D::from([*u, *v])),
+                                                )
+                                            })
+                                            .collect()
+                                    })
+                                    .collect::<Vec<Vec<_>>>()
+                            });
+
+                    integrators[&glq_dims]
+                        .iter()
+                        .map(|(weight, integrator)| {
+                            weight
+                                * integrator.integrate_full_stretched(
+                                    p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials,
+                                    stretch,
+                                )
+                        })
+                        .sum::<Complex64>()
+                },
+            ));
+
+            local_matrix
+        }));
+    }
+
+    // add the boundary terms
+    for &(weight, tag) in integral_sum.boundary_terms() {
+        matrix.insert_group(
+            integrate_boundary_pairs::<BSpace>(domain, tag, [num_glq_u, num_glq_v])?
+                .drain(0..)
+                .map(|(ids, value)| (ids, weight * value))
+                .collect(),
+        );
+    }
+
+    Ok(matrix)
+}
+
+/// Fill a single system matrix with the boundary mass integrals of the tangential components of the Basis Functions along the [Domain]'s tagged boundary Edges
+///
+/// Each matrix entry is the integral: `∫ (F_p · t)(F_q · t) dl` over the leaf boundary Edges with the given `tag`, where `t` is the unit tangent along the Edge.
+/// Only the Basis Functions associated with the tagged Edges have non-zero tangential components along them. Material parameters are not included in the integral.
+///
+/// Boundary Edges are tagged with [Mesh::tag_boundary_edges](crate::fem_domain::domain::mesh::Mesh::tag_boundary_edges) before the Domain is constructed.
+///
+/// # Arguments
+/// * `domain`: The [Domain] over which the Galerkin Sampling is to be performed
+/// * `tag`: The boundary tag selecting which Edges are integrated over
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the default values are used.
+/// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument. This is used to instantiate the Domains `BasisSpec`s as [HierCurlBasisFn]s
+///
+/// # Returns
+/// * An `Err` if the `Domain` was not constructed with an `H(Curl)` [ContinuityCondition]
+/// * An `Err` if the `Domain` doesn't have any Degrees of Freedom
+/// * An `Err` if the specified number of Gauss Legendre Points is too small
+/// * An `Err` if none of the `Domain`'s Edges have the given `tag`
+/// * A [SparseMatrix], otherwise
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+///
+/// // absorbing boundary along the right-hand side of the mesh
+/// mesh.tag_boundary_edges(1, |point| (point.x - 2.0).abs() < 1e-12);
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let boundary_matrix = assemble_boundary_matrix::<HierPoly>(&domain, 1, None).unwrap();
+/// assert!(boundary_matrix.num_entries() > 0);
+///
+/// // impedance boundary terms can also be added into an IntegralSum
+/// let k = 1.5;
+/// let sum = IntegralSum::<HierPoly>::new()
+///     .with::<CurlCurl>(1.0)
+///     .with::<L2Inner>(-k * k)
+///     .with_boundary(1, Complex64::new(0.0, k));
+///
+/// let system_matrix = assemble_matrix_sum(&domain, &sum, None).unwrap();
+/// assert!(system_matrix.im.num_entries() > 0);
+/// ```
+pub fn assemble_boundary_matrix<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    tag: usize,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<SparseMatrix, GalerkinSamplingError> {
+    // check for errors
+    let glq_grid_dim = check_sampling_inputs(domain, glq_grid_dim)?;
+
+    let mut matrix = SparseMatrix::new(domain.dofs.len());
+    matrix.insert_group(integrate_boundary_pairs::<BSpace>(
+        domain,
+        tag,
+        glq_grid_dim,
+    )?);
+
+    Ok(matrix)
+}
+
+// Integrate the tangential components of all pairs of BasisSpecs along the leaf boundary Edges with the given tag.
+// Returns the results alongside the DoF IDs of each pair.
+fn integrate_boundary_pairs<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    tag: usize,
+    glq_grid_dim: [Option<usize>; 2],
+) -> Result<Vec<([usize; 2], f64)>, GalerkinSamplingError> {
+    Ok(sample_boundary_edges::<BSpace>(domain, tag, glq_grid_dim)?
+        .iter()
+        .flat_map(|edge_samples| {
+            let dof_samples = &edge_samples.dof_samples;
+            let mut entries = Vec::with_capacity(dof_samples.len() * dof_samples.len());
+
+            for (i, (p_dof_id, p_values)) in dof_samples.iter().enumerate() {
+                for (q_dof_id, q_values) in dof_samples.iter().skip(i) {
+                    entries.push((
+                        [*p_dof_id, *q_dof_id],
+                        edge_samples.integrate(p_values, q_values),
+                    ));
+                }
+            }
+
+            entries
+        })
+        .collect())
+}
+
+// Samples of the tangential components of the Basis Functions supported along a leaf boundary Edge
+pub(crate) struct BoundaryEdgeSamples {
+    pub edge_id: usize,
+    pub elem_id: usize,
+    // Real-Space quadrature points along the Edge
+    pub points: Vec<Point>,
+    // Quadrature weights along the Edge (scaled by the Edge's length)
+    pub weights: Vec<f64>,
+    // The DoF IDs and the tangential components of their Basis Functions at each point
+    pub dof_samples: Vec<(usize, Vec<f64>)>,
+}
+
+impl BoundaryEdgeSamples {
+    // Integrate the product of two sampled functions along the Edge
+    pub fn integrate(&self, a: &[f64], b: &[f64]) -> f64 {
+        self.weights
             .iter()
-            .map(|bs_p| bs_p.integration_data())
-            .enumerate()
+            .zip(a.iter().zip(b.iter()))
+            .map(|(w, (a, b))| w * a * b)
+            .sum()
+    }
+}
+
+// Sample the tangential components of the Basis Functions along each of the leaf boundary Edges with the given tag.
+// The tangent points from an Edge's first Node to its second Node.
+pub(crate) fn sample_boundary_edges<BSpace: HierCurlBasisFnSpace>(
+    domain: &Domain,
+    tag: usize,
+    [num_glq_u, num_glq_v]: [Option<usize>; 2],
+) -> Result<Vec<BoundaryEdgeSamples>, GalerkinSamplingError> {
+    let boundary_edges: Vec<(&Edge, usize)> = domain
+        .mesh
+        .edges
+        .iter()
+        .filter(|edge| edge.boundary_tag() == Some(tag))
+        .filter_map(|edge| edge.active_boundary_elem().map(|elem_id| (edge, elem_id)))
+        .collect();
+
+    if boundary_edges.is_empty() {
+        return Err(GalerkinSamplingError::UnknownBoundaryTag(tag));
+    }
+
+    // construct basis sampler (with points on the edges of the parametric space)
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+    let (bs_sampler, [u_weights, v_weights]): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) =
+        BasisFnSampler::with(i_max as usize, j_max as usize, num_glq_u, num_glq_v, true);
+    let [u_points, v_points] = bs_sampler.glq_points();
+    let [num_u, num_v] = [u_points.len(), v_points.len()];
+
+    Ok(boundary_edges
+        .par_iter()
+        .map(|&(edge, elem_id)| {
+            let elem = &domain.mesh.elems[elem_id];
+            let edge_idx = elem
+                .edges
+                .iter()
+                .position(|e_id| *e_id == edge.id)
+                .expect("Active boundary Elem is not connected to its Edge!");
+
+            // indices of the (interior) glq points along the edge and their weights
+            let (indices, weights): (Vec<[usize; 2]>, Vec<f64>) = match edge_idx {
+                0 | 1 => (1..(num_u - 1))
+                    .map(|m| ([m, if edge_idx == 0 { 0 } else { num_v - 1 }], u_weights[m]))
+                    .unzip(),
+                _ => (1..(num_v - 1))
+                    .map(|n| ([if edge_idx == 2 { 0 } else { num_u - 1 }, n], v_weights[n]))
+                    .unzip(),
+            };
+
+            // unit tangent along the edge
+            let [p0, p1] = domain.mesh.edge_points(edge.id).unwrap();
+            let tangent = V2D::from([(p1.x - p0.x) / edge.length, (p1.y - p0.y) / edge.length]);
+
+            let bs_local = bs_sampler.clone().sample_basis_fn(elem, None);
+            let dof_samples = domain
+                .local_basis_specs(elem_id)
+                .unwrap()
+                .iter()
+                .filter(
+                    |bs| matches!(bs.loc, BasisLoc::EdgeBs(_, bs_edge_id) if bs_edge_id == edge.id),
+                )
+                .map(|bs| {
+                    let (orders, dir, dof_id) = bs.integration_data();
+                    let values = indices
+                        .iter()
+                        .map(|&[m, n]| match dir {
+                            BasisDir::U => bs_local.f_u(orders, [m, n]).dot_with(&tangent),
+                            BasisDir::V => bs_local.f_v(orders, [m, n]).dot_with(&tangent),
+                            BasisDir::W => 0.0,
+                        })
+                        .collect();
+
+                    (dof_id, values)
+                })
+                .collect();
+
+            BoundaryEdgeSamples {
+                edge_id: edge.id,
+                elem_id,
+                points: indices
+                    .iter()
+                    .map(|&[m, n]| elem.real_point(V2D::from([u_points[m], v_points[n]])))
+                    .collect(),
+                weights: weights.iter().map(|w| w * edge.length / 2.0).collect(),
+                dof_samples,
+            }
+        })
+        .collect())
+}
+
+// Integrate all pairs of overlapping BasisSpecs associated with an Elem: (local, local) pairs and (local, descendant) pairs.
+// The Elem's Materials are used for all integrals. Returns the results alongside the DoF IDs of each pair.
+// `integrate` also receives the Elem over which each integral is computed (the Elem itself or one of its descendants),
+// and the dimensions of the GLQ grid that the Basis Functions were sampled over (see `elem_pair_glq_dims`)
+pub(crate) fn integrate_elem_pairs<BSpace, T, F>(
+    domain: &Domain,
+    elem: &Elem,
+    bf_sampler_elem: &mut BasisFnSampler<HierCurlBasisFn<BSpace>>,
+    glq_grid_dim: [Option<usize>; 2],
+    integrate: F,
+) -> Vec<([usize; 2], T)>
+where
+    BSpace: HierCurlBasisFnSpace,
+    F: Fn(
+        &Elem,
+        [usize; 2],
+        BasisDir,
+        BasisDir,
+        [usize; 2],
+        [usize; 2],
+        &HierCurlBasisFn<BSpace>,
+        &HierCurlBasisFn<BSpace>,
+        &Materials,
+    ) -> T,
+{
+    let elem_materials = elem.get_materials();
+
+    // get relevant data for this Elem
+    let local_glq_dims = elem_pair_glq_dims(elem, elem, glq_grid_dim);
+    let bs_local = bf_sampler_elem.sample_basis_fn_over_grid(elem, None, local_glq_dims);
+    let local_basis_specs = domain.local_basis_specs(elem.id).unwrap();
+    let desc_basis_specs = domain.descendant_basis_specs(elem.id).unwrap();
+
+    let num_desc_basis_specs: usize = desc_basis_specs.iter().map(|(_, bss)| bss.len()).sum();
+    let mut entries: Vec<([usize; 2], T)> = Vec::with_capacity(
+        local_basis_specs.len() * (local_basis_specs.len() / 2 + num_desc_basis_specs),
+    );
+
+    // local - local
+    for (i, (p_orders, p_dir, p_dof_id)) in local_basis_specs
+        .iter()
+        .map(|bs_p| bs_p.integration_data())
+        .enumerate()
+    {
+        for (q_orders, q_dir, q_dof_id) in local_basis_specs
+            .iter()
+            .skip(i)
+            .map(|bs_q| bs_q.integration_data())
         {
-            for (q_orders, q_dir, q_dof_id) in local_basis_specs
+            entries.push((
+                [p_dof_id, q_dof_id],
+                integrate(
+                    elem,
+                    local_glq_dims,
+                    p_dir,
+                    q_dir,
+                    p_orders,
+                    q_orders,
+                    &bs_local,
+                    &bs_local,
+                    elem_materials,
+                ),
+            ));
+        }
+    }
+
+    // local - desc
+    for (p_orders, p_dir, p_dof_id) in local_basis_specs.iter().map(|bs_p| bs_p.integration_data())
+    {
+        for &(q_elem_id, q_elem_basis_specs) in desc_basis_specs.iter() {
+            let q_elem = &domain.mesh.elems[q_elem_id];
+            let glq_dims = elem_pair_glq_dims(elem, q_elem, glq_grid_dim);
+            let bs_p_sampled =
+                bf_sampler_elem.sample_basis_fn_over_grid(elem, Some(q_elem), glq_dims);
+            let bs_q_local = bf_sampler_elem.sample_basis_fn_over_grid(q_elem, None, glq_dims);
+
+            for (q_orders, q_dir, q_dof_id) in q_elem_basis_specs
                 .iter()
-                .skip(i)
                 .map(|bs_q| bs_q.integration_data())
             {
-                let a = a_integrator
-                    .integrate(
+                entries.push((
+                    [p_dof_id, q_dof_id],
+                    integrate(
+                        q_elem,
+                        glq_dims,
                         p_dir,
                         q_dir,
                         p_orders,
                         q_orders,
-                        &bs_local,
-                        &bs_local,
+                        &bs_p_sampled,
+                        &bs_q_local,
                         elem_materials,
-                    )
-                    .full_solution();
-                let b = b_integrator
-                    .integrate(
-                        p_dir,
-                        q_dir,
-                        p_orders,
-                        q_orders,
-                        &bs_local,
-                        &bs_local,
-                        elem_materials,
-                    )
-                    .full_solution();
-
-                local_a_entries.push(([p_dof_id, q_dof_id], a));
-                local_b_entries.push(([p_dof_id, q_dof_id], b));
+                    ),
+                ));
             }
         }
+    }
+
+    entries
+}
+
+/// Integrate a vector-valued source function against a [Domain]'s Basis Space. Return a load vector (or right-hand-side) indexed by DoF ID
+///
+/// Each entry of the load vector is the integral: `<J, F>`, where `J` is the source function, and `F` is the Basis Function associated with the DoF.
+/// The integrals are computed over the leaf [Elem](crate::fem_domain::domain::mesh::elem::Elem)s of the Mesh (where Basis Functions on ancestor Elems are sampled over their leaf descendants)
+///
+/// Computations are parallelized over the Rayon Global Threadpool
+///
+/// # Arguments
+/// * `domain`: The [Domain] over which the Galerkin Sampling is to be performed
+/// * `source`: A function of the Real Space coordinates `(x, y)` which returns the `[x, y]` components of the source at that point
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the default values are used.
+/// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument. This is used to instantiate the Domains `BasisSpec`s as [HierCurlBasisFn]s
+///
+/// # Returns
+/// * An `Err` if the `Domain` was not constructed with an `H(Curl)` [ContinuityCondition]
+/// * An `Err` if the `Domain` doesn't have any Degrees of Freedom
+/// * An `Err` if the specified number of Gauss Legendre Points is too small
+/// * A `Vec<f64>` of length `domain.dofs.len()`, otherwise
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// // integrate a uniform y-directed source against each basis function
+/// let rhs = galerkin_sample_rhs_hcurl::<HierPoly, _>(&domain, |_, _| [0.0, 1.0], None).unwrap();
+/// assert_eq!(rhs.len(), domain.dofs.len());
+/// ```
+pub fn galerkin_sample_rhs_hcurl<BSpace, F>(
+    domain: &Domain,
+    source: F,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<Vec<f64>, GalerkinSamplingError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    F: Fn(f64, f64) -> [f64; 2] + Sync,
+{
+    // check for errors
+    let [num_glq_u, num_glq_v] = check_sampling_inputs(domain, glq_grid_dim)?;
+    let num_dofs = domain.dofs.len();
+
+    // construct basis sampler
+    let [i_max, j_max] = domain.mesh.max_expansion_orders();
+    let (bs_sampler, [u_weights, v_weights]): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) =
+        BasisFnSampler::with(i_max as usize, j_max as usize, num_glq_u, num_glq_v, false);
 
-        local_a.insert_group(local_a_entries);
-        local_b.insert_group(local_b_entries);
+    Ok(domain
+        .mesh
+        .elems
+        .par_iter()
+        .filter(|elem| !elem.has_children())
+        .map(|leaf_elem| {
+            let mut bf_sampler_elem = bs_sampler.clone();
 
-        let mut desc_a_entries: Vec<([usize; 2], f64)> =
-            Vec::with_capacity(local_basis_specs.len() * desc_basis_specs.len());
-        let mut desc_b_entries: Vec<([usize; 2], f64)> =
-            Vec::with_capacity(local_basis_specs.len() * desc_basis_specs.len());
+            // evaluate the source at each of the glq points on the leaf Elem
+            let [u_points, v_points] = bf_sampler_elem.glq_points();
+            let source_values: Vec<Vec<V2D>> = u_points
+                .iter()
+                .map(|u| {
+                    v_points
+                        .iter()
+                        .map(|v| {
+                            let real_point = leaf_elem.real_point(V2D::from([*u, *v]));
+                            V2D::from(source(real_point.x, real_point.y))
+                        })
+                        .collect()
+                })
+                .collect();
 
-        // local - desc
-        for (p_orders, p_dir, p_dof_id) in
-            local_basis_specs.iter().map(|bs_p| bs_p.integration_data())
-        {
-            for &(q_elem_id, q_elem_basis_specs) in desc_basis_specs.iter() {
-                let bs_p_sampled =
-                    bf_sampler_elem.sample_basis_fn(elem, Some(&domain.mesh.elems[q_elem_id]));
-                let bs_q_local =
-                    bf_sampler_elem.sample_basis_fn(&domain.mesh.elems[q_elem_id], None);
+            // integrate the source against the basis functions on the leaf Elem and its ancestors
+            let mut entries: Vec<(usize, f64)> = Vec::new();
+            for anc_elem_id in domain.mesh.ancestor_elems(leaf_elem.id, true).unwrap() {
+                let bs_anc = if anc_elem_id == leaf_elem.id {
+                    bf_sampler_elem.sample_basis_fn(leaf_elem, None)
+                } else {
+                    bf_sampler_elem
+                        .sample_basis_fn(&domain.mesh.elems[anc_elem_id], Some(leaf_elem))
+                };
 
-                for (q_orders, q_dir, q_dof_id) in q_elem_basis_specs
+                for (p_orders, p_dir, p_dof_id) in domain
+                    .local_basis_specs(anc_elem_id)
+                    .unwrap()
                     .iter()
-                    .map(|bs_q| bs_q.integration_data())
+                    .map(|bs_p| bs_p.integration_data())
                 {
-                    let a = a_integrator
-                        .integrate(
-                            p_dir,
-                            q_dir,
-                            p_orders,
-                            q_orders,
-                            &bs_p_sampled,
-                            &bs_q_local,
-                            elem_materials,
-                        )
-                        .full_solution();
-                    let b = b_integrator
-                        .integrate(
-                            p_dir,
-                            q_dir,
-                            p_orders,
-                            q_orders,
-                            &bs_p_sampled,
-                            &bs_q_local,
-                            elem_materials,
-                        )
-                        .full_solution();
-
-                    desc_a_entries.push(([p_dof_id, q_dof_id], a));
-                    desc_b_entries.push(([p_dof_id, q_dof_id], b));
+                    let value = bs_anc.glq_scale()
+                        * real_gauss_quad(&u_weights, &v_weights, |m, n| {
+                            let p_value = match p_dir {
+                                BasisDir::U => bs_anc.f_u(p_orders, [m, n]),
+                                BasisDir::V => bs_anc.f_v(p_orders, [m, n]),
+                                BasisDir::W => V2D::default(),
+                            };
+
+                            source_values[m][n].dot_with(&p_value) * bs_anc.sample_scale([m, n])
+                        });
+
+                    entries.push((p_dof_id, value));
                 }
             }
-        }
 
-        local_a.insert_group(desc_a_entries);
-        local_b.insert_group(desc_b_entries);
+            entries
+        })
+        .fold(
+            || vec![0.0; num_dofs],
+            |mut rhs, entries| {
+                for (dof_id, value) in entries {
+                    rhs[dof_id] += value;
+                }
+                rhs
+            },
+        )
+        .reduce(
+            || vec![0.0; num_dofs],
+            |mut rhs_a, rhs_b| {
+                for (a, b) in rhs_a.iter_mut().zip(rhs_b) {
+                    *a += b;
+                }
+                rhs_a
+            },
+        ))
+}
 
-        [local_a, local_b]
-    }));
+// The dimensions of the GLQ grid used to integrate over a pair of Elems (an Elem and itself, or an Elem and one of its descendants).
+// Fixed dimensions are used if they were specified. Otherwise, they are chosen from the highest expansion orders on the two Elems,
+// such that Elems with low expansion orders are not integrated with as many points as the highest order Elem in the Mesh.
+pub(crate) fn elem_pair_glq_dims(
+    p_elem: &Elem,
+    q_elem: &Elem,
+    [num_glq_u, num_glq_v]: [Option<usize>; 2],
+) -> [usize; 2] {
+    let [p_ni, p_nj] = p_elem.poly_orders.as_array();
+    let [q_ni, q_nj] = q_elem.poly_orders.as_array();
 
-    Ok(gep)
+    [
+        num_glq_u.unwrap_or_else(|| default_ngq(std::cmp::max(p_ni, q_ni))),
+        num_glq_v.unwrap_or_else(|| default_ngq(std::cmp::max(p_nj, q_nj))),
+    ]
+}
+
+// The dimensions of every GLQ grid needed to integrate over the pairs of Elems in a Domain
+pub(crate) fn domain_glq_grids(
+    domain: &Domain,
+    glq_grid_dim: [Option<usize>; 2],
+) -> BTreeSet<[usize; 2]> {
+    let mut grids = BTreeSet::new();
+    for elem in domain.mesh.elems.iter() {
+        grids.insert(elem_pair_glq_dims(elem, elem, glq_grid_dim));
+        for (desc_elem_id, _) in domain.descendant_basis_specs(elem.id).unwrap() {
+            grids.insert(elem_pair_glq_dims(
+                elem,
+                &domain.mesh.elems[desc_elem_id],
+                glq_grid_dim,
+            ));
+        }
+    }
+    grids
+}
+
+// Construct an integrator for each GLQ grid from its weights
+pub(crate) fn grid_integrators<BSpace, T, F>(
+    bs_sampler: &BasisFnSampler<HierCurlBasisFn<BSpace>>,
+    glq_grids: &BTreeSet<[usize; 2]>,
+    build: F,
+) -> HashMap<[usize; 2], T>
+where
+    BSpace: HierCurlBasisFnSpace,
+    F: Fn(&[f64], &[f64]) -> T,
+{
+    glq_grids
+        .iter()
+        .map(|glq_dims| {
+            let [u_weights, v_weights] = bs_sampler.glq_weights(*glq_dims);
+            (*glq_dims, build(&u_weights, &v_weights))
+        })
+        .collect()
+}
+
+// Ensure that the Domain can be sampled with the given GLQ settings. Returns the number of GLQ points along each direction
+pub(crate) fn check_sampling_inputs(
+    domain: &Domain,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<[Option<usize>; 2], GalerkinSamplingError> {
+    if domain.cc != ContinuityCondition::HCurl {
+        return Err(GalerkinSamplingError::WrongContinuityCondition(
+            ContinuityCondition::HCurl,
+            domain.cc,
+        ));
+    }
+    if domain.dofs.is_empty() {
+        return Err(GalerkinSamplingError::EmptyDOFSet);
+    }
+    match glq_grid_dim {
+        Some([u_dim, v_dim]) => {
+            if u_dim < MIN_GLQ_ORDER || v_dim < MIN_GLQ_ORDER {
+                Err(GalerkinSamplingError::InvalidGLQSettings)
+            } else {
+                Ok([Some(u_dim), Some(v_dim)])
+            }
+        }
+        None => Ok([None; 2]),
+    }
 }
 
 /// Error Type for Galerkin Sampling Functions
@@ -192,6 +847,8 @@ pub enum GalerkinSamplingError {
     WrongContinuityCondition(ContinuityCondition, ContinuityCondition),
     EmptyDOFSet,
     InvalidGLQSettings,
+    EmptyIntegralSum,
+    UnknownBoundaryTag(usize),
 }
 
 impl std::error::Error for GalerkinSamplingError {}
@@ -209,6 +866,379 @@ impl fmt::Display for GalerkinSamplingError {
             Self::InvalidGLQSettings => {
                 write!(f, "Invalid GLQ Settings (the number of GLQ points must be at least {}); Cannot execute Galerkin Sampling!", MIN_GLQ_ORDER)
             }
+            Self::EmptyIntegralSum => write!(
+                f,
+                "Integral Sum has no terms; Cannot execute Galerkin Sampling!"
+            ),
+            Self::UnknownBoundaryTag(tag) => write!(
+                f,
+                "No boundary Edges have the tag {}; Cannot execute Galerkin Sampling!",
+                tag
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::{
+        evaluate_hcurl_basis_fn, hierarchical_basis_fns::poly::HierPoly,
+    };
+    use crate::fem_domain::domain::mesh::{h_refinement::HRef, Mesh};
+    use crate::fem_problem::integration::glq::gauss_quadrature_points;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use crate::fem_problem::pml::{Pml, PmlDir, PmlProfile};
+    use std::collections::BTreeMap;
+
+    fn refined_test_domain() -> Domain {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        mesh.h_refine_elems(vec![0, 3], HRef::T).unwrap();
+        Domain::from_mesh(mesh, ContinuityCondition::HCurl)
+    }
+
+    fn entry_map(sm: &SparseMatrix) -> BTreeMap<[usize; 2], f64> {
+        sm.iter_upper_tri().collect()
+    }
+
+    #[test]
+    fn single_matrices_match_gep() {
+        let domain = refined_test_domain();
+
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, Some([8, 8]))
+            .unwrap();
+        let a = assemble_matrix::<HierPoly, CurlCurl>(&domain, Some([8, 8])).unwrap();
+        let b = assemble_matrix::<HierPoly, L2Inner>(&domain, Some([8, 8])).unwrap();
+
+        assert_eq!(entry_map(&gep.a), entry_map(&a));
+        assert_eq!(entry_map(&gep.b), entry_map(&b));
+    }
+
+    #[test]
+    fn adaptive_quadrature_orders() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+        mesh.h_refine_elems(vec![0, 3], HRef::T).unwrap();
+        mesh.set_expansion_orders(vec![(1, [5, 3]), (4, [4, 2])])
+            .unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        // grids are chosen from the highest orders on each pair of Elems, unless they are fixed
+        let elems = &domain.mesh.elems;
+        assert_eq!(
+            elem_pair_glq_dims(&elems[1], &elems[1], [None; 2]),
+            [32, 16]
+        );
+        assert_eq!(elem_pair_glq_dims(&elems[0], &elems[4], [None; 2]), [16, 8]);
+        assert_eq!(elem_pair_glq_dims(&elems[0], &elems[5], [None; 2]), [8, 8]);
+        assert_eq!(
+            elem_pair_glq_dims(&elems[0], &elems[4], [Some(12), None]),
+            [12, 8]
+        );
+        assert!(domain_glq_grids(&domain, [None; 2]).len() > 1);
+        assert_eq!(domain_glq_grids(&domain, [Some(32); 2]).len(), 1);
+
+        // the per-pair grids are exact for the polynomial integrands, so they agree with a fine fixed grid
+        let gep_adaptive =
+            galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+        let gep_fixed =
+            galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, Some([32, 32]))
+                .unwrap();
+
+        for (adaptive, fixed) in [
+            (&gep_adaptive.a, &gep_fixed.a),
+            (&gep_adaptive.b, &gep_fixed.b),
+        ] {
+            let adaptive_entries = entry_map(adaptive);
+            let fixed_entries = entry_map(fixed);
+            assert_eq!(adaptive_entries.len(), fixed_entries.len());
+
+            for (rc, fixed_value) in fixed_entries {
+                let tol = 1e-10 * fixed_value.abs().max(1.0);
+                assert!((adaptive_entries[&rc] - fixed_value).abs() < tol);
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_integral_sum() {
+        let domain = refined_test_domain();
+        let k_sq = 2.5;
+
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, Some([8, 8]))
+            .unwrap();
+        let sum = IntegralSum::<HierPoly>::new()
+            .with::<CurlCurl>(1.0)
+            .with::<L2Inner>(Complex64::new(-k_sq, 0.5));
+        let system = assemble_matrix_sum(&domain, &sum, Some([8, 8])).unwrap();
+
+        let a_entries = entry_map(&gep.a);
+        let b_entries = entry_map(&gep.b);
+        let re_entries = entry_map(&system.re);
+        let im_entries = entry_map(&system.im);
+        assert_eq!(re_entries.len(), a_entries.len());
+
+        for (rc, a) in a_entries {
+            let b = b_entries[&rc];
+            assert!((re_entries[&rc] - (a - k_sq * b)).abs() < 1e-12);
+            assert!((im_entries[&rc] - 0.5 * b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn empty_integral_sum() {
+        let domain = refined_test_domain();
+        let sum = IntegralSum::<HierPoly>::new();
+
+        assert!(matches!(
+            assemble_matrix_sum(&domain, &sum, None),
+            Err(GalerkinSamplingError::EmptyIntegralSum)
+        ));
+    }
+
+    #[test]
+    fn rhs_polynomial_source() {
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders([4, 4]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        let rhs = galerkin_sample_rhs_hcurl::<HierPoly, _>(&domain, |x, y| [x * y * y, 0.0], None)
+            .unwrap();
+
+        // <[x y^2, 0], F_u(1, 2)> = (∫ u^2 du) * (∫ v^2 (v^2 - 1) dv) = -8/45
+        let bs = domain.basis_specs[0]
+            .iter()
+            .find(|bs| bs.dir == BasisDir::U && bs.i == 1 && bs.j == 2)
+            .unwrap();
+        assert!((rhs[bs.dof_id.unwrap()] + 8.0 / 45.0).abs() < 1e-12);
+
+        // the source has no y-component, so the v-directed integrals should vanish
+        for bs in domain.basis_specs[0]
+            .iter()
+            .filter(|bs| bs.dir == BasisDir::V)
+        {
+            assert!(rhs[bs.dof_id.unwrap()].abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn rhs_over_refined_elems() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+        mesh.h_refine_elems(vec![0], HRef::T).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        let rhs =
+            galerkin_sample_rhs_hcurl::<HierPoly, _>(&domain, |_, _| [0.0, 1.0], None).unwrap();
+
+        // The DoF shared between Elems 0 and 1 is defined over the (h-refined) parent Elem 0.
+        // Its integral is accumulated over Elem 0's children: (x_u * ∫(1 + u)du * ∫ 1 dv) * 2 = 4
+        let bs = domain.basis_specs[0]
+            .iter()
+            .find(|bs| bs.dir == BasisDir::V && bs.i == 1 && bs.j == 0)
+            .unwrap();
+        assert!((rhs[bs.dof_id.unwrap()] - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn boundary_mass_integrals() {
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+        mesh.tag_boundary_edges(3, |point| (point.y + 1.0).abs() < 1e-12);
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        let boundary = assemble_boundary_matrix::<HierPoly>(&domain, 3, None).unwrap();
+        let entries = entry_map(&boundary);
+
+        // along the bottom edge (v = -1): F_u(i, 0) = 2 u^i
+        let dof_id = |i: u8| {
+            domain.basis_specs[0]
+                .iter()
+                .find(|bs| bs.dir == BasisDir::U && bs.i == i && bs.j == 0)
+                .unwrap()
+                .dof_id
+                .unwrap()
+        };
+        let [d0, d1] = [dof_id(0), dof_id(1)];
+
+        assert_eq!(entries.len(), 3);
+        assert!((entries[&[d0, d0]] - 8.0).abs() < 1e-12);
+        assert!((entries[&[d1, d1]] - 8.0 / 3.0).abs() < 1e-12);
+        assert!(entries[&[d0.min(d1), d0.max(d1)]].abs() < 1e-12);
+    }
+
+    #[test]
+    fn boundary_terms_in_integral_sum() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        mesh.tag_boundary_edges(1, |point| (point.x - 2.0).abs() < 1e-12);
+        mesh.h_refine_elems(vec![1], HRef::T).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let k = 1.5;
+
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, Some([8, 8]))
+            .unwrap();
+        let boundary = assemble_boundary_matrix::<HierPoly>(&domain, 1, Some([8, 8])).unwrap();
+        let sum = IntegralSum::<HierPoly>::new()
+            .with::<CurlCurl>(1.0)
+            .with::<L2Inner>(-k * k)
+            .with_boundary(1, Complex64::new(0.0, k));
+        let system = assemble_matrix_sum(&domain, &sum, Some([8, 8])).unwrap();
+
+        let b_entries = entry_map(&gep.b);
+        let re_entries = entry_map(&system.re);
+        for (rc, a) in entry_map(&gep.a) {
+            assert!((re_entries[&rc] - (a - k * k * b_entries[&rc])).abs() < 1e-12);
+        }
+
+        let im_entries = entry_map(&system.im);
+        let boundary_entries = entry_map(&boundary);
+        assert!(!boundary_entries.is_empty());
+        for (rc, value) in im_entries {
+            let expected = boundary_entries.get(&rc).copied().unwrap_or(0.0) * k;
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn unknown_boundary_tag() {
+        let domain = refined_test_domain();
+
+        assert!(matches!(
+            assemble_boundary_matrix::<HierPoly>(&domain, 0, None),
+            Err(GalerkinSamplingError::UnknownBoundaryTag(0))
+        ));
+    }
+
+    #[test]
+    fn pml_stretched_integral_sum() {
+        let domain = refined_test_domain();
+        let k_sq = 2.5;
+        let s_x = Complex64::new(1.0, -0.5);
+
+        // Elems 1 and 3 (x > 1) are within the PML
+        let pml = Pml::new(
+            [Point::new(0.0, 0.0), Point::new(1.0, 1.0)],
+            1.0,
+            PmlDir::X,
+            PmlProfile::Constant(0.5),
+        )
+        .unwrap();
+
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, Some([8, 8]))
+            .unwrap();
+        let sum = IntegralSum::<HierPoly>::new()
+            .with::<CurlCurl>(1.0)
+            .with::<L2Inner>(-k_sq)
+            .with_pml(pml);
+        let system = assemble_matrix_sum(&domain, &sum, Some([8, 8])).unwrap();
+
+        let a_entries = entry_map(&gep.a);
+        let b_entries = entry_map(&gep.b);
+        let re_entries = entry_map(&system.re);
+        let im_entries = entry_map(&system.im);
+
+        // elem-type basis functions on Elem 1 only interact with each other
+        let elem_1_bs: Vec<_> = domain.basis_specs[1]
+            .iter()
+            .filter(|bs| bs.loc == BasisLoc::ElemBs)
+            .collect();
+        assert!(!elem_1_bs.is_empty());
+
+        for (i, bs_p) in elem_1_bs.iter().enumerate() {
+            for bs_q in elem_1_bs.iter().skip(i) {
+                let [p_id, q_id] = [bs_p.dof_id.unwrap(), bs_q.dof_id.unwrap()];
+                let rc = [p_id.min(q_id), p_id.max(q_id)];
+
+                let mass_factor = match (bs_p.dir, bs_q.dir) {
+                    (BasisDir::U, BasisDir::U) => 1.0 / s_x,
+                    (BasisDir::V, BasisDir::V) => s_x,
+                    _ => Complex64::new(1.0, 0.0),
+                };
+                let expected = a_entries[&rc] / s_x - k_sq * b_entries[&rc] * mass_factor;
+
+                assert!((re_entries[&rc] - expected.re).abs() < 1e-12);
+                assert!((im_entries.get(&rc).copied().unwrap_or(0.0) - expected.im).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn pml_graded_stretch() {
+        let domain = refined_test_domain();
+        let k_sq = 2.5;
+
+        // linearly graded stretch through Elem 1 (x in [1, 2])
+        let pml = Pml::new(
+            [Point::new(0.0, 0.0), Point::new(1.0, 1.0)],
+            1.0,
+            PmlDir::X,
+            PmlProfile::Polynomial {
+                order: 1.0,
+                max: 2.0,
+            },
+        )
+        .unwrap();
+        let sum = IntegralSum::<HierPoly>::new()
+            .with::<CurlCurl>(1.0)
+            .with::<L2Inner>(-k_sq)
+            .with_pml(pml.clone());
+        let system = assemble_matrix_sum(&domain, &sum, Some([8, 8])).unwrap();
+        let re_entries = entry_map(&system.re);
+        let im_entries = entry_map(&system.im);
+
+        // integrate the stretched integrands directly: `(curl_p * curl_q) / (s_x * s_y) - k² * ε * (s_y / s_x * p_x * q_x + s_x / s_y * p_y * q_y)`
+        let elem = &domain.mesh.elems[1];
+        let eps = elem.get_materials().eps_rel.re;
+        let mu = elem.get_materials().mu_rel.re;
+        let [p0, p1] = domain.mesh.elem_diag_points(1).unwrap();
+        let area_scale = (p1.x - p0.x) * (p1.y - p0.y) / 4.0;
+        let (points, weights) = gauss_quadrature_points(8, false);
+
+        let elem_1_bs: Vec<_> = domain.basis_specs[1]
+            .iter()
+            .filter(|bs| bs.loc == BasisLoc::ElemBs)
+            .collect();
+        assert!(!elem_1_bs.is_empty());
+
+        for (i, bs_p) in elem_1_bs.iter().enumerate() {
+            for bs_q in elem_1_bs.iter().skip(i) {
+                let mut expected = Complex64::new(0.0, 0.0);
+                for (u, w_u) in points.iter().zip(weights.iter()) {
+                    for (v, w_v) in points.iter().zip(weights.iter()) {
+                        let [s_x, s_y] = pml.stretch(elem.real_point(V2D::from([*u, *v])));
+                        let [p, q] = [bs_p, bs_q].map(|bs| {
+                            evaluate_hcurl_basis_fn::<HierPoly>(
+                                elem,
+                                None,
+                                bs.dir,
+                                [bs.i as usize, bs.j as usize],
+                                [*u, *v],
+                            )
+                            .unwrap()
+                        });
+
+                        expected += w_u
+                            * w_v
+                            * area_scale
+                            * (p.curl * q.curl / (mu * s_x * s_y)
+                                - k_sq
+                                    * eps
+                                    * (s_y / s_x * p.value.x() * q.value.x()
+                                        + s_x / s_y * p.value.y() * q.value.y()));
+                    }
+                }
+
+                let [p_id, q_id] = [bs_p.dof_id.unwrap(), bs_q.dof_id.unwrap()];
+                let rc = [p_id.min(q_id), p_id.max(q_id)];
+                let scale = expected.norm().max(1.0);
+                assert!((re_entries[&rc] - expected.re).abs() < 1e-10 * scale);
+                assert!(
+                    (im_entries.get(&rc).copied().unwrap_or(0.0) - expected.im).abs()
+                        < 1e-10 * scale
+                );
+            }
         }
     }
 }
@@ -1,5 +1,7 @@
+use super::pml::Pml;
 use crate::fem_domain::basis::{HierCurlBasisFn, HierCurlBasisFnSpace};
 use crate::fem_domain::domain::{dof::basis_spec::BasisDir, mesh::element::Materials};
+use num_complex::Complex64;
 
 /// Methods to assist in Gauss-Legendre-Quadrature integration
 pub mod glq;
@@ -87,4 +89,205 @@ pub trait HierCurlIntegral: Sync + Send {
         q_basis: &HierCurlBasisFn<BSpace>,
         materials: &Materials,
     ) -> IntegralResult;
+
+    /// Compute the full integral between [HierCurlBasisFn]'s P and Q under a complex coordinate stretch (see [Pml](crate::fem_problem::pml::Pml)).
+    ///
+    /// `stretch[m][n]` holds the stretching factors `[s_x, s_y]` at the `[m, n]`th quadrature point, such that the stretch is applied within the integrand.
+    /// Integrals which are unaffected by coordinate stretching can use the default implementation.
+    #[allow(clippy::too_many_arguments)]
+    fn integrate_stretched<BSpace: HierCurlBasisFnSpace>(
+        &self,
+        p_dir: BasisDir,
+        q_dir: BasisDir,
+        p_orders: [usize; 2],
+        q_orders: [usize; 2],
+        p_basis: &HierCurlBasisFn<BSpace>,
+        q_basis: &HierCurlBasisFn<BSpace>,
+        materials: &Materials,
+        _stretch: &[Vec<[Complex64; 2]>],
+    ) -> Complex64 {
+        Complex64::from(
+            self.integrate(
+                p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials,
+            )
+            .full_solution(),
+        )
+    }
+}
+
+/// An object-safe counterpart to [HierCurlIntegral] for a particular [HierCurlBasisFnSpace]
+///
+/// This trait is implemented for all [HierCurlIntegral]s, allowing Integrals of different types to be stored together (see [IntegralSum])
+pub trait DynHierCurlIntegral<BSpace: HierCurlBasisFnSpace>: Sync + Send {
+    /// Compute the full solution of the integral between [HierCurlBasisFn]'s P and Q (see [HierCurlIntegral::integrate])
+    #[allow(clippy::too_many_arguments)]
+    fn integrate_full(
+        &self,
+        p_dir: BasisDir,
+        q_dir: BasisDir,
+        p_orders: [usize; 2],
+        q_orders: [usize; 2],
+        p_basis: &HierCurlBasisFn<BSpace>,
+        q_basis: &HierCurlBasisFn<BSpace>,
+        materials: &Materials,
+    ) -> f64;
+
+    /// Compute the full solution of the integral between [HierCurlBasisFn]'s P and Q under a complex coordinate stretch (see [HierCurlIntegral::integrate_stretched])
+    #[allow(clippy::too_many_arguments)]
+    fn integrate_full_stretched(
+        &self,
+        p_dir: BasisDir,
+        q_dir: BasisDir,
+        p_orders: [usize; 2],
+        q_orders: [usize; 2],
+        p_basis: &HierCurlBasisFn<BSpace>,
+        q_basis: &HierCurlBasisFn<BSpace>,
+        materials: &Materials,
+        stretch: &[Vec<[Complex64; 2]>],
+    ) -> Complex64;
+}
+
+impl<BSpace: HierCurlBasisFnSpace, I: HierCurlIntegral> DynHierCurlIntegral<BSpace> for I {
+    fn integrate_full(
+        &self,
+        p_dir: BasisDir,
+        q_dir: BasisDir,
+        p_orders: [usize; 2],
+        q_orders: [usize; 2],
+        p_basis: &HierCurlBasisFn<BSpace>,
+        q_basis: &HierCurlBasisFn<BSpace>,
+        materials: &Materials,
+    ) -> f64 {
+        self.integrate(
+            p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials,
+        )
+        .full_solution()
+    }
+
+    fn integrate_full_stretched(
+        &self,
+        p_dir: BasisDir,
+        q_dir: BasisDir,
+        p_orders: [usize; 2],
+        q_orders: [usize; 2],
+        p_basis: &HierCurlBasisFn<BSpace>,
+        q_basis: &HierCurlBasisFn<BSpace>,
+        materials: &Materials,
+        stretch: &[Vec<[Complex64; 2]>],
+    ) -> Complex64 {
+        self.integrate_stretched(
+            p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, materials, stretch,
+        )
+    }
+}
+
+type DynIntegralConstructor<BSpace> = fn(&[f64], &[f64]) -> Box<dyn DynHierCurlIntegral<BSpace>>;
+
+/// A weighted sum of [HierCurlIntegral]s: `w_0 * I_0 + w_1 * I_1 + ...`, where the weights are complex scalars
+///
+/// Used to assemble a single system matrix from several bilinear forms (ex: `CurlCurl - k² * L2Inner`)
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let k_squared = 2.5;
+/// let sum = IntegralSum::<HierPoly>::new()
+///     .with::<CurlCurl>(1.0)
+///     .with::<L2Inner>(-k_squared);
+///
+/// assert_eq!(sum.len(), 2);
+/// ```
+pub struct IntegralSum<BSpace: HierCurlBasisFnSpace> {
+    terms: Vec<(Complex64, DynIntegralConstructor<BSpace>)>,
+    boundary_terms: Vec<(Complex64, usize)>,
+    pml: Option<Pml>,
+}
+
+impl<BSpace: HierCurlBasisFnSpace> IntegralSum<BSpace> {
+    /// Create an empty sum
+    pub fn new() -> Self {
+        Self {
+            terms: Vec::new(),
+            boundary_terms: Vec::new(),
+            pml: None,
+        }
+    }
+
+    /// Add a term to the sum (builder style)
+    pub fn with<I: HierCurlIntegral + 'static>(mut self, weight: impl Into<Complex64>) -> Self {
+        self.push::<I>(weight);
+        self
+    }
+
+    /// Add a term to the sum
+    pub fn push<I: HierCurlIntegral + 'static>(&mut self, weight: impl Into<Complex64>) {
+        self.terms.push((weight.into(), |u_weights, v_weights| {
+            Box::new(I::with_weights(u_weights, v_weights))
+        }));
+    }
+
+    /// Add a boundary term to the sum (builder style)
+    ///
+    /// Boundary terms are mass integrals of the tangential components of the Basis Functions along the boundary Edges with the given `tag`
+    pub fn with_boundary(mut self, tag: usize, weight: impl Into<Complex64>) -> Self {
+        self.push_boundary(tag, weight);
+        self
+    }
+
+    /// Add a boundary term to the sum
+    pub fn push_boundary(&mut self, tag: usize, weight: impl Into<Complex64>) {
+        self.boundary_terms.push((weight.into(), tag));
+    }
+
+    /// The weights and tags of each of the boundary terms
+    pub fn boundary_terms(&self) -> &[(Complex64, usize)] {
+        &self.boundary_terms
+    }
+
+    /// Apply a Perfectly Matched Layer to the (non-boundary) terms of the sum (builder style)
+    ///
+    /// Each term is computed with [HierCurlIntegral::integrate_stretched], where the stretch is evaluated at each of the quadrature points
+    pub fn with_pml(mut self, pml: Pml) -> Self {
+        self.pml = Some(pml);
+        self
+    }
+
+    /// The Perfectly Matched Layer applied to the sum (if any)
+    pub fn pml(&self) -> Option<&Pml> {
+        self.pml.as_ref()
+    }
+
+    /// Number of terms in the sum (including boundary terms)
+    pub fn len(&self) -> usize {
+        self.terms.len() + self.boundary_terms.len()
+    }
+
+    /// Check if the sum has no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.boundary_terms.is_empty()
+    }
+
+    /// Check if the sum has any (non-boundary) terms integrated over the Elems of the Domain
+    pub fn has_domain_terms(&self) -> bool {
+        !self.terms.is_empty()
+    }
+
+    /// Instantiate each of the Integrals with a set of Gauss-Legendre-Quadrature weights. Returns the integrators alongside their weights
+    pub fn integrators(
+        &self,
+        u_weights: &[f64],
+        v_weights: &[f64],
+    ) -> Vec<(Complex64, Box<dyn DynHierCurlIntegral<BSpace>>)> {
+        self.terms
+            .iter()
+            .map(|(weight, constructor)| (*weight, constructor(u_weights, v_weights)))
+            .collect()
+    }
+}
+
+impl<BSpace: HierCurlBasisFnSpace> Default for IntegralSum<BSpace> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
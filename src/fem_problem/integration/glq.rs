@@ -1,4 +1,5 @@
 use nalgebra::{DMatrix, SymmetricEigen};
+use num_complex::Complex64;
 
 /// 2D Gauss Legendre Quadrature integral of some function F defined over an m by n rectangular region.
 /// ```
@@ -31,6 +32,36 @@ where
     solution
 }
 
+/// 2D Gauss Legendre Quadrature integral of some complex-valued function F defined over an m by n rectangular region.
+/// ```
+/// use fem_2d::fem_problem::integration::glq::*;
+/// use num_complex::Complex64;
+///
+/// let (u_points, u_weights) = gauss_quadrature_points(10, false);
+/// let (v_points, v_weights) = gauss_quadrature_points(10, false);
+///
+/// // compute the integral of (u^2 + i * v^2)
+/// let solution = complex_gauss_quad(&u_weights, &v_weights, |m, n| {
+///    Complex64::new(u_points[m].powi(2), v_points[n].powi(2))
+/// });
+///
+/// assert!((solution - Complex64::new(4.0 / 3.0, 4.0 / 3.0)).norm() < 1e-12);
+/// ```
+pub fn complex_gauss_quad<F>(u_weights: &[f64], v_weights: &[f64], integrand: F) -> Complex64
+where
+    F: Fn(usize, usize) -> Complex64,
+{
+    let mut solution = Complex64::new(0.0, 0.0);
+    for (m, u_w) in u_weights.iter().enumerate() {
+        let mut inner_solution = Complex64::new(0.0, 0.0);
+        for (n, v_w) in v_weights.iter().enumerate() {
+            inner_solution += integrand(m, n) * v_w;
+        }
+        solution += inner_solution * u_w;
+    }
+    solution
+}
+
 /// 2D Gauss Legendre Quadrature integral of some function F defined over an m by n rectangular region.
 ///
 /// This is the same as [real_gauss_quad] except, the outer edge (the first and last elements of 'u_weights' and 'v_weights') are ignored.
@@ -75,6 +106,52 @@ where
     solution
 }
 
+/// 2D Gauss Legendre Quadrature integral of a separable function `F(u, v) = F_u(u) * F_v(v)` defined over an m by n rectangular region.
+///
+/// This gives the same result as [real_gauss_quad], but the integral is factored into a product of two 1D integrals,
+/// such that `F_u` and `F_v` are only evaluated `m + n` times (rather than `m * n` times).
+///
+/// ```
+/// use fem_2d::fem_problem::integration::glq::*;
+///
+/// // define glq points over `(-1, 1)` in both directions
+/// let (u_points, u_weights) = gauss_quadrature_points(10, false);
+/// let (v_points, v_weights) = gauss_quadrature_points(10, false);
+///
+/// // compute the integral of (u^2 * v^2)
+/// let solution = real_gauss_quad_separable(
+///     &u_weights,
+///     &v_weights,
+///     |m| u_points[m].powi(2),
+///     |n| v_points[n].powi(2),
+/// );
+///
+/// assert!((solution - 4.0 / 9.0).abs() < 1e-12);
+/// ```
+pub fn real_gauss_quad_separable<FU, FV>(
+    u_weights: &[f64],
+    v_weights: &[f64],
+    u_integrand: FU,
+    v_integrand: FV,
+) -> f64
+where
+    FU: Fn(usize) -> f64,
+    FV: Fn(usize) -> f64,
+{
+    let u_solution: f64 = u_weights
+        .iter()
+        .enumerate()
+        .map(|(m, u_w)| u_integrand(m) * u_w)
+        .sum();
+    let v_solution: f64 = v_weights
+        .iter()
+        .enumerate()
+        .map(|(n, v_w)| v_integrand(n) * v_w)
+        .sum();
+
+    u_solution * v_solution
+}
+
 /// 1D integral over some function F, which is defined along one edge of a rectangular parametric region.
 ///
 /// ```
@@ -4,6 +4,7 @@ use crate::fem_domain::basis::{HierCurlBasisFn, HierCurlBasisFnSpace};
 use crate::fem_domain::domain::{
     dof::basis_spec::BasisDir, mesh::element::Materials, mesh::space::V2D,
 };
+use num_complex::Complex64;
 
 /// <∇ × u, ∇ × ρ>
 pub mod curl_curl {
@@ -37,56 +38,10 @@ pub mod curl_curl {
                 (1.0 / materials.mu_rel.re)
                     // * p_basis.glq_scale()
                     // * q_basis.glq_scale()
-                    * match (p_dir, q_dir) {
-                        (BasisDir::U, BasisDir::U) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                let p_curl = p_basis
-                                    .f_u_d1(p_orders, [m, n], q_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-                                let q_curl = q_basis
-                                    .f_u_d1(q_orders, [m, n], p_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-
-                                p_curl * q_curl * max_uv_ratios(p_basis, q_basis, [m, n])
-                            })
-                        }
-                        (BasisDir::U, BasisDir::V) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                let p_curl = p_basis
-                                    .f_u_d1(p_orders, [m, n], q_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-                                let q_curl = q_basis
-                                    .f_v_d1(q_orders, [m, n], p_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-
-                                p_curl * q_curl
-                            })
-                        }
-                        (BasisDir::V, BasisDir::U) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                let p_curl = p_basis
-                                    .f_v_d1(p_orders, [m, n], q_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-                                let q_curl = q_basis
-                                    .f_u_d1(q_orders, [m, n], p_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-
-                                p_curl * q_curl
-                            })
-                        }
-                        (BasisDir::V, BasisDir::V) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                let p_curl = p_basis
-                                    .f_v_d1(p_orders, [m, n], q_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-                                let q_curl = q_basis
-                                    .f_v_d1(q_orders, [m, n], p_basis.deriv_scale())
-                                    .dot_with(&CURL_OP);
-
-                                p_curl * q_curl * max_vu_ratios(p_basis, q_basis, [m, n])
-                            })
-                        }
-                        (_, _) => 0.0,
+                    * if p_basis.has_separable_jacobian() && q_basis.has_separable_jacobian() {
+                        self.separable_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis)
+                    } else {
+                        self.full_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis)
                     },
             )
         }
@@ -235,10 +190,147 @@ pub mod curl_curl {
 
             IntegralResult::ByParts(surface_term, edge_terms)
         }
+
+        /// The curl-curl product is scaled by `1 / (s_x * s_y)` at each quadrature point (from: `μ_eff = μ * s_x * s_y`)
+        fn integrate_stretched<BSpace: HierCurlBasisFnSpace>(
+            &self,
+            p_dir: BasisDir,
+            q_dir: BasisDir,
+            p_orders: [usize; 2],
+            q_orders: [usize; 2],
+            p_basis: &HierCurlBasisFn<BSpace>,
+            q_basis: &HierCurlBasisFn<BSpace>,
+            materials: &Materials,
+            stretch: &[Vec<[Complex64; 2]>],
+        ) -> Complex64 {
+            if p_dir == BasisDir::W || q_dir == BasisDir::W {
+                return Complex64::new(0.0, 0.0);
+            }
+
+            (1.0 / materials.mu_rel.re)
+                * complex_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
+                    let [s_x, s_y] = stretch[m][n];
+                    curl_product(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, [m, n])
+                        / (s_x * s_y)
+                })
+        }
+    }
+
+    impl CurlCurl {
+        // Integrate the curls of P and Q with a full 2D quadrature
+        pub(super) fn full_quad<BSpace: HierCurlBasisFnSpace>(
+            &self,
+            p_dir: BasisDir,
+            q_dir: BasisDir,
+            p_orders: [usize; 2],
+            q_orders: [usize; 2],
+            p_basis: &HierCurlBasisFn<BSpace>,
+            q_basis: &HierCurlBasisFn<BSpace>,
+        ) -> f64 {
+            if p_dir == BasisDir::W || q_dir == BasisDir::W {
+                return 0.0;
+            }
+
+            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
+                curl_product(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis, [m, n])
+            })
+        }
+
+        // Integrate the curls of P and Q over Basis Functions with constant diagonal Jacobians.
+        // Each curl is then a product of a 1D function along u and a 1D function along v
+        //  (ex: `∇ × F_u = -N_i(u) * T'_j(v) * du/dx`), so the 2D quadrature is computed as a product of two 1D quadratures
+        pub(super) fn separable_quad<BSpace: HierCurlBasisFnSpace>(
+            &self,
+            p_dir: BasisDir,
+            q_dir: BasisDir,
+            [p_i, p_j]: [usize; 2],
+            [q_i, q_j]: [usize; 2],
+            p_basis: &HierCurlBasisFn<BSpace>,
+            q_basis: &HierCurlBasisFn<BSpace>,
+        ) -> f64 {
+            let [p_u, p_v] = [p_basis.u_shapes(), p_basis.v_shapes()];
+            let [q_u, q_v] = [q_basis.u_shapes(), q_basis.v_shapes()];
+
+            // the constant coefficients of each curl (derivatives are taken w.r.t. the other function's parametric space)
+            let p_coef_u = p_basis.jac_inv[0][0].u[0] * q_basis.deriv_scale()[0];
+            let p_coef_v = p_basis.jac_inv[0][0].v[1] * q_basis.deriv_scale()[1];
+            let q_coef_u = q_basis.jac_inv[0][0].u[0] * p_basis.deriv_scale()[0];
+            let q_coef_v = q_basis.jac_inv[0][0].v[1] * p_basis.deriv_scale()[1];
+
+            match (p_dir, q_dir) {
+                (BasisDir::U, BasisDir::U) => {
+                    p_coef_u
+                        * q_coef_u
+                        * max_uv_ratios(p_basis, q_basis, [0, 0])
+                        * real_gauss_quad_separable(
+                            &self.u_weights,
+                            &self.v_weights,
+                            |m| p_u.norm(p_i, m) * q_u.norm(q_i, m),
+                            |n| p_v.tang_d1(p_j, n) * q_v.tang_d1(q_j, n),
+                        )
+                }
+                (BasisDir::U, BasisDir::V) => {
+                    -p_coef_u
+                        * q_coef_v
+                        * real_gauss_quad_separable(
+                            &self.u_weights,
+                            &self.v_weights,
+                            |m| p_u.norm(p_i, m) * q_u.tang_d1(q_i, m),
+                            |n| p_v.tang_d1(p_j, n) * q_v.norm(q_j, n),
+                        )
+                }
+                (BasisDir::V, BasisDir::U) => {
+                    -p_coef_v
+                        * q_coef_u
+                        * real_gauss_quad_separable(
+                            &self.u_weights,
+                            &self.v_weights,
+                            |m| p_u.tang_d1(p_i, m) * q_u.norm(q_i, m),
+                            |n| p_v.norm(p_j, n) * q_v.tang_d1(q_j, n),
+                        )
+                }
+                (BasisDir::V, BasisDir::V) => {
+                    p_coef_v
+                        * q_coef_v
+                        * max_vu_ratios(p_basis, q_basis, [0, 0])
+                        * real_gauss_quad_separable(
+                            &self.u_weights,
+                            &self.v_weights,
+                            |m| p_u.tang_d1(p_i, m) * q_u.tang_d1(q_i, m),
+                            |n| p_v.norm(p_j, n) * q_v.norm(q_j, n),
+                        )
+                }
+                (_, _) => 0.0,
+            }
+        }
     }
 
     const CURL_OP: V2D = V2D::from([-1.0, 1.0]);
 
+    // The product of the curls of P and Q (u- or v-directed) at the `[m, n]`th quadrature point
+    fn curl_product<BSpace: HierCurlBasisFnSpace>(
+        p_dir: BasisDir,
+        q_dir: BasisDir,
+        p_orders: [usize; 2],
+        q_orders: [usize; 2],
+        p_basis: &HierCurlBasisFn<BSpace>,
+        q_basis: &HierCurlBasisFn<BSpace>,
+        [m, n]: [usize; 2],
+    ) -> f64 {
+        let curl = |dir, orders, basis: &HierCurlBasisFn<BSpace>, para_scale| match dir {
+            BasisDir::U => basis.f_u_d1(orders, [m, n], para_scale).dot_with(&CURL_OP),
+            _ => basis.f_v_d1(orders, [m, n], para_scale).dot_with(&CURL_OP),
+        };
+
+        curl(p_dir, p_orders, p_basis, q_basis.deriv_scale())
+            * curl(q_dir, q_orders, q_basis, p_basis.deriv_scale())
+            * match (p_dir, q_dir) {
+                (BasisDir::U, BasisDir::U) => max_uv_ratios(p_basis, q_basis, [m, n]),
+                (BasisDir::V, BasisDir::V) => max_vu_ratios(p_basis, q_basis, [m, n]),
+                (_, _) => 1.0,
+            }
+    }
+
     const EDGE_UNIT_VECTORS: [V2D; 4] = [
         V2D::from([-1.0, 0.0]),
         V2D::from([1.0, 0.0]),
@@ -303,52 +395,10 @@ pub mod inner {
                 materials.eps_rel.re
                     * p_basis.glq_scale()
                     * q_basis.glq_scale()
-                    * match (p_dir, q_dir) {
-                        (BasisDir::U, BasisDir::U) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                V2D::dot(
-                                    p_basis.f_u(p_orders, [m, n]),
-                                    q_basis.f_u(q_orders, [m, n]),
-                                ) * partial_max(
-                                    p_basis.sample_scale([m, n]),
-                                    q_basis.sample_scale([m, n]),
-                                )
-                            })
-                        }
-                        (BasisDir::U, BasisDir::V) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                V2D::dot(
-                                    p_basis.f_u(p_orders, [m, n]),
-                                    q_basis.f_v(q_orders, [m, n]),
-                                ) * partial_max(
-                                    p_basis.sample_scale([m, n]),
-                                    q_basis.sample_scale([m, n]),
-                                )
-                            })
-                        }
-                        (BasisDir::V, BasisDir::U) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                V2D::dot(
-                                    p_basis.f_v(p_orders, [m, n]),
-                                    q_basis.f_u(q_orders, [m, n]),
-                                ) * partial_max(
-                                    p_basis.sample_scale([m, n]),
-                                    q_basis.sample_scale([m, n]),
-                                )
-                            })
-                        }
-                        (BasisDir::V, BasisDir::V) => {
-                            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
-                                V2D::dot(
-                                    p_basis.f_v(p_orders, [m, n]),
-                                    q_basis.f_v(q_orders, [m, n]),
-                                ) * partial_max(
-                                    p_basis.sample_scale([m, n]),
-                                    q_basis.sample_scale([m, n]),
-                                )
-                            })
-                        }
-                        (_, _) => 0.0,
+                    * if p_basis.has_separable_jacobian() && q_basis.has_separable_jacobian() {
+                        self.separable_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis)
+                    } else {
+                        self.full_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis)
                     },
             )
         }
@@ -416,9 +466,238 @@ pub mod inner {
                     },
             )
         }
+
+        /// `ε_eff = ε * diag(s_y / s_x, s_x / s_y)` is applied to the x and y components of P and Q at each quadrature point
+        fn integrate_stretched<BSpace: HierCurlBasisFnSpace>(
+            &self,
+            p_dir: BasisDir,
+            q_dir: BasisDir,
+            p_orders: [usize; 2],
+            q_orders: [usize; 2],
+            p_basis: &HierCurlBasisFn<BSpace>,
+            q_basis: &HierCurlBasisFn<BSpace>,
+            materials: &Materials,
+            stretch: &[Vec<[Complex64; 2]>],
+        ) -> Complex64 {
+            if p_dir == BasisDir::W || q_dir == BasisDir::W {
+                return Complex64::new(0.0, 0.0);
+            }
+
+            materials.eps_rel.re
+                * p_basis.glq_scale()
+                * q_basis.glq_scale()
+                * complex_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
+                    let [s_x, s_y] = stretch[m][n];
+                    let [p, q] = [
+                        sample(p_dir, p_orders, p_basis, [m, n]),
+                        sample(q_dir, q_orders, q_basis, [m, n]),
+                    ];
+
+                    (p.x() * q.x() * s_y / s_x + p.y() * q.y() * s_x / s_y)
+                        * partial_max(p_basis.sample_scale([m, n]), q_basis.sample_scale([m, n]))
+                })
+        }
+    }
+
+    impl L2Inner {
+        // Integrate the inner product of P and Q with a full 2D quadrature
+        pub(super) fn full_quad<BSpace: HierCurlBasisFnSpace>(
+            &self,
+            p_dir: BasisDir,
+            q_dir: BasisDir,
+            p_orders: [usize; 2],
+            q_orders: [usize; 2],
+            p_basis: &HierCurlBasisFn<BSpace>,
+            q_basis: &HierCurlBasisFn<BSpace>,
+        ) -> f64 {
+            if p_dir == BasisDir::W || q_dir == BasisDir::W {
+                return 0.0;
+            }
+
+            real_gauss_quad(&self.u_weights, &self.v_weights, |m, n| {
+                V2D::dot(
+                    sample(p_dir, p_orders, p_basis, [m, n]),
+                    sample(q_dir, q_orders, q_basis, [m, n]),
+                ) * partial_max(p_basis.sample_scale([m, n]), q_basis.sample_scale([m, n]))
+            })
+        }
+
+        // Integrate the inner product of P and Q over Basis Functions with constant diagonal Jacobians.
+        // Each function is then a constant vector times a product of 1D functions along u and v
+        //  (ex: `F_u = N_i(u) * T_j(v) * J^-1_u`), so the 2D quadrature is computed as a product of two 1D quadratures.
+        // u- and v-directed functions are orthogonal.
+        pub(super) fn separable_quad<BSpace: HierCurlBasisFnSpace>(
+            &self,
+            p_dir: BasisDir,
+            q_dir: BasisDir,
+            [p_i, p_j]: [usize; 2],
+            [q_i, q_j]: [usize; 2],
+            p_basis: &HierCurlBasisFn<BSpace>,
+            q_basis: &HierCurlBasisFn<BSpace>,
+        ) -> f64 {
+            let [p_u, p_v] = [p_basis.u_shapes(), p_basis.v_shapes()];
+            let [q_u, q_v] = [q_basis.u_shapes(), q_basis.v_shapes()];
+            let [p_jac_inv, q_jac_inv] = [&p_basis.jac_inv[0][0], &q_basis.jac_inv[0][0]];
+            let sample_scale =
+                partial_max(p_basis.sample_scale([0, 0]), q_basis.sample_scale([0, 0]));
+
+            match (p_dir, q_dir) {
+                (BasisDir::U, BasisDir::U) => {
+                    V2D::dot(p_jac_inv.u, q_jac_inv.u)
+                        * sample_scale
+                        * real_gauss_quad_separable(
+                            &self.u_weights,
+                            &self.v_weights,
+                            |m| p_u.norm(p_i, m) * q_u.norm(q_i, m),
+                            |n| p_v.tang(p_j, n) * q_v.tang(q_j, n),
+                        )
+                }
+                (BasisDir::V, BasisDir::V) => {
+                    V2D::dot(p_jac_inv.v, q_jac_inv.v)
+                        * sample_scale
+                        * real_gauss_quad_separable(
+                            &self.u_weights,
+                            &self.v_weights,
+                            |m| p_u.tang(p_i, m) * q_u.tang(q_i, m),
+                            |n| p_v.norm(p_j, n) * q_v.norm(q_j, n),
+                        )
+                }
+                (_, _) => 0.0,
+            }
+        }
+    }
+
+    // The value of a u- or v-directed Basis Function at the `[m, n]`th quadrature point
+    fn sample<BSpace: HierCurlBasisFnSpace>(
+        dir: BasisDir,
+        orders: [usize; 2],
+        basis: &HierCurlBasisFn<BSpace>,
+        [m, n]: [usize; 2],
+    ) -> V2D {
+        match dir {
+            BasisDir::U => basis.f_u(orders, [m, n]),
+            _ => basis.f_v(orders, [m, n]),
+        }
     }
 
     fn partial_max(v1: f64, v2: f64) -> f64 {
         std::cmp::max_by(v1, v2, |a, b| a.partial_cmp(b).unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::curl_curl::CurlCurl;
+    use super::inner::L2Inner;
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::{lobatto::HierLobatto, poly::HierPoly};
+    use crate::fem_domain::basis::BasisFnSampler;
+    use crate::fem_domain::domain::mesh::{h_refinement::HRef, Mesh};
+    use crate::fem_problem::integration::HierCurlIntegral;
+    use std::sync::Arc;
+
+    const I_MAX: usize = 5;
+    const J_MAX: usize = 4;
+
+    type BasisPair<BSpace> = [Arc<HierCurlBasisFn<BSpace>>; 2];
+    type Case = (BasisDir, BasisDir, [usize; 2], [usize; 2]);
+
+    // The Basis Functions of an Elem paired with themselves, and with those of one of its (anisotropically refined) descendants
+    fn separable_basis_pairs<BSpace: HierCurlBasisFnSpace>(
+    ) -> ([Vec<f64>; 2], [BasisPair<BSpace>; 2]) {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.h_refine_elems(vec![0], HRef::T).unwrap();
+        mesh.h_refine_elems(vec![4], HRef::u()).unwrap();
+        let [elem, desc_elem] = [&mesh.elems[0], mesh.elems.last().unwrap()];
+
+        let (mut sampler, weights): (BasisFnSampler<HierCurlBasisFn<BSpace>>, _) =
+            BasisFnSampler::with(I_MAX, J_MAX, None, None, false);
+
+        let pairs = [
+            [
+                sampler.sample_basis_fn(elem, None),
+                sampler.sample_basis_fn(elem, None),
+            ],
+            [
+                sampler.sample_basis_fn(elem, Some(desc_elem)),
+                sampler.sample_basis_fn(desc_elem, None),
+            ],
+        ];
+        for [p_basis, q_basis] in pairs.iter() {
+            assert!(p_basis.has_separable_jacobian() && q_basis.has_separable_jacobian());
+        }
+
+        (weights, pairs)
+    }
+
+    // Every pair of u/v directions and expansion orders
+    fn all_cases() -> Vec<Case> {
+        let orders: Vec<[usize; 2]> = (0..I_MAX)
+            .flat_map(|i| (0..J_MAX).map(move |j| [i, j]))
+            .collect();
+
+        let mut cases = Vec::new();
+        for p_dir in [BasisDir::U, BasisDir::V] {
+            for q_dir in [BasisDir::U, BasisDir::V] {
+                for &p_orders in orders.iter() {
+                    for &q_orders in orders.iter() {
+                        cases.push((p_dir, q_dir, p_orders, q_orders));
+                    }
+                }
+            }
+        }
+        cases
+    }
+
+    fn assert_matches_full_quad(case: Case, separable: f64, full: f64) {
+        assert!(
+            (separable - full).abs() < 1e-10 * full.abs().max(1.0),
+            "{:?}: {} != {}",
+            case,
+            separable,
+            full
+        );
+    }
+
+    fn check_curl_curl<BSpace: HierCurlBasisFnSpace>() {
+        let ([u_weights, v_weights], pairs) = separable_basis_pairs::<BSpace>();
+        let integral = CurlCurl::with_weights(&u_weights, &v_weights);
+
+        for [p_basis, q_basis] in pairs.iter() {
+            for case @ (p_dir, q_dir, p_orders, q_orders) in all_cases() {
+                assert_matches_full_quad(
+                    case,
+                    integral.separable_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis),
+                    integral.full_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis),
+                );
+            }
+        }
+    }
+
+    fn check_inner<BSpace: HierCurlBasisFnSpace>() {
+        let ([u_weights, v_weights], pairs) = separable_basis_pairs::<BSpace>();
+        let integral = L2Inner::with_weights(&u_weights, &v_weights);
+
+        for [p_basis, q_basis] in pairs.iter() {
+            for case @ (p_dir, q_dir, p_orders, q_orders) in all_cases() {
+                assert_matches_full_quad(
+                    case,
+                    integral.separable_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis),
+                    integral.full_quad(p_dir, q_dir, p_orders, q_orders, p_basis, q_basis),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn separable_curl_curl() {
+        check_curl_curl::<HierPoly>();
+        check_curl_curl::<HierLobatto>();
+    }
+
+    #[test]
+    fn separable_inner() {
+        check_inner::<HierPoly>();
+        check_inner::<HierLobatto>();
+    }
+}
@@ -16,6 +16,8 @@ pub mod nalgebra_solve;
 pub mod slepc_solve;
 /// Sparsely Packed Matrix
 pub mod sparse_matrix;
+/// A sparse direct solver for large, symmetric positive-definite linear systems
+pub mod sparse_solve;
 
 use nalgebra::DMatrix;
 use rayon::prelude::*;
@@ -81,6 +83,7 @@ impl ParallelExtend<[SparseMatrix; 2]> for GEP {
 }
 
 /// Solution to an Eigenvalue Problem
+#[derive(Clone, Debug)]
 pub struct EigenPair {
     /// Eigenvalue
     pub value: f64,
@@ -94,4 +97,150 @@ impl EigenPair {
         let norm = self.vector.iter().map(|x| x.powi(2)).sum::<f64>().sqrt();
         self.vector.iter().map(|x| x / norm).collect()
     }
+
+    /// The norm of the eigenvector with respect to the `b` (mass) matrix: `sqrt(uᵀ B u)`
+    pub fn b_norm(&self, b: &SparseMatrix) -> f64 {
+        b.bilinear_form(&self.vector, &self.vector).sqrt()
+    }
+
+    /// Eigenvector normalized such that `uᵀ B u = 1`, with a canonical sign
+    ///
+    /// Because the eigenvectors are real, the only "phase" ambiguity is the sign. It is fixed such that the entry with the largest magnitude is positive (the first such entry in the case of a tie).
+    pub fn b_normalized_eigenvector(&self, b: &SparseMatrix) -> Vec<f64> {
+        let norm = self.b_norm(b);
+        let sign = canonical_sign(&self.vector);
+        self.vector.iter().map(|x| sign * x / norm).collect()
+    }
+
+    /// Normalize the eigenvector in place such that `uᵀ B u = 1` (see [EigenPair::b_normalized_eigenvector])
+    pub fn b_normalize(&mut self, b: &SparseMatrix) {
+        self.vector = self.b_normalized_eigenvector(b);
+    }
+
+    /// The `b`-weighted overlap between two solutions on the same Domain: `uᵀ B v / (‖u‖_B ‖v‖_B)`
+    ///
+    /// The result is in `[-1, 1]` and is insensitive to the scaling of either eigenvector
+    pub fn b_overlap(&self, other: &Self, b: &SparseMatrix) -> f64 {
+        b.bilinear_form(&self.vector, &other.vector) / (self.b_norm(b) * other.b_norm(b))
+    }
+
+    /// The Modal Assurance Criterion between two solutions on the same Domain: `(uᵀ B v)² / ((uᵀ B u)(vᵀ B v))`
+    ///
+    /// The result is in `[0, 1]`, where 1 indicates that the two eigenvectors describe the same mode (regardless of sign or scaling)
+    pub fn modal_assurance_criterion(&self, other: &Self, b: &SparseMatrix) -> f64 {
+        self.b_overlap(other, b).powi(2)
+    }
+}
+
+/// Compute the Modal Assurance Criterion between every pair of modes in two sets of solutions on the same Domain
+///
+/// Entry `[i][j]` of the result is the MAC between `modes_a[i]` and `modes_b[j]`
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::unit();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+/// let b = gep.b.clone();
+///
+/// let mode_a = nalgebra_solve_gep(gep.clone(), 9.87).unwrap();
+/// let mode_b = nalgebra_solve_gep(gep, 19.7).unwrap();
+///
+/// let mac = mac_matrix(&[mode_a.clone(), mode_b.clone()], &[mode_b, mode_a], &b);
+///
+/// assert!(mac[0][1] > 1.0 - 1e-10);
+/// assert!(mac[0][0] < 1e-10);
+/// ```
+pub fn mac_matrix(modes_a: &[EigenPair], modes_b: &[EigenPair], b: &SparseMatrix) -> Vec<Vec<f64>> {
+    modes_a
+        .par_iter()
+        .map(|mode_a| {
+            modes_b
+                .iter()
+                .map(|mode_b| mode_a.modal_assurance_criterion(mode_b, b))
+                .collect()
+        })
+        .collect()
+}
+
+// the sign which makes the largest magnitude entry of a vector positive
+fn canonical_sign(vector: &[f64]) -> f64 {
+    let mut max_entry = 0.0_f64;
+    for x in vector {
+        if x.abs() > max_entry.abs() {
+            max_entry = *x;
+        }
+    }
+
+    if max_entry < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_domain::domain::{mesh::Mesh, ContinuityCondition, Domain};
+    use crate::fem_problem::galerkin::galerkin_sample_gep_hcurl;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use nalgebra_solve::nalgebra_solve_gep;
+
+    fn mesh_a_gep() -> GEP {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap()
+    }
+
+    #[test]
+    fn b_normalization() {
+        let gep = mesh_a_gep();
+        let b = gep.b.clone();
+        let mut eigen_pair = nalgebra_solve_gep(gep, 3.62).unwrap();
+
+        // scaling and flipping the eigenvector does not change the normalized result
+        let normalized = eigen_pair.b_normalized_eigenvector(&b);
+        eigen_pair.vector.iter_mut().for_each(|x| *x *= -3.5);
+        eigen_pair.b_normalize(&b);
+
+        assert!((eigen_pair.b_norm(&b) - 1.0).abs() < 1e-12);
+        for (x, y) in normalized.iter().zip(eigen_pair.vector.iter()) {
+            assert!((x - y).abs() < 1e-12);
+        }
+
+        let max_entry = normalized
+            .iter()
+            .cloned()
+            .max_by(|x, y| x.abs().partial_cmp(&y.abs()).unwrap())
+            .unwrap();
+        assert!(max_entry > 0.0);
+    }
+
+    #[test]
+    fn modal_assurance_criterion() {
+        let gep = mesh_a_gep();
+        let b = gep.b.clone();
+        let mode_a = nalgebra_solve_gep(gep.clone(), 3.62).unwrap();
+        let mode_b = nalgebra_solve_gep(gep, 12.0).unwrap();
+        assert!((mode_a.value - mode_b.value).abs() > 1e-3);
+
+        let flipped = EigenPair {
+            value: mode_a.value,
+            vector: mode_a.vector.iter().map(|x| -2.0 * x).collect(),
+        };
+
+        assert!((mode_a.b_overlap(&flipped, &b) + 1.0).abs() < 1e-12);
+        assert!((mode_a.modal_assurance_criterion(&flipped, &b) - 1.0).abs() < 1e-12);
+
+        // distinct modes are B-orthogonal
+        assert!(mode_a.modal_assurance_criterion(&mode_b, &b) < 1e-10);
+    }
 }
@@ -1,59 +1,143 @@
-use super::{EigenPair, GEP};
-use nalgebra::SymmetricEigen;
+use super::{sparse_matrix::ComplexSparseMatrix, EigenPair, GEP};
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
+use num_complex::Complex64;
 use std::fmt;
 
 // TODO: use Nalgebra's Sparse crate
-const MAX_DENSE_SIZE: usize = 1000;
+/// Maximum dimension of the systems solved by the dense Nalgebra solvers (see [sparse_solve_spd](super::sparse_solve::sparse_solve_spd) for larger positive-definite systems)
+pub const MAX_DENSE_SIZE: usize = 1000;
 
 /// This function is only recommended in scenarios where the problem size is small and the B-matrix is known to be very well conditioned
-///
-/// This function directly inverts the B-matrix using Nalgebra's Cholesky Decomposition which does not work well when B is ill-conditioned.
+/// This function reduces the problem to a standard symmetric eigenproblem by inverting the Cholesky factor of the B-matrix (using Nalgebra's Cholesky Decomposition) which does not work well when B is ill-conditioned.
 /// It also casts the sparse-matrices as dense matrix objects which uses a very large amount of memory when the matrices are large.
 ///
 /// For larger or more difficult problems the SLEPC Solver is recommended.
 pub fn nalgebra_solve_gep(gep: GEP, target_eigenvalue: f64) -> Result<EigenPair, NalgebraGEPError> {
+    let mut eigen_pairs = nalgebra_solve_gep_n(gep, target_eigenvalue, 1)?;
+    Ok(eigen_pairs.remove(0))
+}
+
+/// Solve a GEP for the `num_pairs` Eigenpairs with Eigenvalues closest to the target (ordered by distance from the target)
+///
+/// This has the same limitations as [nalgebra_solve_gep]. It is useful when several candidate modes are needed at once (ex: for mode tracking in a parametric sweep).
+/// Non-finite eigenvalues are discarded, and an empty `Vec` is returned if `num_pairs` is zero.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+///
+/// let eigen_pairs = nalgebra_solve_gep_n(gep.clone(), 3.62, 3).unwrap();
+///
+/// assert_eq!(eigen_pairs.len(), 3);
+/// assert!((eigen_pairs[0].value - 3.62).abs() <= (eigen_pairs[1].value - 3.62).abs());
+/// assert!(nalgebra_solve_gep_n(gep, 3.62, 0).unwrap().is_empty());
+/// ```
+pub fn nalgebra_solve_gep_n(
+    gep: GEP,
+    target_eigenvalue: f64,
+    num_pairs: usize,
+) -> Result<Vec<EigenPair>, NalgebraGEPError> {
     if gep.a.dimension > MAX_DENSE_SIZE {
         return Err(NalgebraGEPError::ProblemTooLarge);
     }
+    if num_pairs == 0 {
+        return Ok(Vec::new());
+    }
     let [a_mat, b_mat] = gep.to_nalgebra_dense_mats();
     if let Some(cholesky_decomp) = b_mat.cholesky() {
-        let b_inverse = cholesky_decomp.inverse();
-        let ba_product = b_inverse * a_mat;
-        let ba_se_decomp = SymmetricEigen::new(ba_product);
+        // reduce to a standard symmetric eigenproblem: (L⁻¹ A L⁻ᵀ) y = λ y, where x = L⁻ᵀ y
+        let l_inverse = match cholesky_decomp.l().try_inverse() {
+            Some(l_inverse) => l_inverse,
+            None => return Err(NalgebraGEPError::FailedToInvertB),
+        };
+        let reduced = &l_inverse * a_mat * l_inverse.transpose();
+        let ba_se_decomp = SymmetricEigen::new(reduced);
 
         if ba_se_decomp.eigenvalues.iter().all(|e| e.abs() < 1e-12) {
             return Err(NalgebraGEPError::SpuriouslyConverged);
         }
 
-        let mut delta_min = f64::MAX;
-        let mut delta_min_idx = 0;
-        for (eval_idx, eval) in ba_se_decomp.eigenvalues.iter().enumerate() {
-            let delta = (eval - target_eigenvalue).abs();
-            if delta < delta_min {
-                delta_min_idx = eval_idx;
-                delta_min = delta;
-            }
+        // order the finite eigenvalues by their distance from the target (ties retain their original order)
+        let mut eval_indices: Vec<usize> = (0..ba_se_decomp.eigenvalues.len())
+            .filter(|eval_idx| ba_se_decomp.eigenvalues[*eval_idx].is_finite())
+            .collect();
+        if eval_indices.is_empty() {
+            return Err(NalgebraGEPError::NonFiniteEigenvalues);
         }
+        eval_indices.sort_by(|a, b| {
+            let delta_a = (ba_se_decomp.eigenvalues[*a] - target_eigenvalue).abs();
+            let delta_b = (ba_se_decomp.eigenvalues[*b] - target_eigenvalue).abs();
+            delta_a.total_cmp(&delta_b)
+        });
 
-        Ok(EigenPair {
-            value: *ba_se_decomp.eigenvalues.get(delta_min_idx).unwrap(),
-            vector: ba_se_decomp
-                .eigenvectors
-                .column(delta_min_idx)
-                .iter()
-                .cloned()
-                .collect(),
-        })
+        let l_inverse_t = l_inverse.transpose();
+        Ok(eval_indices
+            .into_iter()
+            .take(num_pairs)
+            .map(|eval_idx| EigenPair {
+                value: ba_se_decomp.eigenvalues[eval_idx],
+                vector: (&l_inverse_t * ba_se_decomp.eigenvectors.column(eval_idx))
+                    .iter()
+                    .cloned()
+                    .collect(),
+            })
+            .collect())
     } else {
         Err(NalgebraGEPError::FailedToInvertB)
     }
 }
 
+/// Solve the (complex-symmetric) linear system: `Ax = b` using Nalgebra's LU Decomposition
+///
+/// This function casts the sparse-matrix as a dense matrix object, and is only recommended when the problem size is small.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut a = ComplexSparseMatrix::new(2);
+/// a.insert([0, 0], Complex64::new(2.0, 0.0));
+/// a.insert([0, 1], Complex64::new(0.0, 1.0));
+/// a.insert([1, 1], Complex64::new(1.0, 0.0));
+///
+/// let x = nalgebra_solve_linear(a, &[Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)]).unwrap();
+///
+/// // check the first row: 2 * x_0 + i * x_1 = 1
+/// let row_0 = x[0] * 2.0 + x[1] * Complex64::new(0.0, 1.0);
+/// assert!((row_0 - Complex64::new(1.0, 0.0)).norm() < 1e-12);
+/// ```
+pub fn nalgebra_solve_linear(
+    a: ComplexSparseMatrix,
+    b: &[Complex64],
+) -> Result<Vec<Complex64>, NalgebraLinearError> {
+    if a.dimension() > MAX_DENSE_SIZE {
+        return Err(NalgebraLinearError::ProblemTooLarge);
+    }
+    if a.dimension() != b.len() {
+        return Err(NalgebraLinearError::DimensionMismatch(
+            a.dimension(),
+            b.len(),
+        ));
+    }
+
+    let a_mat: DMatrix<Complex64> = a.into();
+    match a_mat.lu().solve(&DVector::from_column_slice(b)) {
+        Some(x) => Ok(x.iter().cloned().collect()),
+        None => Err(NalgebraLinearError::SingularMatrix),
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Error type for the SlepcGEP solver
 pub enum NalgebraGEPError {
     FailedToInvertB,
     SpuriouslyConverged,
+    NonFiniteEigenvalues,
     ProblemTooLarge,
 }
 
@@ -67,6 +151,38 @@ impl std::fmt::Display for NalgebraGEPError {
                 "Failed to invert B-matrix (via cholesky); likely ill-conditioned!"
             ),
             Self::SpuriouslyConverged => write!(f, "Only spurious modes were found!"),
+            Self::NonFiniteEigenvalues => write!(
+                f,
+                "All Eigenvalues are non-finite; A or B is likely ill-conditioned!"
+            ),
+            Self::ProblemTooLarge => write!(
+                f,
+                "Matrices Exceeded Maximum Size ({}x{}); Cannot Solve!",
+                MAX_DENSE_SIZE, MAX_DENSE_SIZE
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Error type for the Nalgebra linear solver
+pub enum NalgebraLinearError {
+    SingularMatrix,
+    DimensionMismatch(usize, usize),
+    ProblemTooLarge,
+}
+
+impl std::error::Error for NalgebraLinearError {}
+
+impl std::fmt::Display for NalgebraLinearError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SingularMatrix => write!(f, "Failed to solve linear system; matrix is singular!"),
+            Self::DimensionMismatch(mat_dim, rhs_dim) => write!(
+                f,
+                "Matrix dimension ({}) does not match right-hand-side length ({}); Cannot Solve!",
+                mat_dim, rhs_dim
+            ),
             Self::ProblemTooLarge => write!(
                 f,
                 "Matrices Exceeded Maximum Size ({}x{}); Cannot Solve!",
@@ -4,6 +4,9 @@ use std::io::{BufWriter, Write};
 
 use bytes::{BufMut, BytesMut};
 use nalgebra::DMatrix;
+use num_complex::Complex64;
+use rayon::prelude::*;
+use std::sync::mpsc::channel;
 
 //TODO: switch to something more efficient than a BTreeMap (preallocate with know num zeros)
 
@@ -126,6 +129,41 @@ impl SparseMatrix {
             .map(|(coords, value)| ([coords[0] as usize, coords[1] as usize], *value))
     }
 
+    /// Evaluate the bilinear form: `xᵀ M y` (using the symmetry of the matrix)
+    pub fn bilinear_form(&self, x: &[f64], y: &[f64]) -> f64 {
+        assert!(
+            x.len() == self.dimension && y.len() == self.dimension,
+            "Vector lengths do not match the matrix dimension; cannot evaluate bilinear form!"
+        );
+
+        self.iter_upper_tri()
+            .map(|([r, c], value)| {
+                if r == c {
+                    value * x[r] * y[c]
+                } else {
+                    value * (x[r] * y[c] + x[c] * y[r])
+                }
+            })
+            .sum()
+    }
+
+    /// Compute the matrix-vector product: `M x` (using the symmetry of the matrix)
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        assert!(
+            x.len() == self.dimension,
+            "Vector length does not match the matrix dimension; cannot compute product!"
+        );
+
+        let mut product = vec![0.0; self.dimension];
+        for ([r, c], value) in self.iter_upper_tri() {
+            product[r] += value * x[c];
+            if r != c {
+                product[c] += value * x[r];
+            }
+        }
+        product
+    }
+
     pub fn write_to_petsc_binary_format(&self, path: impl AsRef<str>) -> std::io::Result<()> {
         let file = File::create(path.as_ref())?;
         let mut writer = BufWriter::new(file);
@@ -181,6 +219,151 @@ impl From<SparseMatrix> for DMatrix<f64> {
     }
 }
 
+impl ParallelExtend<SparseMatrix> for SparseMatrix {
+    fn par_extend<I>(&mut self, elem_matrices_iter: I)
+    where
+        I: IntoParallelIterator<Item = SparseMatrix>,
+    {
+        let (sender, receiver) = channel();
+
+        elem_matrices_iter
+            .into_par_iter()
+            .for_each_with(sender, |s, elem_matrix| {
+                s.send(elem_matrix).expect(
+                    "Failed to send sub-matrices over MSPC channel; cannot construct Matrix!",
+                )
+            });
+
+        receiver
+            .iter()
+            .for_each(|mut elem_matrix| self.consume_matrix(&mut elem_matrix));
+    }
+}
+
+/// A pair of [SparseMatrix]s storing the Real and Imaginary parts of a complex-symmetric matrix
+#[derive(Clone)]
+pub struct ComplexSparseMatrix {
+    /// Real Part
+    pub re: SparseMatrix,
+    /// Imaginary Part
+    pub im: SparseMatrix,
+}
+
+impl ComplexSparseMatrix {
+    pub fn new(dimension: usize) -> Self {
+        Self {
+            re: SparseMatrix::new(dimension),
+            im: SparseMatrix::new(dimension),
+        }
+    }
+
+    /// Size of the square matrix
+    pub fn dimension(&self) -> usize {
+        self.re.dimension
+    }
+
+    /// Insert a value into the matrix. Assumes symmetry: row/col order does not matter.
+    pub fn insert(&mut self, rc: [usize; 2], value: Complex64) {
+        self.re.insert(rc, value.re);
+        self.im.insert(rc, value.im);
+    }
+
+    /// Insert a group of entries
+    pub fn insert_group(&mut self, entry_group: Vec<([usize; 2], Complex64)>) {
+        let (re_entries, im_entries) = entry_group
+            .into_iter()
+            .map(|(rc, v)| ((rc, v.re), (rc, v.im)))
+            .unzip();
+
+        self.re.insert_group(re_entries);
+        self.im.insert_group(im_entries);
+    }
+
+    /// Add the entries of another complex sparse matrix (multiplied by `scale`) into this matrix
+    pub fn add_scaled(&mut self, other: &Self, scale: Complex64) {
+        assert!(
+            self.dimension() == other.dimension(),
+            "Sparse Matrices have different dimensions; cannot add matrix!"
+        );
+
+        self.insert_group(
+            other
+                .iter_upper_tri()
+                .map(|(rc, value)| (rc, value * scale))
+                .collect(),
+        );
+    }
+
+    /// Consume the entries from another complex sparse matrix leaving it empty.
+    pub fn consume_matrix(&mut self, other: &mut Self) {
+        self.re.consume_matrix(&mut other.re);
+        self.im.consume_matrix(&mut other.im);
+    }
+
+    /// Iterate over the upper triangle of the matrix.
+    pub fn iter_upper_tri(&self) -> impl Iterator<Item = ([usize; 2], Complex64)> {
+        let mut entries: BTreeMap<[usize; 2], Complex64> = self
+            .re
+            .iter_upper_tri()
+            .map(|(rc, v)| (rc, Complex64::from(v)))
+            .collect();
+
+        for (rc, v) in self.im.iter_upper_tri() {
+            entries
+                .entry(rc)
+                .and_modify(|curr_val| curr_val.im += v)
+                .or_insert(Complex64::new(0.0, v));
+        }
+
+        entries.into_iter()
+    }
+}
+
+impl From<SparseMatrix> for ComplexSparseMatrix {
+    fn from(sm: SparseMatrix) -> Self {
+        let dimension = sm.dimension;
+        Self {
+            re: sm,
+            im: SparseMatrix::new(dimension),
+        }
+    }
+}
+
+impl From<ComplexSparseMatrix> for DMatrix<Complex64> {
+    fn from(csm: ComplexSparseMatrix) -> Self {
+        let mut dense =
+            DMatrix::from_element(csm.dimension(), csm.dimension(), Complex64::from(0.0));
+
+        for ([r, c], v) in csm.iter_upper_tri() {
+            dense[(r, c)] = v;
+            dense[(c, r)] = v;
+        }
+
+        dense
+    }
+}
+
+impl ParallelExtend<ComplexSparseMatrix> for ComplexSparseMatrix {
+    fn par_extend<I>(&mut self, elem_matrices_iter: I)
+    where
+        I: IntoParallelIterator<Item = ComplexSparseMatrix>,
+    {
+        let (sender, receiver) = channel();
+
+        elem_matrices_iter
+            .into_par_iter()
+            .for_each_with(sender, |s, elem_matrix| {
+                s.send(elem_matrix).expect(
+                    "Failed to send sub-matrices over MSPC channel; cannot construct Matrix!",
+                )
+            });
+
+        receiver
+            .iter()
+            .for_each(|mut elem_matrix| self.consume_matrix(&mut elem_matrix));
+    }
+}
+
 impl From<SparseMatrix> for AIJMatrixBinary {
     fn from(mut sm: SparseMatrix) -> Self {
         // number of entries in each row
@@ -347,6 +530,58 @@ mod tests {
         assert!(sm_a_entries.get(&[3, 1]).is_none());
     }
 
+    #[test]
+    fn complex_matrix_entries() {
+        let mut csm = ComplexSparseMatrix::new(4);
+
+        csm.insert([0, 0], Complex64::new(1.0, 0.0));
+        csm.insert([2, 1], Complex64::new(0.0, -2.0));
+        csm.insert_group(vec![
+            ([1, 2], Complex64::new(3.0, 0.5)),
+            ([3, 3], Complex64::new(0.0, 1.0)),
+        ]);
+
+        let entries: Vec<([usize; 2], Complex64)> = csm.iter_upper_tri().collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0], ([0, 0], Complex64::new(1.0, 0.0)));
+        assert_eq!(entries[1], ([1, 2], Complex64::new(3.0, -1.5)));
+        assert_eq!(entries[2], ([3, 3], Complex64::new(0.0, 1.0)));
+
+        let mut csm_sum = csm.clone();
+        csm_sum.add_scaled(&csm, Complex64::new(0.0, 1.0));
+        assert_eq!(
+            csm_sum.iter_upper_tri().nth(1).unwrap().1,
+            Complex64::new(4.5, 1.5)
+        );
+
+        let dense: DMatrix<Complex64> = csm.into();
+        assert_eq!(dense[(2, 1)], Complex64::new(3.0, -1.5));
+        assert_eq!(dense[(1, 2)], Complex64::new(3.0, -1.5));
+        assert_eq!(dense[(0, 3)], Complex64::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn bilinear_form() {
+        let mut sm = SparseMatrix::new(3);
+        sm.insert([0, 0], 2.0);
+        sm.insert([2, 1], 0.5);
+        sm.insert([1, 1], 1.0);
+
+        let x = [1.0, 2.0, 3.0];
+        let y = [-1.0, 1.0, 4.0];
+
+        // 2(1)(-1) + 1(2)(1) + 0.5(2)(4) + 0.5(3)(1)
+        assert!((sm.bilinear_form(&x, &y) - 5.5).abs() < 1e-15);
+        assert!((sm.bilinear_form(&x, &y) - sm.bilinear_form(&y, &x)).abs() < 1e-15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bilinear_form_of_different_dim() {
+        let sm = SparseMatrix::new(3);
+        let _ = sm.bilinear_form(&[1.0; 3], &[1.0; 4]);
+    }
+
     #[test]
     #[should_panic]
     fn consume_matrix_of_different_dim() {
@@ -0,0 +1,277 @@
+use super::sparse_matrix::SparseMatrix;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Solve a sparse, symmetric positive-definite linear system `A x = b` with an `L D Lᵀ` (Cholesky) factorization
+///
+/// The rows and columns are first reordered with the Reverse Cuthill-McKee algorithm to reduce their bandwidth, and the factorization is computed
+/// over the envelope of the reordered matrix. As such, memory usage scales with the size of the envelope rather than the square of the dimension,
+/// and there is no limit on the size of the system (unlike [nalgebra_solve_linear](super::nalgebra_solve::nalgebra_solve_linear)).
+///
+/// The factorization does not pivot, so it is only suitable for positive-definite matrices (ex: Gram matrices).
+/// A `SparseSolveError::NotPositiveDefinite` is returned if a non-positive pivot is encountered.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut a = SparseMatrix::new(3);
+/// a.insert([0, 0], 4.0);
+/// a.insert([0, 2], 1.0);
+/// a.insert([1, 1], 3.0);
+/// a.insert([2, 2], 2.0);
+///
+/// let x = sparse_solve_spd(&a, &[1.0, 2.0, 3.0]).unwrap();
+///
+/// // check the first row: 4 * x_0 + x_2 = 1
+/// assert!((4.0 * x[0] + x[2] - 1.0).abs() < 1e-12);
+/// ```
+pub fn sparse_solve_spd(a: &SparseMatrix, b: &[f64]) -> Result<Vec<f64>, SparseSolveError> {
+    if a.dimension != b.len() {
+        return Err(SparseSolveError::DimensionMismatch(a.dimension, b.len()));
+    }
+
+    let n = a.dimension;
+    let order = reverse_cuthill_mckee(a);
+    let mut position = vec![0; n];
+    for (new_idx, old_idx) in order.iter().enumerate() {
+        position[*old_idx] = new_idx;
+    }
+
+    // the first column of each row's envelope (in the reordered matrix)
+    let mut first = (0..n).collect::<Vec<usize>>();
+    for ([r, c], _) in a.iter_upper_tri() {
+        let [r, c] = [position[r], position[c]];
+        let [row, col] = [r.max(c), r.min(c)];
+        first[row] = first[row].min(col);
+    }
+
+    // store the lower triangle of each row within the envelope: row i covers columns first[i]..=i
+    let mut row_starts = Vec::with_capacity(n + 1);
+    row_starts.push(0);
+    for (i, first_col) in first.iter().enumerate() {
+        row_starts.push(row_starts[i] + i - first_col + 1);
+    }
+    let mut envelope = vec![0.0; row_starts[n]];
+    for ([r, c], value) in a.iter_upper_tri() {
+        let [r, c] = [position[r], position[c]];
+        let [row, col] = [r.max(c), r.min(c)];
+        envelope[row_starts[row] + col - first[row]] = value;
+    }
+
+    // factor: each row is replaced by [L_i0, L_i1, ..., D_i]
+    let mut diag = vec![0.0; n];
+    for i in 0..n {
+        let (prev_rows, row_i) = envelope.split_at_mut(row_starts[i]);
+        let row_i = &mut row_i[..=i - first[i]];
+
+        // g_ij = L_ij * D_j
+        for j in first[i]..i {
+            let k0 = first[i].max(first[j]);
+            let row_j = &prev_rows[row_starts[j]..row_starts[j + 1]];
+            let sum: f64 = (k0..j)
+                .map(|k| row_i[k - first[i]] * row_j[k - first[j]])
+                .sum();
+            row_i[j - first[i]] -= sum;
+        }
+
+        let mut d_i = row_i[i - first[i]];
+        for j in first[i]..i {
+            let g_ij = row_i[j - first[i]];
+            row_i[j - first[i]] = g_ij / diag[j];
+            d_i -= g_ij * row_i[j - first[i]];
+        }
+
+        // the pivots of a positive-definite matrix are all positive
+        if d_i <= 0.0 || !d_i.is_finite() {
+            return Err(SparseSolveError::NotPositiveDefinite(order[i]));
+        }
+        diag[i] = d_i;
+        row_i[i - first[i]] = d_i;
+    }
+
+    // forward substitution (L y = b), diagonal scaling (D z = y), and backward substitution (Lᵀ x = z)
+    let mut x: Vec<f64> = order.iter().map(|old_idx| b[*old_idx]).collect();
+    for i in 0..n {
+        let row_i = &envelope[row_starts[i]..row_starts[i + 1]];
+        let sum: f64 = (first[i]..i).map(|j| row_i[j - first[i]] * x[j]).sum();
+        x[i] -= sum;
+    }
+    x.iter_mut()
+        .zip(diag.iter())
+        .for_each(|(x_i, d_i)| *x_i /= d_i);
+    for i in (0..n).rev() {
+        let row_i = &envelope[row_starts[i]..row_starts[i + 1]];
+        for j in first[i]..i {
+            x[j] -= row_i[j - first[i]] * x[i];
+        }
+    }
+
+    let mut solution = vec![0.0; n];
+    for (new_idx, old_idx) in order.iter().enumerate() {
+        solution[*old_idx] = x[new_idx];
+    }
+    Ok(solution)
+}
+
+// Reverse Cuthill-McKee ordering of the matrix's rows. Returns the original row index at each new position
+fn reverse_cuthill_mckee(a: &SparseMatrix) -> Vec<usize> {
+    let n = a.dimension;
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for ([r, c], value) in a.iter_upper_tri() {
+        if r != c && value != 0.0 {
+            neighbors[r].push(c);
+            neighbors[c].push(r);
+        }
+    }
+    for adjacent in neighbors.iter_mut() {
+        adjacent.sort_unstable();
+        adjacent.dedup();
+    }
+
+    let mut by_degree: Vec<usize> = (0..n).collect();
+    by_degree.sort_by_key(|idx| neighbors[*idx].len());
+
+    let mut order = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    let mut queue = VecDeque::new();
+
+    // start each connected component from one of its lowest degree nodes
+    for start in by_degree {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        queue.push_back(start);
+
+        while let Some(idx) = queue.pop_front() {
+            order.push(idx);
+
+            let mut unvisited: Vec<usize> = neighbors[idx]
+                .iter()
+                .copied()
+                .filter(|adj| !visited[*adj])
+                .collect();
+            unvisited.sort_by_key(|adj| neighbors[*adj].len());
+            for adj in unvisited {
+                visited[adj] = true;
+                queue.push_back(adj);
+            }
+        }
+    }
+
+    order.reverse();
+    order
+}
+
+#[derive(Debug, Clone)]
+/// Error type for the sparse linear solver
+pub enum SparseSolveError {
+    DimensionMismatch(usize, usize),
+    NotPositiveDefinite(usize),
+}
+
+impl std::error::Error for SparseSolveError {}
+
+impl fmt::Display for SparseSolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DimensionMismatch(mat_dim, rhs_dim) => write!(
+                f,
+                "Matrix dimension ({}) does not match right-hand-side length ({}); Cannot Solve!",
+                mat_dim, rhs_dim
+            ),
+            Self::NotPositiveDefinite(row) => write!(
+                f,
+                "Encountered a non-positive pivot at row {}; Cannot factor a matrix that is not positive-definite!",
+                row
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 2D finite-difference Laplacian (shifted by `shift`) over an n by n grid, with scrambled row indices
+    fn scrambled_laplacian(n: usize, shift: f64) -> SparseMatrix {
+        let idx = |x: usize, y: usize| ((x * n + y) * 7919) % (n * n);
+        let mut a = SparseMatrix::new(n * n);
+        for x in 0..n {
+            for y in 0..n {
+                a.insert([idx(x, y), idx(x, y)], 4.0 - shift);
+                if x + 1 < n {
+                    a.insert([idx(x, y), idx(x + 1, y)], -1.0);
+                }
+                if y + 1 < n {
+                    a.insert([idx(x, y), idx(x, y + 1)], -1.0);
+                }
+            }
+        }
+        a
+    }
+
+    fn relative_residual(a: &SparseMatrix, x: &[f64], b: &[f64]) -> f64 {
+        let residual: f64 = a
+            .mul_vec(x)
+            .iter()
+            .zip(b.iter())
+            .map(|(ax, b)| (ax - b).powi(2))
+            .sum();
+        (residual / b.iter().map(|b| b.powi(2)).sum::<f64>()).sqrt()
+    }
+
+    #[test]
+    fn positive_definite_system() {
+        let a = scrambled_laplacian(40, 0.0);
+        let b: Vec<f64> = (0..a.dimension).map(|i| (i as f64 * 0.1).sin()).collect();
+
+        let x = sparse_solve_spd(&a, &b).unwrap();
+        assert!(relative_residual(&a, &x, &b) < 1e-12);
+    }
+
+    #[test]
+    fn bandwidth_reduction() {
+        let a = scrambled_laplacian(20, 0.0);
+        let order = reverse_cuthill_mckee(&a);
+        let mut position = vec![0; a.dimension];
+        for (new_idx, old_idx) in order.iter().enumerate() {
+            position[*old_idx] = new_idx;
+        }
+
+        let bandwidth = |position: &dyn Fn(usize) -> usize| {
+            a.iter_upper_tri()
+                .map(|([r, c], _)| position(r).abs_diff(position(c)))
+                .max()
+                .unwrap()
+        };
+        assert!(bandwidth(&|idx| position[idx]) <= 2 * 20);
+        assert!(bandwidth(&|idx| idx) > 10 * 20);
+    }
+
+    #[test]
+    fn solver_errors() {
+        let a = scrambled_laplacian(4, 0.0);
+        assert!(matches!(
+            sparse_solve_spd(&a, &[1.0; 5]),
+            Err(SparseSolveError::DimensionMismatch(16, 5))
+        ));
+
+        let mut singular = SparseMatrix::new(2);
+        singular.insert([0, 0], 1.0);
+        singular.insert([0, 1], 1.0);
+        singular.insert([1, 1], 1.0);
+        assert!(matches!(
+            sparse_solve_spd(&singular, &[1.0, 1.0]),
+            Err(SparseSolveError::NotPositiveDefinite(_))
+        ));
+
+        // indefinite systems are rejected rather than solved without pivoting
+        let indefinite = scrambled_laplacian(40, 1.3);
+        assert!(matches!(
+            sparse_solve_spd(&indefinite, &vec![1.0; indefinite.dimension]),
+            Err(SparseSolveError::NotPositiveDefinite(_))
+        ));
+    }
+}
@@ -0,0 +1,469 @@
+use super::galerkin::{assemble_matrix_sum, galerkin_sample_rhs_hcurl, GalerkinSamplingError};
+use super::integration::{
+    glq::gauss_quadrature_points,
+    integrals::{curl_curl::CurlCurl, inner::L2Inner},
+    IntegralSum,
+};
+use super::linalg::nalgebra_solve::{nalgebra_solve_linear, NalgebraLinearError, MAX_DENSE_SIZE};
+use super::linalg::sparse_solve::{sparse_solve_spd, SparseSolveError};
+use crate::fem_domain::basis::HierCurlBasisFnSpace;
+use crate::fem_domain::domain::{
+    fields::{sample_local_fields, LocalFields},
+    mesh::{element::Materials, h_refinement::HRef, space::Point, Mesh},
+    ContinuityCondition, Domain,
+};
+use num_complex::Complex64;
+use rayon::prelude::*;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Default step size used to differentiate a [ManufacturedSolution]
+pub const DEFAULT_DIFFERENCE_STEP: f64 = 1e-3;
+
+// 4th-order central difference stencil: (offset, weight)
+const STENCIL: [(f64, f64); 4] = [
+    (-2.0, 1.0 / 12.0),
+    (-1.0, -8.0 / 12.0),
+    (1.0, 8.0 / 12.0),
+    (2.0, -1.0 / 12.0),
+];
+
+/// An analytic vector field `E(x, y)` used to verify the assembled operators with the Method of Manufactured Solutions
+///
+/// The source: `f = ∇ × (μ⁻¹ ∇ × E) - k² ε E` is computed from the field with 4th-order central differences, such that `E` is the exact solution of the driven problem: `∇ × (μ⁻¹ ∇ × E) - k² ε E = f`.
+///
+/// The field must have a vanishing tangential component along the boundary of the Mesh (which is treated as a perfect electric conductor), and `μ⁻¹ ∇ × E` must be continuous across material interfaces.
+pub struct ManufacturedSolution<F> {
+    field: F,
+    step: f64,
+}
+
+/// The errors of a solution computed with a [ManufacturedSolution]'s source
+#[derive(Clone, Copy, Debug)]
+pub struct ManufacturedErrors {
+    /// Number of Degrees of Freedom in the Domain
+    pub num_dofs: usize,
+    /// `‖E - E_h‖`
+    pub l2_error: f64,
+    /// `sqrt(‖E - E_h‖² + ‖∇ × (E - E_h)‖²)`
+    pub hcurl_error: f64,
+    /// `‖E‖`
+    pub l2_norm: f64,
+    /// `sqrt(‖E‖² + ‖∇ × E‖²)`
+    pub hcurl_norm: f64,
+}
+
+impl ManufacturedErrors {
+    /// L2 error relative to the norm of the exact field
+    pub fn relative_l2_error(&self) -> f64 {
+        self.l2_error / self.l2_norm
+    }
+
+    /// H(curl) error relative to the norm of the exact field
+    pub fn relative_hcurl_error(&self) -> f64 {
+        self.hcurl_error / self.hcurl_norm
+    }
+}
+
+impl<F> ManufacturedSolution<F>
+where
+    F: Fn(f64, f64) -> [f64; 2] + Sync,
+{
+    /// Create a manufactured solution from a function of the Real Space coordinates `(x, y)` which returns the `[x, y]` components of the field
+    pub fn new(field: F) -> Self {
+        Self {
+            field,
+            step: DEFAULT_DIFFERENCE_STEP,
+        }
+    }
+
+    /// Set the step size used for finite differences (it should be small relative to the scale over which the field varies)
+    pub fn with_step(mut self, step: f64) -> Self {
+        assert!(step > 0.0, "Finite difference step must be positive!");
+        self.step = step;
+        self
+    }
+
+    /// The field at a point
+    pub fn field(&self, x: f64, y: f64) -> [f64; 2] {
+        (self.field)(x, y)
+    }
+
+    /// The (z-directed) curl of the field at a point: `∂Ey/∂x - ∂Ex/∂y`
+    pub fn curl(&self, x: f64, y: f64) -> f64 {
+        self.derivative(|x, y| self.field(x, y)[1], [x, y], 0)
+            - self.derivative(|x, y| self.field(x, y)[0], [x, y], 1)
+    }
+
+    /// The consistent source at a point: `f = ∇ × (μ⁻¹ ∇ × E) - k² ε E` (only the real parts of the material parameters are used)
+    pub fn source(&self, x: f64, y: f64, materials: &Materials, wavenumber_sq: f64) -> [f64; 2] {
+        let [eps, mu] = [materials.eps_rel.re, materials.mu_rel.re];
+        let curl = |x: f64, y: f64| self.curl(x, y);
+        let field = self.field(x, y);
+
+        // ∇ × (c ẑ) = (∂c/∂y, -∂c/∂x)
+        [
+            self.derivative(curl, [x, y], 1) / mu - wavenumber_sq * eps * field[0],
+            -self.derivative(curl, [x, y], 0) / mu - wavenumber_sq * eps * field[1],
+        ]
+    }
+
+    /// Solve the driven problem: `∇ × (μ⁻¹ ∇ × E_h) - k² ε E_h = f` over a Domain, and compute the error with respect to the manufactured field
+    ///
+    /// The system matrix is assembled from [CurlCurl] and [L2Inner] integrals, and the load vector is sampled with [galerkin_sample_rhs_hcurl].
+    /// `k²` must not be an eigenvalue of the Domain (negative values always produce a positive-definite system).
+    ///
+    /// For `k² < 0`, the system is positive-definite and is solved in its sparse form with [sparse_solve_spd], so there is no limit on the size of the Domain.
+    /// Otherwise, the system is indefinite (or singular), and is solved with the pivoting dense solver ([nalgebra_solve_linear]), which is limited to [MAX_DENSE_SIZE] DoFs.
+    ///
+    /// # Returns
+    /// * The solution (indexed by DoF ID) and its errors
+    /// * An `Err` if Galerkin Sampling or the linear solve fails, or if `k² >= 0` and the Domain has more than [MAX_DENSE_SIZE] DoFs
+    /// * An `Err` if a quadrature point does not fall within a leaf-`Elem` of the Mesh (such that its materials are unknown)
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let mut mesh = RectangularCavity::homogeneous(1.0, 1.0, Materials::default()).mesh();
+    /// mesh.set_global_expansion_orders([4, 4]).unwrap();
+    /// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    ///
+    /// // the tangential field vanishes along the boundary of the unit square
+    /// let mms = ManufacturedSolution::new(|x, y| [(PI * y).sin() * x.exp(), (PI * x).sin() * y.cos()]);
+    ///
+    /// let (solution, errors) = mms.solve::<HierPoly>(&domain, -1.0, None).unwrap();
+    /// assert_eq!(solution.len(), domain.dofs.len());
+    /// assert!(errors.relative_hcurl_error() < 1e-2);
+    /// ```
+    pub fn solve<BSpace: HierCurlBasisFnSpace>(
+        &self,
+        domain: &Domain,
+        wavenumber_sq: f64,
+        glq_grid_dim: Option<[usize; 2]>,
+    ) -> Result<(Vec<f64>, ManufacturedErrors), ManufacturedError> {
+        if wavenumber_sq >= 0.0 && domain.dofs.len() > MAX_DENSE_SIZE {
+            return Err(ManufacturedError::IndefiniteSystemTooLarge(
+                domain.dofs.len(),
+            ));
+        }
+
+        let sum = IntegralSum::<BSpace>::new()
+            .with::<CurlCurl>(1.0)
+            .with::<L2Inner>(-wavenumber_sq);
+        let a = assemble_matrix_sum(domain, &sum, glq_grid_dim)?;
+
+        // the first point (if any) which could not be located in the Mesh
+        let unlocated = OnceLock::new();
+        let rhs = galerkin_sample_rhs_hcurl::<BSpace, _>(
+            domain,
+            |x, y| match domain.mesh.leaf_elem_containing(&Point::new(x, y)) {
+                Some(elem_id) => self.source(
+                    x,
+                    y,
+                    domain.mesh.elems[elem_id].get_materials(),
+                    wavenumber_sq,
+                ),
+                None => {
+                    let _ = unlocated.set([x, y]);
+                    [0.0; 2]
+                }
+            },
+            glq_grid_dim,
+        )?;
+        if let Some(point) = unlocated.into_inner() {
+            return Err(ManufacturedError::PointOutsideMesh(point));
+        }
+
+        let solution = if wavenumber_sq < 0.0 {
+            sparse_solve_spd(&a.re, &rhs)?
+        } else {
+            let b: Vec<Complex64> = rhs.iter().map(|value| Complex64::from(*value)).collect();
+            nalgebra_solve_linear(a, &b)?.iter().map(|x| x.re).collect()
+        };
+        let errors = self.errors::<BSpace>(domain, &solution);
+
+        Ok((solution, errors))
+    }
+
+    /// Compute the L2 and H(curl) errors of a solution (indexed by DoF ID) with respect to the manufactured field
+    ///
+    /// The errors are integrated over the leaf-`Elem`s of the Mesh (including the contributions from ancestor Basis Functions).
+    pub fn errors<BSpace: HierCurlBasisFnSpace>(
+        &self,
+        domain: &Domain,
+        solution: &[f64],
+    ) -> ManufacturedErrors {
+        let [i_max, j_max] = domain.mesh.max_expansion_orders();
+        let (glq_points, glq_weights) =
+            gauss_quadrature_points(i_max.max(j_max) as usize + 4, false);
+
+        let [l2_error_sq, curl_error_sq, l2_norm_sq, curl_norm_sq] = domain
+            .mesh
+            .elems
+            .par_iter()
+            .filter(|elem| !elem.has_children())
+            .map(|elem| {
+                let [p0, p1] = domain.mesh.elem_diag_points(elem.id).unwrap();
+                let xs: Vec<f64> = glq_points
+                    .iter()
+                    .map(|u| p0.x + (u + 1.0) * (p1.x - p0.x) / 2.0)
+                    .collect();
+                let ys: Vec<f64> = glq_points
+                    .iter()
+                    .map(|v| p0.y + (v + 1.0) * (p1.y - p0.y) / 2.0)
+                    .collect();
+                let area_scale = (p1.x - p0.x) * (p1.y - p0.y) / 4.0;
+
+                let LocalFields { field, curl, .. } =
+                    sample_local_fields::<BSpace>(domain, elem.id, [&xs, &ys], solution);
+
+                let mut sums = [0.0; 4];
+                for (m, w_m) in glq_weights.iter().enumerate() {
+                    for (n, w_n) in glq_weights.iter().enumerate() {
+                        let weight = w_m * w_n * area_scale;
+                        let exact = self.field(xs[m], ys[n]);
+                        let exact_curl = self.curl(xs[m], ys[n]);
+
+                        sums[0] += weight
+                            * ((exact[0] - field[m][n][0]).powi(2)
+                                + (exact[1] - field[m][n][1]).powi(2));
+                        sums[1] += weight * (exact_curl - curl[m][n]).powi(2);
+                        sums[2] += weight * (exact[0].powi(2) + exact[1].powi(2));
+                        sums[3] += weight * exact_curl.powi(2);
+                    }
+                }
+                sums
+            })
+            .reduce(
+                || [0.0; 4],
+                |a, b| [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]],
+            );
+
+        ManufacturedErrors {
+            num_dofs: domain.dofs.len(),
+            l2_error: l2_error_sq.sqrt(),
+            hcurl_error: (l2_error_sq + curl_error_sq).sqrt(),
+            l2_norm: l2_norm_sq.sqrt(),
+            hcurl_norm: (l2_norm_sq + curl_norm_sq).sqrt(),
+        }
+    }
+
+    // 4th-order central difference of a scalar function along the x (`axis = 0`) or y (`axis = 1`) direction
+    fn derivative<G: Fn(f64, f64) -> f64>(&self, g: G, [x, y]: [f64; 2], axis: usize) -> f64 {
+        STENCIL
+            .iter()
+            .map(|(offset, weight)| {
+                let delta = offset * self.step;
+                weight
+                    * match axis {
+                        0 => g(x + delta, y),
+                        _ => g(x, y + delta),
+                    }
+            })
+            .sum::<f64>()
+            / self.step
+    }
+}
+
+/// Solve a [ManufacturedSolution]'s driven problem over a sequence of uniformly h-refined Meshes, and report the errors at each refinement level
+///
+/// The initial Mesh (with its current expansion orders) is solved, followed by `num_refinements` applications of [HRef::T] to all leaf-`Elem`s.
+/// The `i`th entry of the result corresponds to `i` global h-refinements of the initial Mesh.
+/// Basis Functions on ancestor `Elem`s are retained by each refinement, so the integrals between Basis Functions on different levels of the Mesh are exercised.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+/// use std::f64::consts::PI;
+///
+/// let mut mesh = RectangularCavity::homogeneous(1.0, 1.0, Materials::default()).mesh();
+/// mesh.set_global_expansion_orders([2, 2]).unwrap();
+///
+/// let mms = ManufacturedSolution::new(|x, y| [(PI * y).sin() * x.exp(), (PI * x).sin() * y.cos()]);
+/// let levels = manufactured_h_study::<HierPoly, _>(&mesh, &mms, -1.0, 2).unwrap();
+///
+/// assert!(levels
+///     .windows(2)
+///     .all(|pair| pair[1].hcurl_error < pair[0].hcurl_error));
+/// ```
+pub fn manufactured_h_study<BSpace, F>(
+    mesh: &Mesh,
+    solution: &ManufacturedSolution<F>,
+    wavenumber_sq: f64,
+    num_refinements: usize,
+) -> Result<Vec<ManufacturedErrors>, ManufacturedError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    F: Fn(f64, f64) -> [f64; 2] + Sync,
+{
+    let mut mesh = mesh.clone();
+    let mut levels = Vec::with_capacity(num_refinements + 1);
+
+    for h_level in 0..=num_refinements {
+        if h_level > 0 {
+            mesh.global_h_refinement(HRef::T);
+        }
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let (_, errors) = solution.solve::<BSpace>(&domain, wavenumber_sq, None)?;
+        levels.push(errors);
+        mesh = domain.mesh;
+    }
+
+    Ok(levels)
+}
+
+/// Error Type for Method of Manufactured Solutions problems
+#[derive(Debug)]
+pub enum ManufacturedError {
+    Sampling(GalerkinSamplingError),
+    Solver(NalgebraLinearError),
+    SparseSolver(SparseSolveError),
+    IndefiniteSystemTooLarge(usize),
+    PointOutsideMesh([f64; 2]),
+}
+
+impl std::error::Error for ManufacturedError {}
+
+impl fmt::Display for ManufacturedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::Solver(err) => write!(f, "{}", err),
+            Self::SparseSolver(err) => write!(f, "{}", err),
+            Self::IndefiniteSystemTooLarge(num_dofs) => write!(
+                f,
+                "Systems with k² >= 0 are indefinite and require the dense solver, but the Domain has {} DoFs (max: {}); Cannot Solve!",
+                num_dofs, MAX_DENSE_SIZE
+            ),
+            Self::PointOutsideMesh([x, y]) => write!(
+                f,
+                "Quadrature point ({}, {}) is outside of the Mesh; Cannot compute Manufactured Source!",
+                x, y
+            ),
+        }
+    }
+}
+
+impl From<GalerkinSamplingError> for ManufacturedError {
+    fn from(err: GalerkinSamplingError) -> Self {
+        Self::Sampling(err)
+    }
+}
+
+impl From<NalgebraLinearError> for ManufacturedError {
+    fn from(err: NalgebraLinearError) -> Self {
+        Self::Solver(err)
+    }
+}
+
+impl From<SparseSolveError> for ManufacturedError {
+    fn from(err: SparseSolveError) -> Self {
+        Self::SparseSolver(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_problem::verification::{CavityLayer, RectangularCavity};
+    use std::f64::consts::PI;
+
+    fn smooth_field(x: f64, y: f64) -> [f64; 2] {
+        [(PI * y).sin() * x.exp(), (PI * x).sin() * y.cos()]
+    }
+
+    #[test]
+    fn finite_differences() {
+        let mms = ManufacturedSolution::new(smooth_field);
+        let materials = Materials::from_array([2.0, 0.0, 0.5, 0.0]);
+        let [x, y] = [0.3, 0.7];
+
+        // ∂Ey/∂x - ∂Ex/∂y
+        let curl = PI * (PI * x).cos() * y.cos() - PI * (PI * y).cos() * x.exp();
+        assert!((mms.curl(x, y) - curl).abs() < 1e-9);
+
+        // μ⁻¹ (∂c/∂y, -∂c/∂x) - k² ε E
+        let dc_dx = -PI * PI * (PI * x).sin() * y.cos() - PI * (PI * y).cos() * x.exp();
+        let dc_dy = -PI * (PI * x).cos() * y.sin() + PI * PI * (PI * y).sin() * x.exp();
+        let field = smooth_field(x, y);
+        let source = mms.source(x, y, &materials, 3.0);
+        assert!((source[0] - (dc_dy / 0.5 - 6.0 * field[0])).abs() < 1e-6);
+        assert!((source[1] - (-dc_dx / 0.5 - 6.0 * field[1])).abs() < 1e-6);
+    }
+
+    #[test]
+    fn h_convergence() {
+        let mut mesh = RectangularCavity::homogeneous(1.0, 1.0, Materials::default()).mesh();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+
+        let mms = ManufacturedSolution::new(smooth_field);
+        let levels = manufactured_h_study::<HierPoly, _>(&mesh, &mms, 2.0, 2).unwrap();
+
+        for pair in levels.windows(2) {
+            // halving h should reduce the errors by (about) a factor of 2^p
+            assert!(pair[1].hcurl_error < pair[0].hcurl_error / 3.0);
+            assert!(pair[1].l2_error < pair[0].l2_error / 3.0);
+            assert!(pair[1].num_dofs > pair[0].num_dofs);
+        }
+    }
+
+    #[test]
+    fn fine_meshes() {
+        let mut mesh = RectangularCavity::homogeneous(1.0, 1.0, Materials::default()).mesh();
+        mesh.set_global_expansion_orders([2, 2]).unwrap();
+
+        let mms = ManufacturedSolution::new(smooth_field);
+        let levels = manufactured_h_study::<HierPoly, _>(&mesh, &mms, -1.0, 4).unwrap();
+
+        // the finest levels are too large for the dense solver
+        assert!(levels.last().unwrap().num_dofs > MAX_DENSE_SIZE);
+        // the coarsest level (4 DoFs) is outside of the asymptotic regime
+        assert!(levels[1..]
+            .windows(2)
+            .all(|pair| pair[1].hcurl_error < pair[0].hcurl_error / 3.0));
+
+        // indefinite systems of the same size are rejected (rather than solved without pivoting)
+        for _ in 0..4 {
+            mesh.global_h_refinement(HRef::T);
+        }
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        assert!(matches!(
+            mms.solve::<HierPoly>(&domain, 2.0, None),
+            Err(ManufacturedError::IndefiniteSystemTooLarge(num_dofs)) if num_dofs > MAX_DENSE_SIZE
+        ));
+    }
+
+    #[test]
+    fn layered_materials() {
+        // μ is uniform, so the smooth field satisfies the interface conditions across the permittivity step
+        let cavity = RectangularCavity::layered(
+            1.0,
+            vec![
+                CavityLayer {
+                    thickness: 0.5,
+                    materials: Materials::default(),
+                },
+                CavityLayer {
+                    thickness: 0.5,
+                    materials: Materials::from_array([3.0, 0.0, 1.0, 0.0]),
+                },
+            ],
+        );
+        let mms = ManufacturedSolution::new(smooth_field);
+
+        let mut mesh = cavity.mesh();
+        mesh.set_global_expansion_orders([7, 7]).unwrap();
+        let domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+        let (_, coarse) = mms.solve::<HierPoly>(&domain, 5.0, None).unwrap();
+        assert!(coarse.relative_hcurl_error() < 1e-4);
+
+        // a local h-refinement introduces Basis Functions on multiple levels of the Mesh
+        mesh.h_refine_elems(vec![1], HRef::T).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let (_, refined) = mms.solve::<HierPoly>(&domain, 5.0, None).unwrap();
+        assert!(refined.hcurl_error < coarse.hcurl_error);
+    }
+}
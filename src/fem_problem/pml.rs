@@ -0,0 +1,233 @@
+use crate::fem_domain::domain::mesh::space::Point;
+use num_complex::Complex64;
+use std::fmt;
+
+/// The directions along which a [Pml] attenuates outgoing waves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PmlDir {
+    /// Layers on the left and right of the interior region
+    X,
+    /// Layers on the bottom and top of the interior region
+    Y,
+    /// Layers on all four sides of the interior region (including the corners)
+    XY,
+}
+
+/// The grading profile of the imaginary part of the coordinate stretch through the thickness of a [Pml]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PmlProfile {
+    /// Uniform stretch: `s = 1 - i * max`
+    Constant(f64),
+    /// Polynomially graded stretch: `s(d) = 1 - i * max * (d / thickness)^order`, where `d` is the depth into the layer
+    Polynomial { order: f64, max: f64 },
+}
+
+impl PmlProfile {
+    /// Stretching factor at some normalized depth (between 0 and 1) into the layer
+    pub fn stretch_at(&self, normalized_depth: f64) -> Complex64 {
+        match self {
+            Self::Constant(max) => Complex64::new(1.0, -max),
+            Self::Polynomial { order, max } => {
+                Complex64::new(1.0, -max * normalized_depth.powf(*order))
+            }
+        }
+    }
+}
+
+/// Perfectly Matched Layer described by complex coordinate stretching
+///
+/// The layer surrounds a rectangular interior region (where no stretching is applied) with some thickness along the x and/or y directions.
+/// Points within the layer are stretched by `[s_x, s_y]`, according to their depth into the layer and the [PmlProfile].
+///
+/// The stretch modifies the effective material parameters:
+/// * `ε_eff = ε * diag(s_y / s_x, s_x / s_y)`
+/// * `μ_eff = μ * s_x * s_y`
+///
+/// PMLs are applied to the system matrix through an [IntegralSum](crate::fem_problem::integration::IntegralSum), where each integral applies the stretch at its quadrature points (see [HierCurlIntegral::integrate_stretched](crate::fem_problem::integration::HierCurlIntegral::integrate_stretched)).
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// // absorbing layers of thickness 0.5 to the left and right of [0, 1] x [0, 1]
+/// let pml = Pml::new(
+///     [Point::new(0.0, 0.0), Point::new(1.0, 1.0)],
+///     0.5,
+///     PmlDir::X,
+///     PmlProfile::Polynomial { order: 2.0, max: 4.0 },
+/// )
+/// .unwrap();
+///
+/// // no stretching in the interior
+/// assert_eq!(pml.stretch(Point::new(0.5, 0.5)), [Complex64::new(1.0, 0.0); 2]);
+///
+/// // stretching along x half-way through the right-hand layer
+/// let [s_x, s_y] = pml.stretch(Point::new(1.25, 0.5));
+/// assert!((s_x - Complex64::new(1.0, -1.0)).norm() < 1e-12);
+/// assert_eq!(s_y, Complex64::new(1.0, 0.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pml {
+    interior: [Point; 2],
+    thickness: f64,
+    dir: PmlDir,
+    profile: PmlProfile,
+}
+
+impl Pml {
+    /// Construct a new PML around the `interior` rectangle (defined by its minimum and maximum corners)
+    ///
+    /// # Returns an `Err` if
+    /// * The interior rectangle has a negative or zero area
+    /// * The thickness is not positive
+    /// * The profile's maximum stretch or order is negative
+    pub fn new(
+        interior: [Point; 2],
+        thickness: f64,
+        dir: PmlDir,
+        profile: PmlProfile,
+    ) -> Result<Self, PmlError> {
+        if interior[1].x <= interior[0].x || interior[1].y <= interior[0].y {
+            return Err(PmlError::InvalidInterior);
+        }
+        if thickness <= 0.0 {
+            return Err(PmlError::InvalidThickness(thickness));
+        }
+        match profile {
+            PmlProfile::Constant(max) if max < 0.0 => return Err(PmlError::InvalidProfile),
+            PmlProfile::Polynomial { order, max } if order < 0.0 || max < 0.0 => {
+                return Err(PmlError::InvalidProfile)
+            }
+            _ => (),
+        }
+
+        Ok(Self {
+            interior,
+            thickness,
+            dir,
+            profile,
+        })
+    }
+
+    /// Is the point inside of the absorbing layer?
+    pub fn contains(&self, point: Point) -> bool {
+        let [d_x, d_y] = self.depths(point);
+        d_x > 0.0 || d_y > 0.0
+    }
+
+    /// The complex stretching factors `[s_x, s_y]` at some point
+    pub fn stretch(&self, point: Point) -> [Complex64; 2] {
+        self.depths(point).map(|depth| {
+            if depth > 0.0 {
+                self.profile.stretch_at(depth / self.thickness)
+            } else {
+                Complex64::new(1.0, 0.0)
+            }
+        })
+    }
+
+    // depth into the layer along x and y (clamped to the thickness of the layer)
+    fn depths(&self, point: Point) -> [f64; 2] {
+        let depth_x = (self.interior[0].x - point.x)
+            .max(point.x - self.interior[1].x)
+            .clamp(0.0, self.thickness);
+        let depth_y = (self.interior[0].y - point.y)
+            .max(point.y - self.interior[1].y)
+            .clamp(0.0, self.thickness);
+
+        match self.dir {
+            PmlDir::X => [depth_x, 0.0],
+            PmlDir::Y => [0.0, depth_y],
+            PmlDir::XY => [depth_x, depth_y],
+        }
+    }
+}
+
+/// Error Type for PML construction
+#[derive(Debug)]
+pub enum PmlError {
+    InvalidInterior,
+    InvalidThickness(f64),
+    InvalidProfile,
+}
+
+impl std::error::Error for PmlError {}
+
+impl fmt::Display for PmlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidInterior => write!(
+                f,
+                "PML interior must be defined by its minimum and maximum corners; Cannot construct PML!"
+            ),
+            Self::InvalidThickness(thickness) => write!(
+                f,
+                "PML thickness must be positive (received: {}); Cannot construct PML!",
+                thickness
+            ),
+            Self::InvalidProfile => write!(
+                f,
+                "PML profile must have a non-negative maximum and order; Cannot construct PML!"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_interior() -> [Point; 2] {
+        [Point::new(0.0, 0.0), Point::new(1.0, 1.0)]
+    }
+
+    #[test]
+    fn graded_stretch() {
+        let pml = Pml::new(
+            unit_interior(),
+            0.5,
+            PmlDir::XY,
+            PmlProfile::Polynomial {
+                order: 2.0,
+                max: 8.0,
+            },
+        )
+        .unwrap();
+
+        // corner: stretched in both directions
+        let [s_x, s_y] = pml.stretch(Point::new(-0.25, 1.5));
+        assert!((s_x - Complex64::new(1.0, -2.0)).norm() < 1e-12);
+        assert!((s_y - Complex64::new(1.0, -8.0)).norm() < 1e-12);
+
+        // points beyond the layer use the outer stretch
+        let [s_x, _] = pml.stretch(Point::new(5.0, 0.5));
+        assert!((s_x - Complex64::new(1.0, -8.0)).norm() < 1e-12);
+
+        assert!(pml.contains(Point::new(0.5, 1.1)));
+        assert!(!pml.contains(Point::new(0.5, 0.5)));
+    }
+
+    #[test]
+    fn directional_layers() {
+        let pml = Pml::new(unit_interior(), 1.0, PmlDir::Y, PmlProfile::Constant(1.0)).unwrap();
+
+        assert!(!pml.contains(Point::new(1.5, 0.5)));
+        assert_eq!(
+            pml.stretch(Point::new(0.5, -0.5)),
+            [Complex64::new(1.0, 0.0), Complex64::new(1.0, -1.0)]
+        );
+    }
+
+    #[test]
+    fn invalid_pml() {
+        assert!(Pml::new(
+            [Point::new(1.0, 0.0), Point::new(0.0, 1.0)],
+            1.0,
+            PmlDir::X,
+            PmlProfile::Constant(1.0)
+        )
+        .is_err());
+        assert!(Pml::new(unit_interior(), 0.0, PmlDir::X, PmlProfile::Constant(1.0)).is_err());
+        assert!(Pml::new(unit_interior(), 1.0, PmlDir::X, PmlProfile::Constant(-1.0)).is_err());
+    }
+}
@@ -0,0 +1,779 @@
+use super::galerkin::{
+    assemble_matrix_sum, sample_boundary_edges, BoundaryEdgeSamples, GalerkinSamplingError,
+};
+use super::integration::{
+    integrals::{curl_curl::CurlCurl, inner::L2Inner},
+    IntegralSum,
+};
+use super::linalg::{
+    nalgebra_solve::{nalgebra_solve_linear, NalgebraLinearError},
+    sparse_matrix::ComplexSparseMatrix,
+};
+use super::pml::Pml;
+use crate::fem_domain::basis::HierCurlBasisFnSpace;
+use crate::fem_domain::domain::{mesh::space::Point, Domain};
+use nalgebra::{DMatrix, SymmetricEigen};
+use num_complex::Complex64;
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+/// Speed of light in a vacuum (m/s). Used to convert frequencies into free-space wavenumbers, where Mesh coordinates are in meters.
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+/// The free-space wavenumber (rad/m) associated with some frequency (Hz)
+pub fn wavenumber(frequency: f64) -> f64 {
+    2.0 * PI * frequency / SPEED_OF_LIGHT
+}
+
+/// Description of the mode used to excite (and absorb waves at) a [Port]
+#[derive(Clone)]
+pub enum PortMode {
+    /// A given profile of the tangential electric field along the port: `e(x, y)`
+    ///
+    /// The propagation constant is: `β = sqrt(k² ε μ - k_c²)`, where `k_c` is the `cutoff` wavenumber, and the material parameters are taken from the first Element along the port
+    Profile {
+        profile: Arc<dyn Fn(f64, f64) -> f64 + Send + Sync>,
+        cutoff: f64,
+    },
+    /// The dominant mode of a 1D eigenproblem along the port: `-d/ds(ε⁻¹ dh/ds) - k² μ h = -β² ε⁻¹ h` (where `e = h / ε`)
+    ///
+    /// The eigenproblem is discretized with `segments_per_edge` linear elements along each of the port's Edges. This supports ports with inhomogeneous materials.
+    Computed { segments_per_edge: usize },
+}
+
+/// A Waveguide Port defined along the boundary Edges with some tag
+///
+/// Ports must lie along a single strait line, and their Edges must be tagged (see [Mesh::tag_boundary_edges](crate::fem_domain::domain::mesh::Mesh::tag_boundary_edges)) before the [Domain] is constructed.
+#[derive(Clone)]
+pub struct Port {
+    /// The tag of the port's boundary Edges
+    pub tag: usize,
+    /// The port's mode
+    pub mode: PortMode,
+}
+
+impl Port {
+    /// Define a port with a given tangential field profile and cutoff wavenumber
+    pub fn with_profile<F>(tag: usize, profile: F, cutoff: f64) -> Self
+    where
+        F: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    {
+        Self {
+            tag,
+            mode: PortMode::Profile {
+                profile: Arc::new(profile),
+                cutoff,
+            },
+        }
+    }
+
+    /// Define a port whose mode is computed from a 1D eigenproblem along the port
+    pub fn computed(tag: usize, segments_per_edge: usize) -> Self {
+        Self {
+            tag,
+            mode: PortMode::Computed { segments_per_edge },
+        }
+    }
+}
+
+/// A driven problem excited by one or more [Port]s
+///
+/// The system matrix is: `A = CurlCurl - k² L2Inner + Σ_p j (k² / β_p) g_p g_pᵀ / N_p`, where:
+/// * `g_p` is the projection of the Basis Functions onto port `p`'s mode: `∫ ε F_t e_p dl`
+/// * `N_p` is the mode's norm: `∫ ε e_p² dl`
+///
+/// Exciting port `p` with a unit amplitude mode yields the right-hand-side: `2j (k² / β_p) g_p`. Modal amplitudes are extracted from a solution `x` with: `g_pᵀ x / N_p`.
+///
+/// The curl-curl and mass matrices are assembled once (with an optional [Pml]) and reused for each frequency.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// // parallel plate waveguide with ports on its left and right sides
+/// let mut mesh = Mesh::from_file("./test_input/waveguide.json").unwrap();
+/// mesh.set_global_expansion_orders([4, 2]).unwrap();
+/// mesh.tag_boundary_edges(1, |point| point.x.abs() < 1e-12);
+/// mesh.tag_boundary_edges(2, |point| (point.x - 4.0).abs() < 1e-12);
+/// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let ports = vec![Port::computed(1, 4), Port::computed(2, 4)];
+/// let problem = PortProblem::new::<HierPoly>(&domain, ports, None, None).unwrap();
+///
+/// let s_params = problem.s_parameters(&[50.0e6, 60.0e6]).unwrap();
+/// s_params.write_touchstone("./test_output/waveguide.s2p").unwrap();
+///
+/// // the wave passes through the waveguide without reflection
+/// assert!(s_params.get(0, [0, 0]).norm() < 1e-2);
+/// assert!((s_params.get(0, [1, 0]).norm() - 1.0).abs() < 1e-2);
+/// ```
+pub struct PortProblem<'d> {
+    domain: &'d Domain,
+    ports: Vec<PortData>,
+    curl_curl: ComplexSparseMatrix,
+    mass: ComplexSparseMatrix,
+}
+
+impl<'d> PortProblem<'d> {
+    /// Sample the [Port]s and assemble the frequency-independent parts of the system matrix
+    ///
+    /// # Arguments
+    /// * `domain`: The [Domain] over which the problem is defined
+    /// * `ports`: The list of [Port]s
+    /// * `pml`: An optional Perfectly Matched Layer applied to the curl-curl and mass matrices
+    /// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points in to use for integration along each direction. If `None`, the default values are used.
+    /// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument
+    pub fn new<BSpace: HierCurlBasisFnSpace>(
+        domain: &'d Domain,
+        ports: Vec<Port>,
+        pml: Option<Pml>,
+        glq_grid_dim: Option<[usize; 2]>,
+    ) -> Result<Self, PortError> {
+        if ports.is_empty() {
+            return Err(PortError::NoPorts);
+        }
+
+        let mut cc_sum = IntegralSum::<BSpace>::new().with::<CurlCurl>(1.0);
+        let mut mass_sum = IntegralSum::<BSpace>::new().with::<L2Inner>(1.0);
+        if let Some(pml) = pml {
+            cc_sum = cc_sum.with_pml(pml.clone());
+            mass_sum = mass_sum.with_pml(pml);
+        }
+
+        let curl_curl =
+            assemble_matrix_sum(domain, &cc_sum, glq_grid_dim).map_err(PortError::Sampling)?;
+        let mass =
+            assemble_matrix_sum(domain, &mass_sum, glq_grid_dim).map_err(PortError::Sampling)?;
+
+        let glq_grid_dim = glq_grid_dim.map_or([None; 2], |[u, v]| [Some(u), Some(v)]);
+        let ports = ports
+            .into_iter()
+            .map(|port| PortData::new::<BSpace>(domain, port, glq_grid_dim))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            domain,
+            ports,
+            curl_curl,
+            mass,
+        })
+    }
+
+    /// Number of Ports
+    pub fn num_ports(&self) -> usize {
+        self.ports.len()
+    }
+
+    /// Assemble the system matrix and right-hand-side for a unit amplitude excitation at port `excited_port` and some `frequency` (Hz)
+    pub fn assemble(
+        &self,
+        excited_port: usize,
+        frequency: f64,
+    ) -> Result<(ComplexSparseMatrix, Vec<Complex64>), PortError> {
+        if excited_port >= self.ports.len() {
+            return Err(PortError::PortDoesNotExist(excited_port));
+        }
+
+        let modes = self.sample_modes(frequency)?;
+        Ok(self.assemble_with_modes(&modes, excited_port, frequency))
+    }
+
+    /// Solve the driven problem for a unit amplitude excitation at port `excited_port` and some `frequency` (Hz). Returns the solution vector indexed by DoF ID.
+    pub fn solve(&self, excited_port: usize, frequency: f64) -> Result<Vec<Complex64>, PortError> {
+        let (a, b) = self.assemble(excited_port, frequency)?;
+        nalgebra_solve_linear(a, &b).map_err(PortError::Solver)
+    }
+
+    /// Compute the Scattering Parameters over a sweep of frequencies (Hz)
+    ///
+    /// The S-Parameters are power-normalized such that: `S_ij = (b_i / a_j) * sqrt(P_i / P_j)`, where `P_p ∝ N_p / β_p` is the power carried by a unit amplitude mode at port `p`.
+    /// The phase of each parameter is referenced to the port planes.
+    pub fn s_parameters(&self, frequencies: &[f64]) -> Result<SParameters, PortError> {
+        let num_ports = self.ports.len();
+        let mut values = Vec::with_capacity(frequencies.len());
+
+        for &frequency in frequencies {
+            let modes = self.sample_modes(frequency)?;
+            let mut s_matrix =
+                DMatrix::from_element(num_ports, num_ports, Complex64::new(0.0, 0.0));
+
+            for j in 0..num_ports {
+                let (a, b) = self.assemble_with_modes(&modes, j, frequency);
+                let x = nalgebra_solve_linear(a, &b).map_err(PortError::Solver)?;
+
+                for i in 0..num_ports {
+                    let amplitude = modes[i].amplitude(&x);
+                    let reflected = if i == j { amplitude - 1.0 } else { amplitude };
+
+                    s_matrix[(i, j)] = reflected * (modes[i].power() / modes[j].power()).sqrt();
+                }
+            }
+
+            values.push(s_matrix);
+        }
+
+        Ok(SParameters {
+            frequencies: frequencies.to_vec(),
+            values,
+        })
+    }
+
+    fn sample_modes(&self, frequency: f64) -> Result<Vec<SampledMode>, PortError> {
+        let k = wavenumber(frequency);
+        self.ports
+            .iter()
+            .map(|port| port.sample_mode(k, frequency))
+            .collect()
+    }
+
+    fn assemble_with_modes(
+        &self,
+        modes: &[SampledMode],
+        excited_port: usize,
+        frequency: f64,
+    ) -> (ComplexSparseMatrix, Vec<Complex64>) {
+        let k = wavenumber(frequency);
+
+        let mut a = self.curl_curl.clone();
+        a.add_scaled(&self.mass, Complex64::new(-k * k, 0.0));
+
+        let mut b = vec![Complex64::new(0.0, 0.0); self.domain.dofs.len()];
+
+        for (p, mode) in modes.iter().enumerate() {
+            let coeff = Complex64::new(0.0, k * k / mode.beta);
+
+            let mut port_entries = Vec::with_capacity(mode.projection.len().pow(2) / 2);
+            for (i, (p_dof_id, p_value)) in mode.projection.iter().enumerate() {
+                for (q_dof_id, q_value) in mode.projection.iter().skip(i) {
+                    port_entries.push((
+                        [*p_dof_id, *q_dof_id],
+                        coeff * p_value * q_value / mode.norm,
+                    ));
+                }
+            }
+            a.insert_group(port_entries);
+
+            if p == excited_port {
+                for (dof_id, value) in mode.projection.iter() {
+                    b[*dof_id] += coeff * 2.0 * value;
+                }
+            }
+        }
+
+        (a, b)
+    }
+}
+
+/// Scattering Parameters computed over a sweep of frequencies
+pub struct SParameters {
+    /// Frequencies (Hz)
+    pub frequencies: Vec<f64>,
+    /// S-Matrices at each frequency (entry `(i, j)` is the response at port `i` due to an excitation at port `j`)
+    pub values: Vec<DMatrix<Complex64>>,
+}
+
+impl SParameters {
+    /// Number of Ports
+    pub fn num_ports(&self) -> usize {
+        self.values.first().map_or(0, |s_matrix| s_matrix.nrows())
+    }
+
+    /// Get the S-Parameter `S_ij` at the frequency with index `freq_idx`
+    pub fn get(&self, freq_idx: usize, [i, j]: [usize; 2]) -> Complex64 {
+        self.values[freq_idx][(i, j)]
+    }
+
+    /// Write the S-Parameters to a Touchstone (v1) file (ex: `.s2p` for two-port problems). Values are written in real-imaginary format.
+    pub fn write_touchstone(&self, path: impl AsRef<str>) -> std::io::Result<()> {
+        let file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(file);
+        let num_ports = self.num_ports();
+
+        writeln!(
+            writer,
+            "! {}-port S-Parameters generated by fem_2d",
+            num_ports
+        )?;
+        writeln!(writer, "# HZ S RI R 50")?;
+
+        for (frequency, s_matrix) in self.frequencies.iter().zip(self.values.iter()) {
+            write!(writer, "{:.12e}", frequency)?;
+
+            if num_ports == 2 {
+                // two-port files are written in column-major order on a single line
+                for [i, j] in [[0, 0], [1, 0], [0, 1], [1, 1]] {
+                    let s = s_matrix[(i, j)];
+                    write!(writer, " {:.12e} {:.12e}", s.re, s.im)?;
+                }
+                writeln!(writer)?;
+            } else {
+                // otherwise, each row is written on its own line(s) with up to four entries per line
+                for i in 0..num_ports {
+                    for j in 0..num_ports {
+                        if j > 0 && j % 4 == 0 {
+                            write!(writer, "\n ")?;
+                        }
+                        let s = s_matrix[(i, j)];
+                        write!(writer, " {:.12e} {:.12e}", s.re, s.im)?;
+                    }
+                    writeln!(writer)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The sampled Basis Functions and materials along a Port
+struct PortData {
+    tag: usize,
+    mode: PortMode,
+    edge_samples: Vec<BoundaryEdgeSamples>,
+    // (real) relative permittivity and permeability along each edge
+    edge_materials: Vec<[f64; 2]>,
+    // position of each edge's endpoints along the port (in ascending order)
+    edge_extents: Vec<[f64; 2]>,
+    // orientation of each edge's tangent relative to the port's tangent (±1)
+    edge_orientations: Vec<f64>,
+    // origin and unit tangent of the port's line
+    origin: Point,
+    tangent: [f64; 2],
+}
+
+impl PortData {
+    fn new<BSpace: HierCurlBasisFnSpace>(
+        domain: &Domain,
+        port: Port,
+        glq_grid_dim: [Option<usize>; 2],
+    ) -> Result<Self, PortError> {
+        let mut edge_samples = sample_boundary_edges::<BSpace>(domain, port.tag, glq_grid_dim)
+            .map_err(PortError::Sampling)?;
+
+        // find the port's line from its first Edge
+        let [p0, p1] = domain.mesh.edge_points(edge_samples[0].edge_id).unwrap();
+        let length = p0.dist(p1);
+        let tangent = [(p1.x - p0.x) / length, (p1.y - p0.y) / length];
+        let origin = *p0;
+
+        let position =
+            |point: &Point| (point.x - origin.x) * tangent[0] + (point.y - origin.y) * tangent[1];
+        let offset =
+            |point: &Point| (point.x - origin.x) * tangent[1] - (point.y - origin.y) * tangent[0];
+
+        // sort the edges along the port, ensuring that they fall along a single line
+        let mut extents = Vec::with_capacity(edge_samples.len());
+        for samples in edge_samples.iter() {
+            let [e0, e1] = domain.mesh.edge_points(samples.edge_id).unwrap();
+            if offset(e0).abs() > 1e-9 || offset(e1).abs() > 1e-9 {
+                return Err(PortError::PortNotStraight(port.tag));
+            }
+            let [s0, s1] = [position(e0), position(e1)];
+            extents.push(([s0.min(s1), s0.max(s1)], (s1 - s0).signum()));
+        }
+
+        let mut order: Vec<usize> = (0..edge_samples.len()).collect();
+        order.sort_by(|a, b| extents[*a].0[0].partial_cmp(&extents[*b].0[0]).unwrap());
+
+        let mut sorted_samples: BTreeMap<usize, BoundaryEdgeSamples> =
+            edge_samples.drain(0..).enumerate().collect();
+        let edge_samples: Vec<BoundaryEdgeSamples> = order
+            .iter()
+            .map(|idx| sorted_samples.remove(idx).unwrap())
+            .collect();
+        let edge_extents = order.iter().map(|idx| extents[*idx].0).collect();
+        let edge_orientations = order.iter().map(|idx| extents[*idx].1).collect();
+
+        let edge_materials = edge_samples
+            .iter()
+            .map(|samples| {
+                let materials = domain.mesh.elems[samples.elem_id].get_materials();
+                [materials.eps_rel.re, materials.mu_rel.re]
+            })
+            .collect();
+
+        Ok(Self {
+            tag: port.tag,
+            mode: port.mode,
+            edge_samples,
+            edge_materials,
+            edge_extents,
+            edge_orientations,
+            origin,
+            tangent,
+        })
+    }
+
+    // Sample the port's mode at the quadrature points along each Edge and compute its projection onto the Basis Functions
+    fn sample_mode(&self, k: f64, frequency: f64) -> Result<SampledMode, PortError> {
+        let (beta_sq, profiles): (f64, Vec<Vec<f64>>) = match &self.mode {
+            PortMode::Profile { profile, cutoff } => {
+                let [eps, mu] = self.edge_materials[0];
+                (
+                    k * k * eps * mu - cutoff * cutoff,
+                    self.edge_samples
+                        .iter()
+                        .map(|samples| samples.points.iter().map(|p| profile(p.x, p.y)).collect())
+                        .collect(),
+                )
+            }
+            PortMode::Computed { segments_per_edge } => {
+                self.computed_mode(k, *segments_per_edge)?
+            }
+        };
+
+        if beta_sq <= 0.0 {
+            return Err(PortError::BelowCutoff(self.tag, frequency));
+        }
+
+        let mut projection: BTreeMap<usize, f64> = BTreeMap::new();
+        let mut norm = 0.0;
+        for (((samples, [eps, _]), orientation), e_values) in self
+            .edge_samples
+            .iter()
+            .zip(self.edge_materials.iter())
+            .zip(self.edge_orientations.iter())
+            .zip(profiles.iter())
+        {
+            norm += eps * samples.integrate(e_values, e_values);
+
+            // the Basis Functions' tangential components are relative to the Edge's tangent
+            for (dof_id, f_values) in samples.dof_samples.iter() {
+                *projection.entry(*dof_id).or_insert(0.0) +=
+                    orientation * eps * samples.integrate(e_values, f_values);
+            }
+        }
+
+        if norm <= 0.0 {
+            return Err(PortError::DegenerateMode(self.tag));
+        }
+
+        Ok(SampledMode {
+            beta: beta_sq.sqrt(),
+            norm,
+            projection: projection.into_iter().collect(),
+        })
+    }
+
+    // Solve the 1D eigenproblem for the dominant mode along the port using linear elements. Returns β² and the field profile along each Edge.
+    fn computed_mode(
+        &self,
+        k: f64,
+        segments_per_edge: usize,
+    ) -> Result<(f64, Vec<Vec<f64>>), PortError> {
+        let segments_per_edge = segments_per_edge.max(1);
+
+        // build a 1D mesh of nodes along the port (merging coincident nodes between neighboring edges)
+        let mut nodes: Vec<f64> = Vec::new();
+        let mut segments: Vec<([usize; 2], [f64; 2])> = Vec::new();
+        for ([s_start, s_end], materials) in
+            self.edge_extents.iter().zip(self.edge_materials.iter())
+        {
+            match nodes.last() {
+                Some(last) if (last - s_start).abs() < 1e-9 => (),
+                Some(_) => return Err(PortError::PortNotContiguous(self.tag)),
+                None => nodes.push(*s_start),
+            }
+
+            let h = (s_end - s_start) / segments_per_edge as f64;
+            for seg in 0..segments_per_edge {
+                let n0 = nodes.len() - 1;
+                nodes.push(s_start + h * (seg + 1) as f64);
+                segments.push(([n0, n0 + 1], *materials));
+            }
+        }
+
+        // assemble the stiffness and mass matrices
+        let num_nodes = nodes.len();
+        let mut k_mat = DMatrix::<f64>::zeros(num_nodes, num_nodes);
+        let mut m_mat = DMatrix::<f64>::zeros(num_nodes, num_nodes);
+        for ([n0, n1], [eps, mu]) in segments.iter() {
+            let h = nodes[*n1] - nodes[*n0];
+            for (a, b, stiff, mass) in [
+                (n0, n0, 1.0, 2.0),
+                (n0, n1, -1.0, 1.0),
+                (n1, n0, -1.0, 1.0),
+                (n1, n1, 1.0, 2.0),
+            ] {
+                k_mat[(*a, *b)] += stiff / (eps * h) - k * k * mu * mass * h / 6.0;
+                m_mat[(*a, *b)] += mass * h / (6.0 * eps);
+            }
+        }
+
+        // reduce to a standard symmetric eigenproblem with the cholesky decomposition of M
+        let l = match m_mat.cholesky() {
+            Some(cholesky) => cholesky.l(),
+            None => return Err(PortError::DegenerateMode(self.tag)),
+        };
+        let l_inv = l
+            .clone()
+            .try_inverse()
+            .ok_or(PortError::DegenerateMode(self.tag))?;
+        let reduced = &l_inv * k_mat * l_inv.transpose();
+        let eigen = SymmetricEigen::new(reduced);
+
+        // the dominant mode has the smallest eigenvalue (largest β²)
+        let (min_idx, min_eval) = eigen
+            .eigenvalues
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .unwrap();
+        let h_values = l_inv.transpose() * eigen.eigenvectors.column(min_idx);
+
+        // e = h / ε at the quadrature points along each edge (with linear interpolation between nodes)
+        let interpolate = |s: f64| {
+            let seg_idx = segments
+                .iter()
+                .position(|([_, n1], _)| s <= nodes[*n1] + 1e-12)
+                .unwrap_or(segments.len() - 1);
+            let ([n0, n1], [eps, _]) = segments[seg_idx];
+            let t = (s - nodes[n0]) / (nodes[n1] - nodes[n0]);
+            (h_values[n0] * (1.0 - t) + h_values[n1] * t) / eps
+        };
+
+        let mut profiles: Vec<Vec<f64>> = self
+            .edge_samples
+            .iter()
+            .map(|samples| {
+                samples
+                    .points
+                    .iter()
+                    .map(|p| {
+                        interpolate(
+                            (p.x - self.origin.x) * self.tangent[0]
+                                + (p.y - self.origin.y) * self.tangent[1],
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // normalize the sign of the mode s.t. its average (or largest value) is positive
+        let sum: f64 = h_values.iter().sum();
+        let largest = h_values
+            .iter()
+            .cloned()
+            .max_by(|a, b| a.abs().partial_cmp(&b.abs()).unwrap())
+            .unwrap_or(1.0);
+        let sign = if sum.abs() > 1e-9 * largest.abs() * num_nodes as f64 {
+            sum.signum()
+        } else {
+            largest.signum()
+        };
+        for profile in profiles.iter_mut() {
+            for value in profile.iter_mut() {
+                *value *= sign;
+            }
+        }
+
+        Ok((-min_eval, profiles))
+    }
+}
+
+// A port mode sampled at a particular frequency
+struct SampledMode {
+    beta: f64,
+    // ∫ ε e² dl
+    norm: f64,
+    // ∫ ε F_t e dl for each DoF along the port
+    projection: Vec<(usize, f64)>,
+}
+
+impl SampledMode {
+    // amplitude of the mode within a solution vector
+    fn amplitude(&self, solution: &[Complex64]) -> Complex64 {
+        self.projection
+            .iter()
+            .map(|(dof_id, value)| solution[*dof_id] * value)
+            .sum::<Complex64>()
+            / self.norm
+    }
+
+    // power carried by a unit amplitude mode (up to a constant factor)
+    fn power(&self) -> f64 {
+        self.norm / self.beta
+    }
+}
+
+/// Error Type for Port Problems
+#[derive(Debug)]
+pub enum PortError {
+    NoPorts,
+    PortDoesNotExist(usize),
+    PortNotStraight(usize),
+    PortNotContiguous(usize),
+    BelowCutoff(usize, f64),
+    DegenerateMode(usize),
+    Sampling(GalerkinSamplingError),
+    Solver(NalgebraLinearError),
+}
+
+impl std::error::Error for PortError {}
+
+impl fmt::Display for PortError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoPorts => write!(f, "No Ports were specified; Cannot define Port Problem!"),
+            Self::PortDoesNotExist(port_idx) => {
+                write!(f, "Port {} does not exist; Cannot excite Port!", port_idx)
+            }
+            Self::PortNotStraight(tag) => write!(
+                f,
+                "Edges with tag {} do not fall along a strait line; Cannot define Port!",
+                tag
+            ),
+            Self::PortNotContiguous(tag) => write!(
+                f,
+                "Edges with tag {} are not contiguous; Cannot compute Port Mode!",
+                tag
+            ),
+            Self::BelowCutoff(tag, frequency) => write!(
+                f,
+                "Mode on Port with tag {} does not propagate at {} Hz; Cannot excite Port!",
+                tag, frequency
+            ),
+            Self::DegenerateMode(tag) => write!(
+                f,
+                "Mode on Port with tag {} is degenerate; Cannot excite Port!",
+                tag
+            ),
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::Solver(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_domain::domain::{mesh::Mesh, ContinuityCondition};
+
+    fn waveguide_domain() -> Domain {
+        let mut mesh = Mesh::from_file("./test_input/waveguide.json").unwrap();
+        mesh.set_global_expansion_orders([5, 2]).unwrap();
+        mesh.tag_boundary_edges(1, |point| point.x.abs() < 1e-12);
+        mesh.tag_boundary_edges(2, |point| (point.x - 4.0).abs() < 1e-12);
+        Domain::from_mesh(mesh, ContinuityCondition::HCurl)
+    }
+
+    #[test]
+    fn matched_waveguide() {
+        let domain = waveguide_domain();
+        let ports = vec![
+            Port::with_profile(1, |_, _| 1.0, 0.0),
+            Port::with_profile(2, |_, _| 1.0, 0.0),
+        ];
+        let problem = PortProblem::new::<HierPoly>(&domain, ports, None, None).unwrap();
+
+        let frequency = 50.0e6;
+        let s_params = problem.s_parameters(&[frequency]).unwrap();
+        let transmission = Complex64::new(0.0, -wavenumber(frequency) * 4.0).exp();
+
+        assert_eq!(s_params.num_ports(), 2);
+        assert!(s_params.get(0, [0, 0]).norm() < 1e-3);
+        assert!(s_params.get(0, [1, 1]).norm() < 1e-3);
+        assert!((s_params.get(0, [1, 0]) - transmission).norm() < 1e-3);
+        assert!((s_params.get(0, [0, 1]) - transmission).norm() < 1e-3);
+    }
+
+    #[test]
+    fn computed_mode_matches_profile() {
+        let domain = waveguide_domain();
+        let frequency = 80.0e6;
+
+        let profile_problem = PortProblem::new::<HierPoly>(
+            &domain,
+            vec![
+                Port::with_profile(1, |_, _| 2.0, 0.0),
+                Port::with_profile(2, |_, _| 2.0, 0.0),
+            ],
+            None,
+            None,
+        )
+        .unwrap();
+        let computed_problem = PortProblem::new::<HierPoly>(
+            &domain,
+            vec![Port::computed(1, 3), Port::computed(2, 3)],
+            None,
+            None,
+        )
+        .unwrap();
+
+        let profile_s = profile_problem.s_parameters(&[frequency]).unwrap();
+        let computed_s = computed_problem.s_parameters(&[frequency]).unwrap();
+
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((profile_s.get(0, [i, j]) - computed_s.get(0, [i, j])).norm() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn touchstone_export() {
+        let domain = waveguide_domain();
+        let problem = PortProblem::new::<HierPoly>(
+            &domain,
+            vec![Port::computed(1, 2), Port::computed(2, 2)],
+            None,
+            None,
+        )
+        .unwrap();
+
+        let s_params = problem.s_parameters(&[40.0e6, 50.0e6, 60.0e6]).unwrap();
+        s_params
+            .write_touchstone("./test_output/port_test.s2p")
+            .unwrap();
+
+        let contents = std::fs::read_to_string("./test_output/port_test.s2p").unwrap();
+        let data_lines: Vec<&str> = contents
+            .lines()
+            .filter(|line| !line.starts_with('!') && !line.starts_with('#'))
+            .collect();
+
+        assert!(contents.contains("# HZ S RI R 50"));
+        assert_eq!(data_lines.len(), 3);
+        assert!(data_lines
+            .iter()
+            .all(|line| line.split_whitespace().count() == 9));
+    }
+
+    #[test]
+    fn port_errors() {
+        let domain = waveguide_domain();
+
+        assert!(matches!(
+            PortProblem::new::<HierPoly>(&domain, Vec::new(), None, None),
+            Err(PortError::NoPorts)
+        ));
+        assert!(matches!(
+            PortProblem::new::<HierPoly>(&domain, vec![Port::computed(3, 2)], None, None),
+            Err(PortError::Sampling(
+                GalerkinSamplingError::UnknownBoundaryTag(3)
+            ))
+        ));
+
+        // cutoff wavenumber of 10 rad/m is above the free-space wavenumber at 50 MHz
+        let problem = PortProblem::new::<HierPoly>(
+            &domain,
+            vec![Port::with_profile(1, |_, _| 1.0, 10.0)],
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(matches!(
+            problem.solve(0, 50.0e6),
+            Err(PortError::BelowCutoff(1, _))
+        ));
+        assert!(matches!(
+            problem.solve(1, 50.0e6),
+            Err(PortError::PortDoesNotExist(1))
+        ));
+    }
+}
@@ -0,0 +1,436 @@
+use super::galerkin::{assemble_matrix_sum, GalerkinSamplingError};
+use super::integration::{
+    glq::{gauss_quadrature_points, scale_gauss_quad_points},
+    integrals::{curl_curl::CurlCurl, inner::L2Inner},
+    IntegralSum,
+};
+use super::linalg::sparse_solve::{sparse_solve_spd, SparseSolveError};
+use crate::fem_domain::basis::{default_ngq, HierCurlBasisFnSpace};
+use crate::fem_domain::domain::{
+    fields::{for_each_local_basis_fn, sample_local_fields, LocalFields},
+    mesh::space::{Point, V2D},
+    ContinuityCondition, Domain,
+};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Transfer a solution (indexed by DoF ID) from a previous [Domain] onto a new [Domain]
+///
+/// If the previous Basis Space is contained in the new one (see [embed_solution]), the coefficients are copied directly, and the transfer is exact.
+/// Otherwise, the solution is projected onto the new Basis Space (see [project_solution]).
+///
+/// # Arguments
+/// * `previous`: The [Domain] over which the solution was computed
+/// * `solution`: The solution over the previous Domain
+/// * `domain`: The new [Domain]
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points to use along each direction if a projection is needed. If `None`, a default value is chosen from the maximum expansion order.
+/// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument. It should match the Basis Space used to compute the solution.
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+/// let domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+///
+/// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+/// let eigen_pair = nalgebra_solve_gep(gep, 1.3).unwrap();
+///
+/// // use the solution as an initial guess on the refined domain
+/// mesh.global_p_refinement(PRef::from(1, 1));
+/// mesh.h_refine_elems(vec![0], HRef::T).unwrap();
+/// let refined_domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+///
+/// let initial_guess =
+///     transfer_solution::<HierPoly>(&domain, &eigen_pair.vector, &refined_domain, None).unwrap();
+/// assert_eq!(initial_guess.len(), refined_domain.dofs.len());
+/// ```
+pub fn transfer_solution<BSpace: HierCurlBasisFnSpace>(
+    previous: &Domain,
+    solution: &[f64],
+    domain: &Domain,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<Vec<f64>, ProjectionError> {
+    match embed_solution(previous, solution, domain)? {
+        Some(embedded) => Ok(embedded),
+        None => project_solution::<BSpace>(previous, solution, domain, glq_grid_dim),
+    }
+}
+
+/// Copy the coefficients of a solution from a previous [Domain] onto a new [Domain] whose Basis Space contains the previous one
+///
+/// This is the case when the new Domain's Mesh was produced by p-enriching the previous Mesh: since the Basis Functions are hierarchical,
+/// every previous Basis Function is also present in the new Domain. The solution is then represented exactly by copying the coefficients, and setting the new DoFs to zero.
+///
+/// h-refined `Elem`s hand their elem-type Basis Functions down to their children, so h-refinements generally require a projection instead.
+///
+/// # Returns
+/// * `Ok(Some(solution))`: if every DoF in the previous Domain has an identical DoF in the new Domain
+/// * `Ok(None)`: otherwise
+/// * An `Err` if either Domain was not constructed with an `H(Curl)` [ContinuityCondition], or if the solution doesn't match the previous Domain
+pub fn embed_solution(
+    previous: &Domain,
+    solution: &[f64],
+    domain: &Domain,
+) -> Result<Option<Vec<f64>>, ProjectionError> {
+    check_inputs(previous, solution, domain)?;
+
+    // `Elem`s must be in the same location under both Meshes
+    if previous.mesh.elems.len() > domain.mesh.elems.len()
+        || previous.mesh.elems.iter().any(|elem| {
+            let [p0, p1] = previous.mesh.elem_diag_points(elem.id).unwrap();
+            let [q0, q1] = domain.mesh.elem_diag_points(elem.id).unwrap();
+            p0 != q0 || p1 != q1
+        })
+    {
+        return Ok(None);
+    }
+
+    // look up the new DoF associated with each Basis Function
+    let mut new_dof_ids: BTreeMap<(usize, u8, u8, u8), usize> = BTreeMap::new();
+    for bs in domain.basis_specs.iter().flatten() {
+        if let Some(dof_id) = bs.dof_id {
+            new_dof_ids.insert((bs.elem_id, bs.i, bs.j, bs.dir as u8), dof_id);
+        }
+    }
+
+    // each previous DoF must correspond to exactly one new DoF composed of the same Basis Functions
+    let mut embedded = vec![0.0; domain.dofs.len()];
+    for dof in previous.dofs.iter() {
+        let addresses = dof.get_basis_specs();
+        let matching_ids: Vec<Option<usize>> = addresses
+            .iter()
+            .map(|address| {
+                let bs = &previous.basis_specs[address.elem_id][address.elem_idx];
+                new_dof_ids
+                    .get(&(bs.elem_id, bs.i, bs.j, bs.dir as u8))
+                    .copied()
+            })
+            .collect();
+
+        match matching_ids[0] {
+            Some(new_id)
+                if matching_ids.iter().all(|id| *id == Some(new_id))
+                    && domain.dofs[new_id].get_basis_specs().len() == addresses.len() =>
+            {
+                embedded[new_id] = solution[dof.id];
+            }
+            _ => return Ok(None),
+        }
+    }
+
+    Ok(Some(embedded))
+}
+
+/// Project a solution from a previous [Domain] onto the Basis Space of a new [Domain]
+///
+/// The projection is taken with respect to the (material weighted) H(Curl) inner product: `(u, v) = ∫ μ⁻¹ (∇ × u) (∇ × v) + ε u · v`.
+/// That is, the new solution `x` satisfies: `(φ_i, Σ_j x_j φ_j) = (φ_i, E)` for all new Basis Functions `φ_i`, where `E` is the previous solution.
+///
+/// The integrals are computed over the new Domain's leaf-`Elem`s, where the previous solution is sampled at each quadrature point.
+/// As such, the projection is most accurate when each of the new leaf-`Elem`s falls within a single previous leaf-`Elem` (as is the case after h-refinement).
+///
+/// # Arguments
+/// * `previous`: The [Domain] over which the solution was computed
+/// * `solution`: The solution over the previous Domain
+/// * `domain`: The new [Domain]
+/// * `glq_grid_dim`: The number of Gauss Legendre Quadrature Points to use along each direction. If `None`, a default value is chosen from the maximum expansion order.
+/// * A [HierCurlBasisFnSpace] `BSpace` must be specified as a Generic Argument. It should match the Basis Space used to compute the solution.
+///
+/// The Gram matrix is symmetric positive-definite and is solved in its sparse form with [sparse_solve_spd], so there is no limit on the size of the new Domain.
+pub fn project_solution<BSpace: HierCurlBasisFnSpace>(
+    previous: &Domain,
+    solution: &[f64],
+    domain: &Domain,
+    glq_grid_dim: Option<[usize; 2]>,
+) -> Result<Vec<f64>, ProjectionError> {
+    check_inputs(previous, solution, domain)?;
+
+    let [num_glq_u, num_glq_v] = match glq_grid_dim {
+        Some(dims) => dims,
+        None => {
+            let [i_max, j_max] = domain.mesh.max_expansion_orders();
+            let [pi_max, pj_max] = previous.mesh.max_expansion_orders();
+            [i_max.max(pi_max), j_max.max(pj_max)].map(|order| default_ngq(order as usize))
+        }
+    };
+
+    // H(Curl) Gram matrix over the new Basis Space
+    let gram_matrix = assemble_matrix_sum(
+        domain,
+        &IntegralSum::<BSpace>::new()
+            .with::<CurlCurl>(1.0)
+            .with::<L2Inner>(1.0),
+        Some([num_glq_u, num_glq_v]),
+    )?;
+
+    // inner products of the new Basis Functions with the previous solution
+    let (u_points, u_weights) = gauss_quadrature_points(num_glq_u, false);
+    let (v_points, v_weights) = gauss_quadrature_points(num_glq_v, false);
+
+    let rhs_entries: Vec<(usize, f64)> = domain
+        .mesh
+        .elems
+        .par_iter()
+        .filter(|elem| !elem.has_children())
+        .flat_map_iter(|elem| {
+            let [p0, p1] = domain.mesh.elem_diag_points(elem.id).unwrap();
+            let (_, xs) = scale_gauss_quad_points(&u_points, p0.x, p1.x);
+            let (_, ys) = scale_gauss_quad_points(&v_points, p0.y, p1.y);
+            let area_scale = (p1.x - p0.x) * (p1.y - p0.y) / 4.0;
+
+            let materials = elem.get_materials();
+            let [eps, mu] = [materials.eps_rel.re, materials.mu_rel.re];
+
+            let (fields, curls) =
+                sample_previous_solution::<BSpace>(previous, solution, [&xs, &ys]);
+
+            let mut local_entries: BTreeMap<usize, f64> = BTreeMap::new();
+            for_each_local_basis_fn::<BSpace, _>(
+                domain,
+                elem.id,
+                [&xs, &ys],
+                |bs, [m, n], sample| {
+                    let value = u_weights[m]
+                        * v_weights[n]
+                        * area_scale
+                        * (sample.curl * curls[m][n] / mu
+                            + eps * V2D::dot(sample.field, fields[m][n]));
+                    *local_entries.entry(bs.dof_id.unwrap()).or_insert(0.0) += value;
+                },
+            );
+
+            local_entries.into_iter()
+        })
+        .collect();
+
+    let mut rhs = vec![0.0; domain.dofs.len()];
+    for (dof_id, value) in rhs_entries {
+        rhs[dof_id] += value;
+    }
+
+    Ok(sparse_solve_spd(&gram_matrix.re, &rhs)?)
+}
+
+// sample the previous solution's field and curl over a grid of points
+fn sample_previous_solution<BSpace: HierCurlBasisFnSpace>(
+    previous: &Domain,
+    solution: &[f64],
+    [xs, ys]: [&[f64]; 2],
+) -> (Vec<Vec<V2D>>, Vec<Vec<f64>>) {
+    let [x_min, x_max] = [xs[0], xs[xs.len() - 1]];
+    let [y_min, y_max] = [ys[0], ys[ys.len() - 1]];
+
+    // sample the whole grid at once if it falls within a single previous leaf-Elem
+    let center = Point::new((x_min + x_max) / 2.0, (y_min + y_max) / 2.0);
+    if let Some(leaf_id) = previous.mesh.leaf_elem_containing(&center) {
+        let [p0, p1] = previous.mesh.elem_diag_points(leaf_id).unwrap();
+        if p0.x <= x_min && p1.x >= x_max && p0.y <= y_min && p1.y >= y_max {
+            let LocalFields { field, curl, .. } =
+                sample_local_fields::<BSpace>(previous, leaf_id, [xs, ys], solution);
+            return (field, curl);
+        }
+    }
+
+    // otherwise, sample each point individually
+    let mut field = vec![vec![V2D::from([0.0, 0.0]); ys.len()]; xs.len()];
+    let mut curl = vec![vec![0.0; ys.len()]; xs.len()];
+    for (m, x) in xs.iter().enumerate() {
+        for (n, y) in ys.iter().enumerate() {
+            if let Some(leaf_id) = previous.mesh.leaf_elem_containing(&Point::new(*x, *y)) {
+                let point_fields =
+                    sample_local_fields::<BSpace>(previous, leaf_id, [&[*x], &[*y]], solution);
+                field[m][n] = point_fields.field[0][0];
+                curl[m][n] = point_fields.curl[0][0];
+            }
+        }
+    }
+
+    (field, curl)
+}
+
+fn check_inputs(
+    previous: &Domain,
+    solution: &[f64],
+    domain: &Domain,
+) -> Result<(), ProjectionError> {
+    for cc in [previous.cc, domain.cc] {
+        if cc != ContinuityCondition::HCurl {
+            return Err(ProjectionError::WrongContinuityCondition(cc));
+        }
+    }
+    if solution.len() != previous.dofs.len() {
+        return Err(ProjectionError::MismatchedSolutionSize(
+            previous.dofs.len(),
+            solution.len(),
+        ));
+    }
+    Ok(())
+}
+
+/// Error Type for Solution Transfer between Domains
+#[derive(Debug)]
+pub enum ProjectionError {
+    WrongContinuityCondition(ContinuityCondition),
+    MismatchedSolutionSize(usize, usize),
+    Sampling(GalerkinSamplingError),
+    Solver(SparseSolveError),
+}
+
+impl std::error::Error for ProjectionError {}
+
+impl fmt::Display for ProjectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::WrongContinuityCondition(cc) => write!(
+                f,
+                "Solution transfer is only supported for H(Curl) Domains (found {}); Cannot transfer solution!",
+                cc
+            ),
+            Self::MismatchedSolutionSize(dom_size, sol_size) => write!(
+                f,
+                "Domain size ({}) does not match solution size ({}); Cannot transfer solution!",
+                dom_size, sol_size
+            ),
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::Solver(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<GalerkinSamplingError> for ProjectionError {
+    fn from(err: GalerkinSamplingError) -> Self {
+        Self::Sampling(err)
+    }
+}
+
+impl From<SparseSolveError> for ProjectionError {
+    fn from(err: SparseSolveError) -> Self {
+        Self::Solver(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_domain::domain::mesh::{h_refinement::HRef, p_refinement::PRef, Mesh};
+    use crate::fem_problem::galerkin::galerkin_sample_gep_hcurl;
+    use crate::fem_problem::linalg::nalgebra_solve::{nalgebra_solve_gep, MAX_DENSE_SIZE};
+
+    fn mesh_a_solution(orders: [u8; 2]) -> (Mesh, Domain, Vec<f64>) {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders(orders).unwrap();
+        let domain = Domain::from_mesh(mesh.clone(), ContinuityCondition::HCurl);
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+        let eigen_pair = nalgebra_solve_gep(gep, 1.3).unwrap();
+        (mesh, domain, eigen_pair.vector)
+    }
+
+    // maximum difference between two solutions sampled over the leaf-Elems of the first Domain
+    fn max_field_difference(a: (&Domain, &[f64]), b: (&Domain, &[f64])) -> f64 {
+        let points = [-0.9, -0.3, 0.2, 0.8];
+        let mut max_diff: f64 = 0.0;
+        for elem in a.0.mesh.elems.iter().filter(|elem| !elem.has_children()) {
+            let [p0, p1] = a.0.mesh.elem_diag_points(elem.id).unwrap();
+            let (_, xs) = scale_gauss_quad_points(&points, p0.x, p1.x);
+            let (_, ys) = scale_gauss_quad_points(&points, p0.y, p1.y);
+            let fa = sample_local_fields::<HierPoly>(a.0, elem.id, [&xs, &ys], a.1);
+            let (fb, _) = sample_previous_solution::<HierPoly>(b.0, b.1, [&xs, &ys]);
+            for (fa_row, fb_row) in fa.field.iter().zip(fb.iter()) {
+                for (fa_value, fb_value) in fa_row.iter().zip(fb_row.iter()) {
+                    let diff = *fa_value + *fb_value * -1.0;
+                    max_diff = max_diff.max(V2D::dot(diff, diff).sqrt());
+                }
+            }
+        }
+        max_diff
+    }
+
+    #[test]
+    fn p_enrichment_embeds_exactly() {
+        let (mut mesh, domain, solution) = mesh_a_solution([3, 3]);
+        mesh.global_p_refinement(PRef::from(1, 2));
+        let enriched = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        let embedded = embed_solution(&domain, &solution, &enriched)
+            .unwrap()
+            .unwrap();
+        assert_eq!(embedded.len(), enriched.dofs.len());
+        assert!(max_field_difference((&enriched, &embedded), (&domain, &solution)) < 1e-12);
+    }
+
+    #[test]
+    fn h_refinement_projects() {
+        let (mut mesh, domain, solution) = mesh_a_solution([3, 3]);
+        mesh.h_refine_elems(vec![1, 2], HRef::T).unwrap();
+        mesh.h_refine_elems(vec![0], HRef::U(None)).unwrap();
+        let refined = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        assert!(embed_solution(&domain, &solution, &refined)
+            .unwrap()
+            .is_none());
+
+        // the refined Basis Space contains the previous solution, so the projection should be exact
+        let projected = transfer_solution::<HierPoly>(&domain, &solution, &refined, None).unwrap();
+        assert!(max_field_difference((&refined, &projected), (&domain, &solution)) < 1e-8);
+    }
+
+    #[test]
+    fn large_projection() {
+        let (mut mesh, domain, solution) = mesh_a_solution([3, 3]);
+        mesh.global_h_refinement(HRef::T);
+        mesh.global_h_refinement(HRef::T);
+        mesh.set_global_expansion_orders([4, 4]).unwrap();
+        let refined = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+
+        // too large for the dense solver
+        assert!(refined.dofs.len() > MAX_DENSE_SIZE);
+
+        let projected = project_solution::<HierPoly>(&domain, &solution, &refined, None).unwrap();
+        let scale = max_field_difference(
+            (&domain, &solution),
+            (&refined, &vec![0.0; refined.dofs.len()]),
+        );
+        assert!(max_field_difference((&refined, &projected), (&domain, &solution)) < 1e-6 * scale);
+    }
+
+    #[test]
+    fn projection_onto_coarser_space() {
+        let (_, fine_domain, solution) = mesh_a_solution([4, 4]);
+
+        // a lower order space does not contain the previous solution
+        let (_, coarse_domain, _) = mesh_a_solution([3, 3]);
+        assert!(embed_solution(&fine_domain, &solution, &coarse_domain)
+            .unwrap()
+            .is_none());
+
+        let projected =
+            transfer_solution::<HierPoly>(&fine_domain, &solution, &coarse_domain, None).unwrap();
+        let diff = max_field_difference((&coarse_domain, &projected), (&fine_domain, &solution));
+        let scale = max_field_difference(
+            (&fine_domain, &solution),
+            (&coarse_domain, &vec![0.0; coarse_domain.dofs.len()]),
+        );
+        assert!(diff < 1e-2 * scale);
+
+        // projecting a solution onto its own space is exact
+        let reprojected =
+            project_solution::<HierPoly>(&fine_domain, &solution, &fine_domain, None).unwrap();
+        assert!(reprojected
+            .iter()
+            .zip(solution.iter())
+            .all(|(a, b)| (a - b).abs() < 1e-8));
+    }
+
+    #[test]
+    fn transfer_errors() {
+        let (_, domain, solution) = mesh_a_solution([2, 2]);
+        assert!(matches!(
+            transfer_solution::<HierPoly>(&domain, &solution[1..], &domain, None),
+            Err(ProjectionError::MismatchedSolutionSize(_, _))
+        ));
+    }
+}
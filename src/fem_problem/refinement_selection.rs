@@ -0,0 +1,364 @@
+use crate::fem_domain::domain::{
+    dof::basis_spec::BasisDir,
+    mesh::{h_refinement::HRef, p_refinement::PRef},
+    Domain,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The resolution of a solution along one parametric direction of an `Elem`, as measured by its coefficient decay
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DirResolution {
+    /// The highest-order coefficients are negligible; no refinement is needed in this direction
+    Resolved,
+    /// The coefficients decay quickly; the solution should be p-refined in this direction
+    Smooth,
+    /// The coefficients decay slowly; the solution should be h-refined in this direction
+    Rough,
+}
+
+/// A recommended refinement for a leaf-`Elem`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefinementChoice {
+    /// h-refine the `Elem`
+    H(HRef),
+    /// p-refine the `Elem`
+    P(PRef),
+    /// The solution is resolved in both directions, or no further refinement is possible
+    None,
+}
+
+/// Chooses between h- and p-refinements based on the decay of a solution's hierarchical coefficients
+///
+/// Because the basis functions are hierarchical in `i` and `j`, the coefficients associated with the highest expansion order in a given direction
+/// measure how much of the solution was left unresolved by the lower orders. For each direction, the "decay" is the relative weight of those coefficients:
+///
+/// `d_u = sqrt(Σ_{top u-order} c² / Σ c²)`
+///
+/// (and likewise for `d_v`), where the sums are taken over the `BasisSpec`s on the `Elem` itself. Each direction is then classified as:
+/// * `Resolved`: if `d < resolution_threshold`
+/// * `Smooth`: if `d < smoothness_threshold`
+/// * `Rough`: otherwise
+///
+/// Directions with an expansion order less than 2 do not have enough coefficients to measure decay, and are classified as smooth.
+///
+/// The recommended refinement is:
+/// * An [HRef] across the rough directions (T, U, or V-type), if there are any
+/// * Otherwise, a [PRef] incrementing the expansion order along each smooth direction
+///
+/// If the recommended type of refinement is not possible (due to size or expansion order limits), the other type is used instead.
+/// The extended [HRef] variants are never recommended, as the coefficients do not indicate where the solution is rough within an `Elem`.
+#[derive(Clone, Copy, Debug)]
+pub struct RefinementSelector {
+    /// Upper bound on the decay for a direction to be considered smooth
+    pub smoothness_threshold: f64,
+    /// Upper bound on the decay for a direction to be considered resolved
+    pub resolution_threshold: f64,
+}
+
+impl Default for RefinementSelector {
+    fn default() -> Self {
+        Self {
+            smoothness_threshold: 0.1,
+            resolution_threshold: 1e-4,
+        }
+    }
+}
+
+impl RefinementSelector {
+    /// Create a selector with the given smoothness and resolution thresholds
+    pub fn new(smoothness_threshold: f64, resolution_threshold: f64) -> Self {
+        Self {
+            smoothness_threshold,
+            resolution_threshold,
+        }
+    }
+
+    /// Classify the resolution of a solution along the u and v directions of a leaf-`Elem`
+    pub fn classify(
+        &self,
+        domain: &Domain,
+        elem_id: usize,
+        solution: &[f64],
+    ) -> Result<[DirResolution; 2], RefinementSelectionError> {
+        Ok(
+            coefficient_decay(domain, elem_id, solution)?.map(|decay| match decay {
+                None => DirResolution::Smooth,
+                Some(d) if d < self.resolution_threshold => DirResolution::Resolved,
+                Some(d) if d < self.smoothness_threshold => DirResolution::Smooth,
+                Some(_) => DirResolution::Rough,
+            }),
+        )
+    }
+
+    /// Recommend a refinement for a leaf-`Elem`
+    ///
+    /// # Example
+    /// ```
+    /// use fem_2d::prelude::*;
+    ///
+    /// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+    /// mesh.set_global_expansion_orders([3, 3]).unwrap();
+    /// let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    ///
+    /// let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+    /// let eigen_pair = nalgebra_solve_gep(gep, 1.3).unwrap();
+    ///
+    /// let selector = RefinementSelector::default();
+    /// let mut mesh = domain.mesh.clone();
+    /// match selector.select(&domain, 0, &eigen_pair.vector).unwrap() {
+    ///     RefinementChoice::H(refinement) => mesh.h_refine_elems(vec![0], refinement).unwrap(),
+    ///     RefinementChoice::P(refinement) => mesh.p_refine_elems(vec![0], refinement).unwrap(),
+    ///     RefinementChoice::None => (),
+    /// }
+    /// ```
+    pub fn select(
+        &self,
+        domain: &Domain,
+        elem_id: usize,
+        solution: &[f64],
+    ) -> Result<RefinementChoice, RefinementSelectionError> {
+        let [u_res, v_res] = self.classify(domain, elem_id, solution)?;
+
+        let can_h_refine = domain.mesh.elem_is_h_refineable(elem_id).unwrap();
+        let [[_, u_room], [_, v_room]] = domain.mesh.elem_p_refinement_window(elem_id).unwrap();
+
+        let h_across = |u: bool, v: bool| match (u, v) {
+            (true, true) => Some(HRef::T),
+            (true, false) => Some(HRef::U(None)),
+            (false, true) => Some(HRef::V(None)),
+            (false, false) => None,
+        };
+        let p_along = |u: bool, v: bool| {
+            let refinement = PRef::from((u && u_room > 0) as i8, (v && v_room > 0) as i8);
+            (refinement != PRef::from(0, 0)).then_some(refinement)
+        };
+
+        let rough = h_across(u_res == DirResolution::Rough, v_res == DirResolution::Rough);
+        let unresolved = [u_res, v_res].map(|res| res != DirResolution::Resolved);
+
+        let choice = match rough {
+            Some(refinement) if can_h_refine => RefinementChoice::H(refinement),
+            Some(_) => p_along(unresolved[0], unresolved[1])
+                .map_or(RefinementChoice::None, RefinementChoice::P),
+            None => match p_along(unresolved[0], unresolved[1]) {
+                Some(refinement) => RefinementChoice::P(refinement),
+                None => match h_across(unresolved[0], unresolved[1]) {
+                    Some(refinement) if can_h_refine => RefinementChoice::H(refinement),
+                    _ => RefinementChoice::None,
+                },
+            },
+        };
+
+        Ok(choice)
+    }
+
+    /// Recommend a refinement for every leaf-`Elem` in the Domain
+    pub fn select_all(
+        &self,
+        domain: &Domain,
+        solution: &[f64],
+    ) -> Result<BTreeMap<usize, RefinementChoice>, RefinementSelectionError> {
+        domain
+            .mesh
+            .elems
+            .iter()
+            .filter(|elem| !elem.has_children())
+            .map(|elem| Ok((elem.id, self.select(domain, elem.id, solution)?)))
+            .collect()
+    }
+}
+
+/// The relative weight of a leaf-`Elem`'s highest-order solution coefficients along the u and v directions
+///
+/// A direction's decay is `None` if its expansion order is less than 2, or if the solution vanishes over the `Elem`
+pub fn coefficient_decay(
+    domain: &Domain,
+    elem_id: usize,
+    solution: &[f64],
+) -> Result<[Option<f64>; 2], RefinementSelectionError> {
+    if solution.len() != domain.dofs.len() {
+        return Err(RefinementSelectionError::MismatchedSolutionSize(
+            domain.dofs.len(),
+            solution.len(),
+        ));
+    }
+    let elem = domain
+        .mesh
+        .elems
+        .get(elem_id)
+        .ok_or(RefinementSelectionError::ElemDoesNotExist(elem_id))?;
+    if elem.has_children() {
+        return Err(RefinementSelectionError::ElemHasChildren(elem_id));
+    }
+
+    let orders = elem.poly_orders;
+    let mut total_sq = 0.0;
+    let mut top_sq = [0.0; 2];
+
+    for bs in domain.basis_specs[elem_id].iter() {
+        if let Some(dof_id) = bs.dof_id {
+            let c_sq = solution[dof_id].powi(2);
+            total_sq += c_sq;
+
+            let [u_top, v_top] = match bs.dir {
+                BasisDir::U => [bs.i + 1 == orders.ni, bs.j == orders.nj],
+                BasisDir::V => [bs.i == orders.ni, bs.j + 1 == orders.nj],
+                BasisDir::W => [bs.i == orders.ni, bs.j == orders.nj],
+            };
+            if u_top {
+                top_sq[0] += c_sq;
+            }
+            if v_top {
+                top_sq[1] += c_sq;
+            }
+        }
+    }
+
+    let decay = |order: u8, dir_top_sq: f64| {
+        (order >= 2 && total_sq > 0.0).then(|| (dir_top_sq / total_sq).sqrt())
+    };
+
+    Ok([decay(orders.ni, top_sq[0]), decay(orders.nj, top_sq[1])])
+}
+
+/// Error Type for Refinement Selection
+#[derive(Debug)]
+pub enum RefinementSelectionError {
+    MismatchedSolutionSize(usize, usize),
+    ElemDoesNotExist(usize),
+    ElemHasChildren(usize),
+}
+
+impl std::error::Error for RefinementSelectionError {}
+
+impl fmt::Display for RefinementSelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MismatchedSolutionSize(dom_size, sol_size) => write!(
+                f,
+                "Domain size ({}) does not match solution size ({}); Cannot select refinement!",
+                dom_size, sol_size
+            ),
+            Self::ElemDoesNotExist(elem_id) => write!(
+                f,
+                "Elem {} does not exist; Cannot select refinement!",
+                elem_id
+            ),
+            Self::ElemHasChildren(elem_id) => write!(
+                f,
+                "Elem {} is not a leaf; Cannot select refinement!",
+                elem_id
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::domain::{mesh::Mesh, ContinuityCondition};
+
+    // a solution with unit coefficients on all the DoFs satisfying some predicate
+    fn coefficients<F>(domain: &Domain, predicate: F) -> Vec<f64>
+    where
+        F: Fn(BasisDir, u8, u8) -> bool,
+    {
+        let mut solution = vec![0.0; domain.dofs.len()];
+        for bs in domain.basis_specs[0].iter() {
+            if predicate(bs.dir, bs.i, bs.j) {
+                solution[bs.dof_id.unwrap()] = 1.0;
+            }
+        }
+        solution
+    }
+
+    #[test]
+    fn anisotropic_selection() {
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders([4, 4]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let selector = RefinementSelector::default();
+
+        // only low order coefficients: p-refinement is not needed
+        let low_order = coefficients(&domain, |_, i, j| i < 3 && j < 3);
+        assert_eq!(
+            selector.classify(&domain, 0, &low_order).unwrap(),
+            [DirResolution::Resolved, DirResolution::Resolved]
+        );
+        assert_eq!(
+            selector.select(&domain, 0, &low_order).unwrap(),
+            RefinementChoice::None
+        );
+
+        // large coefficients at the highest u-order: h-refine in the u-direction
+        let rough_u = coefficients(&domain, |dir, i, j| {
+            j < 3
+                && match dir {
+                    BasisDir::U => i == 3,
+                    _ => i == 4,
+                }
+        });
+        assert_eq!(
+            selector.classify(&domain, 0, &rough_u).unwrap(),
+            [DirResolution::Rough, DirResolution::Resolved]
+        );
+        assert_eq!(
+            selector.select(&domain, 0, &rough_u).unwrap(),
+            RefinementChoice::H(HRef::U(None))
+        );
+
+        // small coefficients at the highest v-order: p-refine in the v-direction
+        let mut smooth_v = low_order.clone();
+        for bs in domain.basis_specs[0].iter() {
+            let top_v = match bs.dir {
+                BasisDir::V => bs.j == 3,
+                _ => bs.j == 4,
+            };
+            if top_v && bs.i < 3 {
+                smooth_v[bs.dof_id.unwrap()] = 0.01;
+            }
+        }
+        assert_eq!(
+            selector.classify(&domain, 0, &smooth_v).unwrap(),
+            [DirResolution::Resolved, DirResolution::Smooth]
+        );
+        assert_eq!(
+            selector.select(&domain, 0, &smooth_v).unwrap(),
+            RefinementChoice::P(PRef::from(0, 1))
+        );
+    }
+
+    #[test]
+    fn selection_fallbacks() {
+        let mut mesh = Mesh::unit();
+        mesh.set_global_expansion_orders([1, 1]).unwrap();
+        mesh.global_h_refinement(HRef::T);
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let selector = RefinementSelector::default();
+        let solution = vec![1.0; domain.dofs.len()];
+
+        // first order Elems are always p-refined
+        let choices = selector.select_all(&domain, &solution).unwrap();
+        assert_eq!(
+            choices.keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert!(choices
+            .values()
+            .all(|choice| *choice == RefinementChoice::P(PRef::from(1, 1))));
+
+        assert!(matches!(
+            selector.select(&domain, 0, &solution),
+            Err(RefinementSelectionError::ElemHasChildren(0))
+        ));
+        assert!(matches!(
+            selector.select(&domain, 5, &solution),
+            Err(RefinementSelectionError::ElemDoesNotExist(5))
+        ));
+        assert!(matches!(
+            selector.select(&domain, 1, &solution[1..]),
+            Err(RefinementSelectionError::MismatchedSolutionSize(_, _))
+        ));
+    }
+}
@@ -0,0 +1,495 @@
+use super::galerkin::{galerkin_sample_gep_hcurl, GalerkinSamplingError};
+use super::integration::HierCurlIntegral;
+use super::linalg::{sparse_matrix::SparseMatrix, EigenPair, GEP};
+use crate::fem_domain::basis::HierCurlBasisFnSpace;
+use crate::fem_domain::domain::{
+    mesh::{
+        element::{Element, Materials},
+        Mesh,
+    },
+    ContinuityCondition, Domain,
+};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Parameters controlling a [parametric_sweep]
+#[derive(Clone, Copy, Debug)]
+pub struct SweepSettings {
+    /// The eigenvalue targeted at the first parameter value (subsequent solves target a prediction from the tracked modes)
+    pub target_eigenvalue: f64,
+    /// The number of modes to track through the sweep
+    pub num_modes: usize,
+    /// Number of Gauss Legendre Quadrature Points to use for Galerkin Sampling. If `None`, the default values are used.
+    pub glq_grid_dim: Option<[usize; 2]>,
+}
+
+impl SweepSettings {
+    /// Create a set of sweep settings with a target eigenvalue and number of tracked modes
+    pub fn new(target_eigenvalue: f64, num_modes: usize) -> Self {
+        Self {
+            target_eigenvalue,
+            num_modes,
+            glq_grid_dim: None,
+        }
+    }
+}
+
+/// The tracked modes at one parameter value of a sweep
+pub struct SweepStep {
+    /// The value of the swept parameter
+    pub parameter: f64,
+    /// Number of Degrees of Freedom in the Domain
+    pub num_dofs: usize,
+    /// The tracked modes (in the same order at every step). Eigenvectors are B-normalized, and their signs are aligned with the previous step.
+    pub modes: Vec<EigenPair>,
+    /// The Modal Assurance Criterion between each tracked mode and its match at the previous step (1.0 at the first step)
+    pub overlaps: Vec<f64>,
+}
+
+/// The result of a [parametric_sweep]
+pub struct SweepResult {
+    /// The tracked modes at each parameter value
+    pub steps: Vec<SweepStep>,
+}
+
+impl SweepResult {
+    /// The eigenvalue of a tracked mode at each step of the sweep
+    pub fn tracked_eigenvalues(&self, mode_idx: usize) -> Vec<f64> {
+        self.steps
+            .iter()
+            .map(|step| step.modes[mode_idx].value)
+            .collect()
+    }
+
+    /// The smallest overlap found while matching modes between any two steps of the sweep
+    ///
+    /// Small values indicate that the parameter steps may be too large to track the modes reliably
+    pub fn min_overlap(&self) -> f64 {
+        self.steps
+            .iter()
+            .flat_map(|step| step.overlaps.iter().cloned())
+            .fold(1.0, f64::min)
+    }
+
+    /// Write the tracked eigenvalues to a CSV file
+    ///
+    /// Each row corresponds to one parameter value with the columns: `parameter, num_dofs, mode_0, ..., mode_n, mac_0, ..., mac_n`
+    pub fn write_csv(&self, path: impl AsRef<str>) -> std::io::Result<()> {
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+
+        let num_modes = self.steps.first().map_or(0, |step| step.modes.len());
+        let mut header = vec![String::from("parameter"), String::from("num_dofs")];
+        header.extend((0..num_modes).map(|mode_idx| format!("mode_{}", mode_idx)));
+        header.extend((0..num_modes).map(|mode_idx| format!("mac_{}", mode_idx)));
+        writeln!(writer, "{}", header.join(","))?;
+
+        for step in self.steps.iter() {
+            let mut row = vec![format!("{:e}", step.parameter), step.num_dofs.to_string()];
+            row.extend(step.modes.iter().map(|mode| format!("{:e}", mode.value)));
+            row.extend(step.overlaps.iter().map(|mac| format!("{:e}", mac)));
+            writeln!(writer, "{}", row.join(","))?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// Solve an eigenproblem over a range of parameter values, tracking a set of modes from one value to the next
+///
+/// At each parameter value:
+/// 1. a Mesh is constructed by the `mesh_builder` closure, and a [GEP] is sampled over its [Domain]
+/// 2. the `solver` computes a set of candidate Eigenpairs near the target eigenvalue
+/// 3. each tracked mode is matched with the candidate that has the largest Modal Assurance Criterion (MAC) with respect to its eigenvector at the previous step
+///
+/// Matching by eigenvector overlap (rather than by eigenvalue) keeps track of modes as they cross one another.
+/// At the first step, the `num_modes` candidates closest to the target are tracked (ordered by eigenvalue).
+/// Following steps target a linear prediction of the mean tracked eigenvalue.
+///
+/// The Mesh must have the same Degrees of Freedom at each parameter value (i.e., the same refinement state), so only geometry and materials should depend on the parameter.
+///
+/// # Arguments
+/// * `parameters`: The parameter values (in sweep order)
+/// * `mesh_builder`: A closure that produces the Mesh for a parameter value
+/// * `settings`: The parameters of the sweep
+/// * `solver`: A solver taking a [GEP] and target eigenvalue, and returning a set of candidate Eigenpairs (such as `nalgebra_solve_gep_n`)
+/// * Two [HierCurlIntegral]s: `AI` and `BI` must be specified as Generic Arguments. These are used to populate the A and B matrices respectively
+/// * A [HierCurlBasisFnSpace] `BSpace` must also be specified as a Generic Argument
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+///
+/// // vary the permittivity of the first Element
+/// let result = parametric_sweep::<HierPoly, CurlCurl, L2Inner, _, _, _>(
+///     &[1.0, 1.25, 1.5],
+///     |eps| {
+///         let mut mesh = mesh.clone();
+///         mesh.set_element_materials(|element| match element.id {
+///             0 => Materials::from_array([eps, 0.0, 1.0, 0.0]),
+///             _ => element.materials.clone(),
+///         });
+///         mesh
+///     },
+///     &SweepSettings::new(3.62, 1),
+///     |gep, target| nalgebra_solve_gep_n(gep, target, 4),
+/// )
+/// .unwrap();
+///
+/// let eigenvalues = result.tracked_eigenvalues(0);
+/// assert!(eigenvalues.windows(2).all(|pair| pair[1] < pair[0]));
+/// result.write_csv("./test_output/sweep_example.csv").unwrap();
+/// ```
+pub fn parametric_sweep<BSpace, AI, BI, F, S, E>(
+    parameters: &[f64],
+    mesh_builder: F,
+    settings: &SweepSettings,
+    solver: S,
+) -> Result<SweepResult, SweepError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    AI: HierCurlIntegral,
+    BI: HierCurlIntegral,
+    F: Fn(f64) -> Mesh,
+    S: Fn(GEP, f64) -> Result<Vec<EigenPair>, E>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    if parameters.is_empty() {
+        return Err(SweepError::NoParameters);
+    }
+    if settings.num_modes == 0 {
+        return Err(SweepError::NoModes);
+    }
+
+    let mut steps: Vec<SweepStep> = Vec::with_capacity(parameters.len());
+    let mut target = settings.target_eigenvalue;
+
+    for (step_idx, parameter) in parameters.iter().enumerate() {
+        let domain = Domain::from_mesh(mesh_builder(*parameter), ContinuityCondition::HCurl);
+        let num_dofs = domain.dofs.len();
+        if let Some(prev) = steps.last() {
+            if prev.num_dofs != num_dofs {
+                return Err(SweepError::MismatchedDofs(
+                    step_idx,
+                    prev.num_dofs,
+                    num_dofs,
+                ));
+            }
+        }
+
+        let gep = galerkin_sample_gep_hcurl::<BSpace, AI, BI>(&domain, settings.glq_grid_dim)?;
+        let b = gep.b.clone();
+        let candidates = solver(gep, target).map_err(|err| SweepError::Solver(err.into()))?;
+        if candidates.len() < settings.num_modes {
+            return Err(SweepError::NotEnoughCandidates(
+                step_idx,
+                settings.num_modes,
+                candidates.len(),
+            ));
+        }
+
+        let (mut modes, overlaps) = match steps.last() {
+            None => {
+                let mut modes: Vec<EigenPair> = nearest_candidates(candidates, target)
+                    .into_iter()
+                    .take(settings.num_modes)
+                    .collect();
+                modes.sort_by(|a, b| a.value.total_cmp(&b.value));
+                (modes, vec![1.0; settings.num_modes])
+            }
+            Some(prev) => match_modes(&prev.modes, candidates, &b)
+                .map_err(|mode_idx| SweepError::UnmatchedMode(step_idx, mode_idx))?,
+        };
+
+        for (mode_idx, mode) in modes.iter_mut().enumerate() {
+            mode.b_normalize(&b);
+
+            // align the sign with the previous step so the eigenvectors vary continuously
+            if let Some(prev) = steps.last() {
+                if b.bilinear_form(&mode.vector, &prev.modes[mode_idx].vector) < 0.0 {
+                    mode.vector.iter_mut().for_each(|x| *x = -*x);
+                }
+            }
+        }
+
+        target = predict_target(&modes, steps.last());
+        steps.push(SweepStep {
+            parameter: *parameter,
+            num_dofs,
+            modes,
+            overlaps,
+        });
+    }
+
+    Ok(SweepResult { steps })
+}
+
+/// Run a [parametric_sweep] where the parameter only affects the material properties of a fixed Mesh
+///
+/// The `materials` closure produces the new [Materials] of each [Element] for a parameter value (see [Mesh::set_element_materials])
+pub fn material_sweep<BSpace, AI, BI, F, S, E>(
+    mesh: &Mesh,
+    parameters: &[f64],
+    materials: F,
+    settings: &SweepSettings,
+    solver: S,
+) -> Result<SweepResult, SweepError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    AI: HierCurlIntegral,
+    BI: HierCurlIntegral,
+    F: Fn(f64, &Element) -> Materials,
+    S: Fn(GEP, f64) -> Result<Vec<EigenPair>, E>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    parametric_sweep::<BSpace, AI, BI, _, _, _>(
+        parameters,
+        |parameter| {
+            let mut mesh = mesh.clone();
+            mesh.set_element_materials(|element| materials(parameter, element));
+            mesh
+        },
+        settings,
+        solver,
+    )
+}
+
+// sort the candidates by their distance from the target
+fn nearest_candidates(mut candidates: Vec<EigenPair>, target: f64) -> Vec<EigenPair> {
+    candidates.sort_by(|a, b| {
+        (a.value - target)
+            .abs()
+            .total_cmp(&(b.value - target).abs())
+    });
+    candidates
+}
+
+// Greedily match each of the previous modes with a candidate: the (previous, candidate) pair with the largest MAC is matched first
+// Returns the index of the first previous mode that could not be matched (if any)
+fn match_modes(
+    prev_modes: &[EigenPair],
+    candidates: Vec<EigenPair>,
+    b: &SparseMatrix,
+) -> Result<(Vec<EigenPair>, Vec<f64>), usize> {
+    let mut pairs: Vec<(usize, usize, f64)> =
+        Vec::with_capacity(prev_modes.len() * candidates.len());
+    for (prev_idx, prev_mode) in prev_modes.iter().enumerate() {
+        for (cand_idx, candidate) in candidates.iter().enumerate() {
+            pairs.push((
+                prev_idx,
+                cand_idx,
+                prev_mode.modal_assurance_criterion(candidate, b),
+            ));
+        }
+    }
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut matches: Vec<Option<(usize, f64)>> = vec![None; prev_modes.len()];
+    let mut cand_used = vec![false; candidates.len()];
+    for (prev_idx, cand_idx, mac) in pairs {
+        if matches[prev_idx].is_none() && !cand_used[cand_idx] {
+            matches[prev_idx] = Some((cand_idx, mac));
+            cand_used[cand_idx] = true;
+        }
+    }
+
+    let mut candidates: Vec<Option<EigenPair>> = candidates.into_iter().map(Some).collect();
+    matches
+        .into_iter()
+        .enumerate()
+        .map(|(prev_idx, m)| match m {
+            Some((cand_idx, mac)) => Ok((candidates[cand_idx].take().unwrap(), mac)),
+            None => Err(prev_idx),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|matched| matched.into_iter().unzip())
+}
+
+// predict the mean tracked eigenvalue at the next step (linearly extrapolating from the previous step, if there is one)
+fn predict_target(modes: &[EigenPair], prev_step: Option<&SweepStep>) -> f64 {
+    let mean =
+        |modes: &[EigenPair]| modes.iter().map(|mode| mode.value).sum::<f64>() / modes.len() as f64;
+
+    match prev_step {
+        Some(prev) => 2.0 * mean(modes) - mean(&prev.modes),
+        None => mean(modes),
+    }
+}
+
+/// Error Type for parametric sweeps
+#[derive(Debug)]
+pub enum SweepError {
+    NoParameters,
+    NoModes,
+    MismatchedDofs(usize, usize, usize),
+    NotEnoughCandidates(usize, usize, usize),
+    UnmatchedMode(usize, usize),
+    Sampling(GalerkinSamplingError),
+    Solver(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl std::error::Error for SweepError {}
+
+impl fmt::Display for SweepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoParameters => write!(f, "No parameter values were provided; Cannot run sweep!"),
+            Self::NoModes => write!(f, "At least one mode must be tracked; Cannot run sweep!"),
+            Self::MismatchedDofs(step_idx, expected, found) => write!(
+                f,
+                "Domain at step {} has {} DoFs (expected {}); Cannot compare modes between steps!",
+                step_idx, found, expected
+            ),
+            Self::NotEnoughCandidates(step_idx, expected, found) => write!(
+                f,
+                "Solver returned {} Eigenpairs at step {} (at least {} are needed); Cannot track modes!",
+                found, step_idx, expected
+            ),
+            Self::UnmatchedMode(step_idx, mode_idx) => write!(
+                f,
+                "Mode {} could not be matched with a candidate at step {}; Cannot track modes!",
+                mode_idx, step_idx
+            ),
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::Solver(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<GalerkinSamplingError> for SweepError {
+    fn from(err: GalerkinSamplingError) -> Self {
+        Self::Sampling(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use crate::fem_problem::linalg::nalgebra_solve::nalgebra_solve_gep_n;
+    use std::f64::consts::PI;
+
+    // a single PEC-bounded rectangular cell: [0, width] x [0, 1]
+    fn rectangle_mesh(width: f64) -> Mesh {
+        let mut mesh = Mesh::from_json_str(&format!(
+            r#"{{
+                "Elements": [{{ "materials": [1.0, 0.0, 1.0, 0.0], "node_ids": [0, 1, 2, 3] }}],
+                "Nodes": [[0.0, 0.0], [{w}, 0.0], [0.0, 1.0], [{w}, 1.0]]
+            }}"#,
+            w = width
+        ));
+        mesh.set_global_expansion_orders([5, 5]).unwrap();
+        mesh
+    }
+
+    #[test]
+    fn mode_crossing() {
+        // the TE10 mode (π²/w²) crosses the TE01 mode (π²) as the width shrinks past 1.0
+        let widths = [1.2, 1.1, 0.9, 0.8];
+        let result = parametric_sweep::<HierPoly, CurlCurl, L2Inner, _, _, _>(
+            &widths,
+            rectangle_mesh,
+            &SweepSettings::new(8.5, 2),
+            |gep, target| nalgebra_solve_gep_n(gep, target, 6),
+        )
+        .unwrap();
+
+        let te_10 = result.tracked_eigenvalues(0);
+        let te_01 = result.tracked_eigenvalues(1);
+        for (step_idx, width) in widths.iter().enumerate() {
+            assert!((te_10[step_idx] - PI.powi(2) / width.powi(2)).abs() < 1e-3 * te_10[step_idx]);
+            assert!((te_01[step_idx] - PI.powi(2)).abs() < 1e-3 * te_01[step_idx]);
+        }
+        assert!(result.min_overlap() > 0.9);
+
+        result
+            .write_csv("./test_output/sweep_crossing.csv")
+            .unwrap();
+        let csv = std::fs::read_to_string("./test_output/sweep_crossing.csv").unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "parameter,num_dofs,mode_0,mode_1,mac_0,mac_1"
+        );
+        assert_eq!(lines.count(), widths.len());
+    }
+
+    #[test]
+    fn material_sweep_monotonic() {
+        let mut mesh = Mesh::from_file("./test_input/test_mesh_a.json").unwrap();
+        mesh.set_global_expansion_orders([3, 3]).unwrap();
+
+        let result = material_sweep::<HierPoly, CurlCurl, L2Inner, _, _, _>(
+            &mesh,
+            &[1.0, 1.2, 1.4, 1.6],
+            |scale, element| Materials {
+                eps_rel: element.materials.eps_rel * scale,
+                mu_rel: element.materials.mu_rel,
+            },
+            &SweepSettings::new(3.62, 2),
+            |gep, target| nalgebra_solve_gep_n(gep, target, 4),
+        )
+        .unwrap();
+
+        // uniformly scaling the permittivity scales the eigenvalues by its inverse
+        for mode_idx in 0..2 {
+            let eigenvalues = result.tracked_eigenvalues(mode_idx);
+            for (step, eigenvalue) in result.steps.iter().zip(eigenvalues.iter()) {
+                assert!(
+                    (eigenvalue * step.parameter - eigenvalues[0]).abs() < 1e-8 * eigenvalues[0]
+                );
+            }
+        }
+        assert!(result.min_overlap() > 1.0 - 1e-8);
+    }
+
+    #[test]
+    fn sweep_errors() {
+        let settings = SweepSettings::new(3.62, 2);
+        let solver = |gep, target| nalgebra_solve_gep_n(gep, target, 1);
+
+        assert!(matches!(
+            parametric_sweep::<HierPoly, CurlCurl, L2Inner, _, _, _>(
+                &[],
+                |_| Mesh::unit(),
+                &settings,
+                solver
+            ),
+            Err(SweepError::NoParameters)
+        ));
+        assert!(matches!(
+            parametric_sweep::<HierPoly, CurlCurl, L2Inner, _, _, _>(
+                &[1.0],
+                |_| rectangle_mesh(1.0),
+                &settings,
+                solver
+            ),
+            Err(SweepError::NotEnoughCandidates(0, 2, 1))
+        ));
+
+        // the second mode has no candidate left once the first is matched
+        let mut b = SparseMatrix::new(2);
+        b.insert([0, 0], 1.0);
+        b.insert([1, 1], 1.0);
+        let prev_modes = [
+            EigenPair {
+                value: 1.0,
+                vector: vec![1.0, 0.0],
+            },
+            EigenPair {
+                value: 2.0,
+                vector: vec![0.0, 1.0],
+            },
+        ];
+        let candidates = vec![EigenPair {
+            value: 1.1,
+            vector: vec![1.0, 0.1],
+        }];
+        assert!(matches!(match_modes(&prev_modes, candidates, &b), Err(1)));
+    }
+}
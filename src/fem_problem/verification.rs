@@ -0,0 +1,785 @@
+use super::galerkin::{galerkin_sample_gep_hcurl, GalerkinSamplingError};
+use super::integration::HierCurlIntegral;
+use super::linalg::{EigenPair, GEP};
+use crate::fem_domain::basis::HierCurlBasisFnSpace;
+use crate::fem_domain::domain::{
+    mesh::{
+        element::Materials,
+        h_refinement::HRef,
+        p_refinement::PRefError,
+        space::{Point, V2D},
+        Mesh,
+    },
+    ContinuityCondition, Domain,
+};
+use std::f64::consts::PI;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+// number of samples of the dispersion function used to bracket the eigenvalues of a layered cavity
+const NUM_DISPERSION_SAMPLES: usize = 4000;
+
+/// A slab of homogeneous material in a [RectangularCavity]
+#[derive(Clone, Debug)]
+pub struct CavityLayer {
+    /// Width of the layer along the x-axis
+    pub thickness: f64,
+    /// Material parameters of the layer (only the real parts are used)
+    pub materials: Materials,
+}
+
+/// A PEC-bounded rectangular cavity over `[0, width] x [0, height]` with closed-form (or semi-analytic) resonant modes
+///
+/// The cavity is made up of one or more homogeneous layers stacked along the x-axis.
+/// Eigenvalues follow the normalized convention used throughout the crate: `∇ × (μ⁻¹ ∇ × E) = λ ε E`, where `λ = ω²`.
+///
+/// * Homogeneous cavities have the closed-form eigenvalues: `λ_mn = ((mπ/a)² + (nπ/b)²) / (εμ)`
+/// * Layered cavities are solved with a transverse resonance (transfer matrix) condition, where the eigenvalues are bracketed and refined by bisection
+#[derive(Clone, Debug)]
+pub struct RectangularCavity {
+    /// Height of the cavity along the y-axis
+    pub height: f64,
+    /// Material layers from `x = 0` to `x = width`
+    pub layers: Vec<CavityLayer>,
+}
+
+/// A resonant mode of a [RectangularCavity]
+#[derive(Clone, Copy, Debug)]
+pub struct CavityMode {
+    /// Mode index along the x-axis (the number of half-wavelengths in a homogeneous cavity)
+    pub m: usize,
+    /// Number of half-wavelengths along the y-axis
+    pub n: usize,
+    /// Eigenvalue: `λ = ω²`
+    pub eigenvalue: f64,
+}
+
+impl RectangularCavity {
+    /// A cavity filled with a single material
+    pub fn homogeneous(width: f64, height: f64, materials: Materials) -> Self {
+        Self::layered(
+            height,
+            vec![CavityLayer {
+                thickness: width,
+                materials,
+            }],
+        )
+    }
+
+    /// A cavity made up of material layers stacked along the x-axis
+    pub fn layered(height: f64, layers: Vec<CavityLayer>) -> Self {
+        assert!(height > 0.0, "Cavity height must be positive!");
+        assert!(!layers.is_empty(), "Cavity must have at least one layer!");
+        assert!(
+            layers.iter().all(|layer| layer.thickness > 0.0),
+            "Cavity layer thicknesses must be positive!"
+        );
+
+        Self { height, layers }
+    }
+
+    /// Width of the cavity along the x-axis
+    pub fn width(&self) -> f64 {
+        self.layers.iter().map(|layer| layer.thickness).sum()
+    }
+
+    /// Whether all layers have the same material parameters
+    pub fn is_homogeneous(&self) -> bool {
+        let first = &self.layers[0].materials;
+        self.layers.iter().all(|layer| {
+            layer.materials.eps_rel == first.eps_rel && layer.materials.mu_rel == first.mu_rel
+        })
+    }
+
+    /// Construct a Mesh of the cavity with one `Element` per layer
+    ///
+    /// All Elems have an expansion order of 1 in both directions
+    pub fn mesh(&self) -> Mesh {
+        let num_layers = self.layers.len();
+        let mut x_coords = vec![0.0];
+        for layer in self.layers.iter() {
+            x_coords.push(x_coords.last().unwrap() + layer.thickness);
+        }
+
+        let nodes: Vec<String> = [0.0, self.height]
+            .iter()
+            .flat_map(|y| x_coords.iter().map(move |x| format!("[{:e}, {:e}]", x, y)))
+            .collect();
+        let elements: Vec<String> = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| {
+                format!(
+                    "{{\"materials\": [{:e}, {:e}, {:e}, {:e}], \"node_ids\": [{}, {}, {}, {}]}}",
+                    layer.materials.eps_rel.re,
+                    layer.materials.eps_rel.im,
+                    layer.materials.mu_rel.re,
+                    layer.materials.mu_rel.im,
+                    i,
+                    i + 1,
+                    num_layers + 1 + i,
+                    num_layers + 2 + i
+                )
+            })
+            .collect();
+
+        Mesh::from_json_str(&format!(
+            "{{\"Elements\": [{}], \"Nodes\": [{}]}}",
+            elements.join(", "),
+            nodes.join(", ")
+        ))
+    }
+
+    /// All modes with eigenvalues less than or equal to `max_eigenvalue` (sorted by eigenvalue)
+    pub fn modes(&self, max_eigenvalue: f64) -> Vec<CavityMode> {
+        let mut modes = if self.is_homogeneous() {
+            self.homogeneous_modes(max_eigenvalue)
+        } else {
+            self.layered_modes(max_eigenvalue)
+        };
+        modes.sort_by(|a, b| a.eigenvalue.partial_cmp(&b.eigenvalue).unwrap());
+        modes
+    }
+
+    /// The `count` modes with the smallest eigenvalues
+    pub fn lowest_modes(&self, count: usize) -> Vec<CavityMode> {
+        // start from an estimate of the fundamental eigenvalue, widening the search until enough modes are found
+        let mut max_eigenvalue =
+            self.max_inverse_eps_mu() * (PI / self.width().min(self.height)).powi(2);
+        loop {
+            let modes = self.modes(max_eigenvalue);
+            if modes.len() >= count {
+                return modes.into_iter().take(count).collect();
+            }
+            max_eigenvalue *= 2.0;
+        }
+    }
+
+    /// The electric field of a mode at a point in the cavity (with an arbitrary scale)
+    ///
+    /// The field is derived from the z-directed magnetic field: `Hz = X(x) cos(nπy/b)`, such that: `E ∝ ε⁻¹ (∂Hz/∂y, -∂Hz/∂x)`
+    pub fn field(&self, mode: &CavityMode, point: Point) -> V2D {
+        let ky = mode.n as f64 * PI / self.height;
+        let [x_val, u_val] = self.transverse_state(mode.eigenvalue, ky, point.x);
+        let eps = self.layer_at(point.x).materials.eps_rel.re;
+
+        V2D::from([
+            -ky * x_val * (ky * point.y).sin() / eps,
+            -u_val * (ky * point.y).cos(),
+        ])
+    }
+
+    fn homogeneous_modes(&self, max_eigenvalue: f64) -> Vec<CavityMode> {
+        let materials = &self.layers[0].materials;
+        let eps_mu = materials.eps_rel.re * materials.mu_rel.re;
+        let [kx, ky] = [PI / self.width(), PI / self.height];
+
+        let mut modes = Vec::new();
+        for n in 0.. {
+            if (n as f64 * ky).powi(2) / eps_mu > max_eigenvalue {
+                break;
+            }
+            for m in 0.. {
+                let eigenvalue = ((m as f64 * kx).powi(2) + (n as f64 * ky).powi(2)) / eps_mu;
+                if eigenvalue > max_eigenvalue {
+                    break;
+                }
+                if m + n > 0 {
+                    modes.push(CavityMode { m, n, eigenvalue });
+                }
+            }
+        }
+        modes
+    }
+
+    fn layered_modes(&self, max_eigenvalue: f64) -> Vec<CavityMode> {
+        let max_eps_mu = self
+            .layers
+            .iter()
+            .map(|layer| layer.materials.eps_rel.re * layer.materials.mu_rel.re)
+            .fold(0.0, f64::max);
+
+        let mut modes = Vec::new();
+        for n in 0.. {
+            let ky = n as f64 * PI / self.height;
+            if ky.powi(2) / max_eps_mu > max_eigenvalue {
+                break;
+            }
+
+            // the static solution (n = 0, λ = 0) is excluded by starting the scan just above zero
+            let dispersion =
+                |eigenvalue: f64| self.transverse_state(eigenvalue, ky, self.width())[1];
+            let step = max_eigenvalue / NUM_DISPERSION_SAMPLES as f64;
+            let mut lower = step * 1e-3;
+            let mut f_lower = dispersion(lower);
+            let mut m = if n == 0 { 1 } else { 0 };
+
+            for sample in 1..=NUM_DISPERSION_SAMPLES {
+                let upper = step * sample as f64;
+                let f_upper = dispersion(upper);
+                if f_lower * f_upper <= 0.0 && f_lower != f_upper {
+                    modes.push(CavityMode {
+                        m,
+                        n,
+                        eigenvalue: bisect(&dispersion, lower, upper),
+                    });
+                    m += 1;
+                }
+                lower = upper;
+                f_lower = f_upper;
+            }
+        }
+        modes
+    }
+
+    // Propagate the transverse state [X, X'/ε] from x = 0 (where X = 1 and X' = 0) to the x-coordinate `x`
+    fn transverse_state(&self, eigenvalue: f64, ky: f64, x: f64) -> [f64; 2] {
+        let mut state = [1.0, 0.0];
+        let mut x_start = 0.0;
+
+        for layer in self.layers.iter() {
+            let eps = layer.materials.eps_rel.re;
+            let mu = layer.materials.mu_rel.re;
+            let q = eigenvalue * eps * mu - ky.powi(2);
+            let d = (x - x_start).min(layer.thickness);
+
+            // c = cos(√q d), s = sin(√q d) / √q (continued analytically for q <= 0)
+            let (c, s) = if q > 0.0 {
+                ((q.sqrt() * d).cos(), (q.sqrt() * d).sin() / q.sqrt())
+            } else if q < 0.0 {
+                (
+                    ((-q).sqrt() * d).cosh(),
+                    ((-q).sqrt() * d).sinh() / (-q).sqrt(),
+                )
+            } else {
+                (1.0, d)
+            };
+
+            let [x_val, u_val] = state;
+            state = [
+                c * x_val + s * eps * u_val,
+                -q * s * x_val / eps + c * u_val,
+            ];
+
+            x_start += layer.thickness;
+            if x <= x_start {
+                break;
+            }
+        }
+
+        state
+    }
+
+    fn layer_at(&self, x: f64) -> &CavityLayer {
+        let mut x_end = 0.0;
+        for layer in self.layers.iter() {
+            x_end += layer.thickness;
+            if x <= x_end {
+                return layer;
+            }
+        }
+        self.layers.last().unwrap()
+    }
+
+    fn max_inverse_eps_mu(&self) -> f64 {
+        let eps_mu: Vec<f64> = self
+            .layers
+            .iter()
+            .map(|layer| layer.materials.eps_rel.re * layer.materials.mu_rel.re)
+            .collect();
+        1.0 / eps_mu.iter().cloned().fold(f64::MAX, f64::min)
+    }
+}
+
+fn bisect<F: Fn(f64) -> f64>(f: &F, mut lower: f64, mut upper: f64) -> f64 {
+    let mut f_lower = f(lower);
+    for _ in 0..200 {
+        let mid = 0.5 * (lower + upper);
+        let f_mid = f(mid);
+        if f_mid == 0.0 || (upper - lower) <= 1e-15 * upper {
+            return mid;
+        }
+        if f_lower * f_mid < 0.0 {
+            upper = mid;
+        } else {
+            lower = mid;
+            f_lower = f_mid;
+        }
+    }
+    0.5 * (lower + upper)
+}
+
+/// One solve in a convergence study
+#[derive(Clone, Copy, Debug)]
+pub struct ConvergenceSample {
+    /// Number of Degrees of Freedom in the Domain
+    pub num_dofs: usize,
+    /// Number of global h-refinements applied to the initial Mesh
+    pub h_level: usize,
+    /// Maximum expansion orders over the Mesh
+    pub expansion_orders: [u8; 2],
+    /// Computed eigenvalue
+    pub eigenvalue: f64,
+    /// Relative error with respect to the reference eigenvalue
+    pub relative_error: f64,
+}
+
+/// The results of an [h_convergence_study] or [p_convergence_study]
+#[derive(Clone, Debug)]
+pub struct ConvergenceStudy {
+    /// The reference (exact) eigenvalue
+    pub reference_eigenvalue: f64,
+    /// One sample per solve (in order of increasing refinement)
+    pub samples: Vec<ConvergenceSample>,
+}
+
+impl ConvergenceStudy {
+    /// The algebraic convergence rate `r`: a least-squares fit of `error ~ C N^r`, where `N` is the number of DoFs
+    ///
+    /// Returns `None` if fewer than two samples have a non-zero error
+    pub fn algebraic_rate(&self) -> Option<f64> {
+        self.fit_log_error(|num_dofs| num_dofs.ln())
+    }
+
+    /// The exponential convergence rate `r`: a least-squares fit of `error ~ C exp(r N^½)`, where `N` is the number of DoFs
+    ///
+    /// This is the expected form of convergence for p-refinement in 2D. Returns `None` if fewer than two samples have a non-zero error
+    pub fn exponential_rate(&self) -> Option<f64> {
+        self.fit_log_error(|num_dofs| num_dofs.sqrt())
+    }
+
+    /// Write the samples to a CSV file with the columns: `num_dofs, h_level, order_u, order_v, eigenvalue, relative_error`
+    pub fn write_csv(&self, path: impl AsRef<str>) -> std::io::Result<()> {
+        let output_file = File::create(path.as_ref())?;
+        let mut writer = BufWriter::new(&output_file);
+
+        writeln!(
+            writer,
+            "num_dofs,h_level,order_u,order_v,eigenvalue,relative_error"
+        )?;
+        for sample in self.samples.iter() {
+            writeln!(
+                writer,
+                "{},{},{},{},{:e},{:e}",
+                sample.num_dofs,
+                sample.h_level,
+                sample.expansion_orders[0],
+                sample.expansion_orders[1],
+                sample.eigenvalue,
+                sample.relative_error
+            )?;
+        }
+
+        writer.flush()
+    }
+
+    // slope of a least-squares fit of ln(error) against a function of the DoF count
+    fn fit_log_error<F: Fn(f64) -> f64>(&self, abscissa: F) -> Option<f64> {
+        let points: Vec<[f64; 2]> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.relative_error > 0.0)
+            .map(|sample| [abscissa(sample.num_dofs as f64), sample.relative_error.ln()])
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let [mean_x, mean_y] = points
+            .iter()
+            .fold([0.0, 0.0], |[sx, sy], [x, y]| [sx + x / n, sy + y / n]);
+        let [sxy, sxx] = points.iter().fold([0.0, 0.0], |[sxy, sxx], [x, y]| {
+            [
+                sxy + (x - mean_x) * (y - mean_y),
+                sxx + (x - mean_x).powi(2),
+            ]
+        });
+
+        Some(sxy / sxx)
+    }
+}
+
+/// Compute the error in an eigenvalue under successive uniform h-refinements of a Mesh
+///
+/// The initial Mesh (with its current expansion orders) is solved, followed by `num_refinements` applications of [HRef::T] to all leaf-`Elem`s
+///
+/// # Arguments
+/// * `mesh`: The initial Mesh
+/// * `reference_eigenvalue`: The exact eigenvalue (also used as the solver's target)
+/// * `num_refinements`: The number of uniform h-refinements
+/// * `solver`: A Generalized Eigenproblem solver taking a [GEP] and target eigenvalue (such as `nalgebra_solve_gep`)
+/// * Two [HierCurlIntegral]s: `AI` and `BI` must be specified as Generic Arguments. These are used to populate the A and B matrices respectively
+/// * A [HierCurlBasisFnSpace] `BSpace` must also be specified as a Generic Argument
+///
+/// # Example
+/// ```
+/// use fem_2d::prelude::*;
+///
+/// let cavity = RectangularCavity::homogeneous(1.0, 0.6, Materials::default());
+/// let reference = cavity.lowest_modes(1)[0].eigenvalue;
+///
+/// let mut mesh = cavity.mesh();
+/// mesh.set_global_expansion_orders([3, 3]).unwrap();
+///
+/// let study = h_convergence_study::<HierPoly, CurlCurl, L2Inner, _, _>(&mesh, reference, 2, nalgebra_solve_gep).unwrap();
+///
+/// assert!(study.algebraic_rate().unwrap() < -2.0);
+/// study.write_csv("./test_output/h_convergence_example.csv").unwrap();
+/// ```
+pub fn h_convergence_study<BSpace, AI, BI, S, E>(
+    mesh: &Mesh,
+    reference_eigenvalue: f64,
+    num_refinements: usize,
+    solver: S,
+) -> Result<ConvergenceStudy, ConvergenceError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    AI: HierCurlIntegral,
+    BI: HierCurlIntegral,
+    S: Fn(GEP, f64) -> Result<EigenPair, E>,
+    E: Into<Box<dyn std::error::Error>>,
+{
+    let mut mesh = mesh.clone();
+    let mut samples = Vec::with_capacity(num_refinements + 1);
+
+    for h_level in 0..=num_refinements {
+        if h_level > 0 {
+            mesh.global_h_refinement(HRef::T);
+        }
+        let (domain_mesh, sample) =
+            convergence_sample::<BSpace, AI, BI, _, _>(mesh, reference_eigenvalue, &solver)?;
+        samples.push(ConvergenceSample { h_level, ..sample });
+        mesh = domain_mesh;
+    }
+
+    Ok(ConvergenceStudy {
+        reference_eigenvalue,
+        samples,
+    })
+}
+
+/// Compute the error in an eigenvalue over a sequence of uniform expansion orders on a Mesh
+///
+/// The Mesh's refinement state is retained, while the expansion orders of all `Elem`s are set to `[p, p]` for each `p` in `expansion_orders`
+///
+/// # Arguments
+/// * `mesh`: The Mesh
+/// * `reference_eigenvalue`: The exact eigenvalue (also used as the solver's target)
+/// * `expansion_orders`: The sequence of expansion orders
+/// * `solver`: A Generalized Eigenproblem solver taking a [GEP] and target eigenvalue (such as `nalgebra_solve_gep`)
+/// * Two [HierCurlIntegral]s: `AI` and `BI` must be specified as Generic Arguments. These are used to populate the A and B matrices respectively
+/// * A [HierCurlBasisFnSpace] `BSpace` must also be specified as a Generic Argument
+pub fn p_convergence_study<BSpace, AI, BI, S, E>(
+    mesh: &Mesh,
+    reference_eigenvalue: f64,
+    expansion_orders: &[u8],
+    solver: S,
+) -> Result<ConvergenceStudy, ConvergenceError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    AI: HierCurlIntegral,
+    BI: HierCurlIntegral,
+    S: Fn(GEP, f64) -> Result<EigenPair, E>,
+    E: Into<Box<dyn std::error::Error>>,
+{
+    if expansion_orders.is_empty() {
+        return Err(ConvergenceError::NoSamples);
+    }
+
+    let mut samples = Vec::with_capacity(expansion_orders.len());
+    for order in expansion_orders.iter() {
+        let mut mesh = mesh.clone();
+        mesh.set_global_expansion_orders([*order, *order])?;
+        let (_, sample) =
+            convergence_sample::<BSpace, AI, BI, _, _>(mesh, reference_eigenvalue, &solver)?;
+        samples.push(sample);
+    }
+
+    Ok(ConvergenceStudy {
+        reference_eigenvalue,
+        samples,
+    })
+}
+
+// Solve over a Mesh and compare the eigenvalue to the reference (returning the Mesh for further refinement)
+fn convergence_sample<BSpace, AI, BI, S, E>(
+    mesh: Mesh,
+    reference_eigenvalue: f64,
+    solver: &S,
+) -> Result<(Mesh, ConvergenceSample), ConvergenceError>
+where
+    BSpace: HierCurlBasisFnSpace,
+    AI: HierCurlIntegral,
+    BI: HierCurlIntegral,
+    S: Fn(GEP, f64) -> Result<EigenPair, E>,
+    E: Into<Box<dyn std::error::Error>>,
+{
+    let expansion_orders = mesh.max_expansion_orders();
+    let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+    let gep = galerkin_sample_gep_hcurl::<BSpace, AI, BI>(&domain, None)?;
+    let eigen_pair =
+        solver(gep, reference_eigenvalue).map_err(|err| ConvergenceError::Solver(err.into()))?;
+
+    Ok((
+        domain.mesh,
+        ConvergenceSample {
+            num_dofs: domain.dofs.len(),
+            h_level: 0,
+            expansion_orders,
+            eigenvalue: eigen_pair.value,
+            relative_error: ((eigen_pair.value - reference_eigenvalue) / reference_eigenvalue)
+                .abs(),
+        },
+    ))
+}
+
+/// Error Type for convergence studies
+#[derive(Debug)]
+pub enum ConvergenceError {
+    NoSamples,
+    Sampling(GalerkinSamplingError),
+    Solver(Box<dyn std::error::Error>),
+    PRefinement(PRefError),
+}
+
+impl std::error::Error for ConvergenceError {}
+
+impl fmt::Display for ConvergenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSamples => write!(
+                f,
+                "No expansion orders were provided; Cannot run convergence study!"
+            ),
+            Self::Sampling(err) => write!(f, "{}", err),
+            Self::Solver(err) => write!(f, "{}", err),
+            Self::PRefinement(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<GalerkinSamplingError> for ConvergenceError {
+    fn from(err: GalerkinSamplingError) -> Self {
+        Self::Sampling(err)
+    }
+}
+
+impl From<PRefError> for ConvergenceError {
+    fn from(err: PRefError) -> Self {
+        Self::PRefinement(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
+    use crate::fem_domain::domain::fields::sample_point;
+    use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    use crate::fem_problem::linalg::nalgebra_solve::{nalgebra_solve_gep, nalgebra_solve_gep_n};
+
+    fn two_layer_cavity() -> RectangularCavity {
+        RectangularCavity::layered(
+            0.6,
+            vec![
+                CavityLayer {
+                    thickness: 0.4,
+                    materials: Materials::default(),
+                },
+                CavityLayer {
+                    thickness: 0.6,
+                    materials: Materials::from_array([3.0, 0.0, 1.5, 0.0]),
+                },
+            ],
+        )
+    }
+
+    fn solve(cavity: &RectangularCavity, order: u8, target: f64) -> (Domain, EigenPair) {
+        let mut mesh = cavity.mesh();
+        mesh.set_global_expansion_orders([order, order]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+        let eigen_pair = nalgebra_solve_gep(gep, target).unwrap();
+        (domain, eigen_pair)
+    }
+
+    // compare the lowest modes of a cavity with the full spectrum of a single solve
+    fn assert_lowest_eigenvalues(cavity: &RectangularCavity, order: u8, num_modes: usize) {
+        let mut mesh = cavity.mesh();
+        mesh.set_global_expansion_orders([order, order]).unwrap();
+        let domain = Domain::from_mesh(mesh, ContinuityCondition::HCurl);
+        let gep = galerkin_sample_gep_hcurl::<HierPoly, CurlCurl, L2Inner>(&domain, None).unwrap();
+        let spectrum = nalgebra_solve_gep_n(gep, 0.0, domain.dofs.len()).unwrap();
+
+        for mode in cavity.lowest_modes(num_modes).iter() {
+            let nearest = spectrum
+                .iter()
+                .map(|pair| pair.value)
+                .min_by(|a, b| {
+                    (a - mode.eigenvalue)
+                        .abs()
+                        .partial_cmp(&(b - mode.eigenvalue).abs())
+                        .unwrap()
+                })
+                .unwrap();
+            assert!(
+                (nearest - mode.eigenvalue).abs() < 1e-5 * mode.eigenvalue,
+                "{:?}: {}",
+                mode,
+                nearest
+            );
+        }
+    }
+
+    #[test]
+    fn homogeneous_eigenvalues() {
+        let cavity =
+            RectangularCavity::homogeneous(1.0, 0.6, Materials::from_array([2.0, 0.0, 1.0, 0.0]));
+        let modes = cavity.lowest_modes(4);
+
+        assert_eq!([modes[0].m, modes[0].n], [1, 0]);
+        assert!((modes[0].eigenvalue - PI.powi(2) / 2.0).abs() < 1e-12);
+        assert_lowest_eigenvalues(&cavity, 8, 4);
+    }
+
+    #[test]
+    fn layered_eigenvalues() {
+        let cavity = two_layer_cavity();
+        assert!(!cavity.is_homogeneous());
+        assert_lowest_eigenvalues(&cavity, 8, 5);
+    }
+
+    #[test]
+    fn transfer_matrix_matches_closed_form() {
+        // a homogeneous cavity split into two identical layers exercises the root-finder
+        let materials = Materials::from_array([1.5, 0.0, 2.0, 0.0]);
+        let split = RectangularCavity::layered(
+            0.7,
+            vec![
+                CavityLayer {
+                    thickness: 0.3,
+                    materials: materials.clone(),
+                },
+                CavityLayer {
+                    thickness: 0.8,
+                    materials: materials.clone(),
+                },
+            ],
+        );
+        let single = RectangularCavity::homogeneous(1.1, 0.7, materials);
+
+        let max_eigenvalue = 40.0;
+        let layered = split.layered_modes(max_eigenvalue);
+        let closed_form = single.homogeneous_modes(max_eigenvalue);
+        assert_eq!(layered.len(), closed_form.len());
+
+        for mode in closed_form.iter() {
+            let matched = layered
+                .iter()
+                .find(|other| other.m == mode.m && other.n == mode.n)
+                .unwrap();
+            assert!((matched.eigenvalue - mode.eigenvalue).abs() < 1e-10 * mode.eigenvalue);
+        }
+    }
+
+    #[test]
+    fn layered_field() {
+        let cavity = two_layer_cavity();
+        let mode = cavity
+            .lowest_modes(5)
+            .into_iter()
+            .find(|mode| mode.n == 1)
+            .unwrap();
+        let (domain, eigen_pair) = solve(&cavity, 8, mode.eigenvalue);
+
+        let points: Vec<Point> = [[0.13, 0.21], [0.37, 0.45], [0.55, 0.11], [0.82, 0.52]]
+            .iter()
+            .map(|xy| Point::from(*xy))
+            .collect();
+        let fem: Vec<V2D> = points
+            .iter()
+            .map(|p| {
+                sample_point::<HierPoly>(&domain, &eigen_pair.vector, p)
+                    .unwrap()
+                    .field
+            })
+            .collect();
+        let exact: Vec<V2D> = points.iter().map(|p| cavity.field(&mode, *p)).collect();
+
+        // the fields agree up to a scale factor
+        let scale = fem
+            .iter()
+            .zip(exact.iter())
+            .map(|(f, e)| f.dot_with(e))
+            .sum::<f64>()
+            / exact.iter().map(|e| e.dot_with(e)).sum::<f64>();
+        let max_exact = exact
+            .iter()
+            .map(|e| e.dot_with(e).sqrt())
+            .fold(0.0, f64::max);
+        for (f, e) in fem.iter().zip(exact.iter()) {
+            assert!((f[0] - scale * e[0]).abs() < 1e-4 * scale.abs() * max_exact);
+            assert!((f[1] - scale * e[1]).abs() < 1e-4 * scale.abs() * max_exact);
+        }
+    }
+
+    #[test]
+    fn h_convergence_rate() {
+        let cavity = RectangularCavity::homogeneous(1.0, 0.6, Materials::default());
+        let reference = cavity.lowest_modes(1)[0].eigenvalue;
+
+        for (order, num_refinements) in [(2, 2), (3, 2)] {
+            let mut mesh = cavity.mesh();
+            mesh.set_global_expansion_orders([order, order]).unwrap();
+            let study = h_convergence_study::<HierPoly, CurlCurl, L2Inner, _, _>(
+                &mesh,
+                reference,
+                num_refinements,
+                nalgebra_solve_gep,
+            )
+            .unwrap();
+
+            // asymptotically, the eigenvalue error decays as h^2p ~ N^-p
+            let asymptotic = ConvergenceStudy {
+                reference_eigenvalue: reference,
+                samples: study.samples[num_refinements - 1..].to_vec(),
+            };
+            let rate = asymptotic.algebraic_rate().unwrap();
+            assert!(
+                (rate + order as f64).abs() < 0.5,
+                "order {}: {}",
+                order,
+                rate
+            );
+            assert_eq!(study.samples.last().unwrap().h_level, num_refinements);
+        }
+    }
+
+    #[test]
+    fn p_convergence_rate() {
+        let cavity = two_layer_cavity();
+        let reference = cavity.lowest_modes(1)[0].eigenvalue;
+
+        let study = p_convergence_study::<HierPoly, CurlCurl, L2Inner, _, _>(
+            &cavity.mesh(),
+            reference,
+            &[1, 2, 3, 4, 5, 6],
+            nalgebra_solve_gep,
+        )
+        .unwrap();
+
+        assert!(study
+            .samples
+            .windows(2)
+            .all(|pair| pair[1].relative_error < pair[0].relative_error));
+        assert!(study.samples.last().unwrap().relative_error < 1e-8);
+        assert!(study.exponential_rate().unwrap() < -1.0);
+
+        study.write_csv("./test_output/p_convergence.csv").unwrap();
+        let csv = std::fs::read_to_string("./test_output/p_convergence.csv").unwrap();
+        assert_eq!(csv.lines().count(), 7);
+    }
+}
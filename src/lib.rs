@@ -1,7 +1,3 @@
-#![cfg_attr(
-    feature = "max_ortho_basis",
-    feature(const_fn_floating_point_arithmetic)
-)]
 #![doc = include_str!("../README.md")]
 
 /// Structures defining the FEM Domain, Mesh, and Basis Space
@@ -12,30 +8,85 @@ pub mod fem_problem;
 
 /// Convenient Re-Exports
 pub mod prelude {
-    pub use crate::fem_domain::basis::hierarchical_basis_fns::poly::HierPoly;
-    #[cfg(feature = "max_ortho_basis")]
-    pub use crate::fem_domain::basis::shape_fns::max_ortho::MaxOrthoShapeFn;
+    pub use crate::fem_domain::basis::hierarchical_basis_fns::{
+        chebyshev::HierChebyshev, lobatto::HierLobatto, max_ortho::HierMaxOrtho, poly::HierPoly,
+    };
+    pub use crate::fem_domain::basis::{
+        evaluate_hcurl_basis_fn, BasisEvalError, BasisFnSample, HierCurlBasisFnSpace,
+    };
     pub use crate::fem_domain::domain::{
         dof::{
-            basis_spec::{BSAddress, BasisSpec},
+            basis_spec::{BSAddress, BasisDir, BasisSpec},
             DoF,
         },
-        fields::UniformFieldSpace,
+        fields::{
+            sample_line, sample_point, sample_points, ImageSettings, PointSample, PointSampleError,
+            RasterFieldSpace, UniformFieldSpace, VtuSettings,
+        },
         mesh::{
             elem::Elem,
-            h_refinement::{HRef, HRefError},
+            element::{Element, Materials},
+            export::{MeshColoring, MeshPlotSettings},
+            h_refinement::{HRef, HRefError, MeshIdMap},
             p_refinement::{PRef, PRefError},
+            space::{Point, V2D},
             Mesh,
         },
+        raster::Colormap,
+        vtu::VtuEncoding,
         ContinuityCondition, Domain,
     };
-    pub use crate::fem_problem::galerkin::{galerkin_sample_gep_hcurl, GalerkinSamplingError};
-    pub use crate::fem_problem::integration::integrals::{curl_curl::CurlCurl, inner::L2Inner};
+    pub use crate::fem_problem::adaptive::{
+        hp_adaptive_solve, AdaptiveError, AdaptiveSettings, AdaptiveSolution, AdaptiveStep,
+    };
+    pub use crate::fem_problem::energy::{
+        integrated_quantities, solution_norms, IntegratedQuantities, IntegratedQuantityError,
+        RegionQuantities,
+    };
+    pub use crate::fem_problem::error_estimation::{
+        residual_error_indicators, ErrorEstimationError,
+    };
+    pub use crate::fem_problem::galerkin::{
+        assemble_boundary_matrix, assemble_matrix, assemble_matrix_sum, galerkin_sample_gep_hcurl,
+        galerkin_sample_rhs_hcurl, GalerkinSamplingError,
+    };
+    pub use crate::fem_problem::integration::{
+        integrals::{curl_curl::CurlCurl, inner::L2Inner},
+        IntegralSum,
+    };
     pub use crate::fem_problem::linalg::{
-        nalgebra_solve::{nalgebra_solve_gep, NalgebraGEPError},
+        mac_matrix,
+        nalgebra_solve::{
+            nalgebra_solve_gep, nalgebra_solve_gep_n, nalgebra_solve_linear, NalgebraGEPError,
+            NalgebraLinearError,
+        },
         slepc_solve::{slepc_solve_gep, SlepcGEPError},
+        sparse_matrix::{ComplexSparseMatrix, SparseMatrix},
+        sparse_solve::{sparse_solve_spd, SparseSolveError},
         EigenPair, GEP,
     };
+    pub use crate::fem_problem::manufactured::{
+        manufactured_h_study, ManufacturedError, ManufacturedErrors, ManufacturedSolution,
+    };
+    pub use crate::fem_problem::pml::{Pml, PmlDir, PmlError, PmlProfile};
+    pub use crate::fem_problem::port::{
+        Port, PortError, PortMode, PortProblem, SParameters, SPEED_OF_LIGHT,
+    };
+    pub use crate::fem_problem::projection::{
+        embed_solution, project_solution, transfer_solution, ProjectionError,
+    };
+    pub use crate::fem_problem::refinement_selection::{
+        coefficient_decay, DirResolution, RefinementChoice, RefinementSelectionError,
+        RefinementSelector,
+    };
+    pub use crate::fem_problem::sweep::{
+        material_sweep, parametric_sweep, SweepError, SweepResult, SweepSettings, SweepStep,
+    };
+    pub use crate::fem_problem::verification::{
+        h_convergence_study, p_convergence_study, CavityLayer, CavityMode, ConvergenceError,
+        ConvergenceSample, ConvergenceStudy, RectangularCavity,
+    };
+    pub use num_complex::Complex64;
 }
 
 #[cfg(test)]
@@ -59,10 +110,10 @@ mod tests {
                 .unwrap();
 
         // Solve Eigenvalue Problem
-        let solution = nalgebra_solve_gep(eigenproblem, 2.64).unwrap();
+        let solution = nalgebra_solve_gep(eigenproblem, 3.62).unwrap();
         println!("Found eigenvalue: {:.15}", solution.value);
 
-        assert!((solution.value - 2.6479657_f64).abs() < 1e-6);
+        assert!((solution.value - 3.6180459_f64).abs() < 1e-6);
         assert_eq!(solution.vector.len(), ndofs);
 
         let mut field_space = UniformFieldSpace::new(&domain, [8, 8]);